@@ -0,0 +1,54 @@
+//! Forecasts one agent's action in a crowded area, as the agent panel does while hovering.
+//!
+//! Run with `cargo bench -p signs_core --bench forecast`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use signs_core::{
+    forecast::{forecast, FORECAST_ROLLOUTS},
+    map::generate_seeded_map,
+    prelude::*,
+};
+
+const ACTIONS: [AgentAction; 4] = [
+    AgentAction::Prostelytize,
+    AgentAction::Brutalize,
+    AgentAction::Corrupt,
+    AgentAction::Sacrifice,
+];
+
+fn crowded_area(rng: &mut StdRng, players: &[PlayerId]) -> (WorldArea, AgentId) {
+    let areas = generate_seeded_map(rng.gen(), players.to_vec()).areas;
+    let mut area = areas
+        .into_iter()
+        .max_by_key(|area| area.followers.len())
+        .unwrap();
+    for follower in area.followers.iter_mut() {
+        follower.affinity = choose(rng, players);
+        follower.power = rng.gen_range(5..30);
+    }
+    let agent_id = AgentId {
+        player: players[0],
+        agent: 0,
+    };
+    area.add_agent(Agent::new(
+        generate_agent_name(rng, &Ruleset::default()),
+        agent_id,
+        area.world_position,
+        40,
+    ));
+    (area, agent_id)
+}
+
+fn forecast_actions(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1666);
+    let players: Vec<PlayerId> = (0..4).map(PlayerId).collect();
+    let (area, agent_id) = crowded_area(&mut rng, &players);
+    for action in ACTIONS {
+        c.bench_function(&format!("forecast {:?}", action), |b| {
+            b.iter(|| forecast(&area, agent_id, &action, None, &[], 7, FORECAST_ROLLOUTS))
+        });
+    }
+}
+
+criterion_group!(benches, forecast_actions);
+criterion_main!(benches);
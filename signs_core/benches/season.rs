@@ -0,0 +1,145 @@
+//! Resolves one season of a sixteen player game on a large map, and compares
+//! finding every agent through the season's index with scanning the map for them,
+//! as seasons were resolved before the index.
+//!
+//! Run with `cargo bench -p signs_core`. For the same season resolved the way it was before
+//! the index, run `cargo bench -p signs_core --features unindexed`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use signs_core::{
+    economy::Treasury,
+    map::generate_seeded_map,
+    prelude::*,
+    turns::apply_turns,
+    world::{AgentNumbers, WorldState},
+};
+
+const PLAYERS: u32 = 16;
+// Extra agents per player, as a game looks a few dozen seasons in.
+const RECRUITS: u32 = 8;
+
+fn busy_world(rng: &mut StdRng, players: &[PlayerId]) -> Vec<WorldArea> {
+    let mut areas = generate_seeded_map(rng.gen(), players.to_vec()).areas;
+    for player in players {
+        for id in 4..(4 + RECRUITS) {
+            let area = choose_mut(rng, &mut areas).unwrap();
+            area.add_agent(Agent::new(
+                generate_agent_name(rng, &Ruleset::default()),
+                AgentId {
+                    player: *player,
+                    agent: id,
+                },
+                area.world_position,
+                rng.gen_range(10..40),
+            ));
+        }
+    }
+    for area in areas.iter_mut() {
+        for follower in area.followers.iter_mut() {
+            if rng.gen_bool(0.5) {
+                follower.affinity = choose(rng, players);
+                follower.corrupted = rng.gen_bool(0.1);
+            }
+        }
+    }
+    areas
+}
+
+fn busy_turn(rng: &mut StdRng, player: PlayerId, areas: &[WorldArea]) -> PlayerTurn {
+    let mut turn = PlayerTurn::new(player);
+    for area in areas {
+        for agent in area.player_agents(player) {
+            let mut options = vec![
+                AgentAction::Prostelytize,
+                AgentAction::Brutalize,
+                AgentAction::Corrupt,
+                AgentAction::Sacrifice,
+            ];
+            if let Some(target) = area.nearest_neighbors.get(1) {
+                let name = areas
+                    .iter()
+                    .find(|area| area.world_position == *target)
+                    .unwrap()
+                    .name
+                    .clone();
+                options.push(AgentAction::Move(target.0, target.1, name));
+            }
+            options.retain(|action| action.invalid_reasons(agent, area).is_none());
+            turn.set_action(agent.id, choose(rng, &options).unwrap_or(AgentAction::None));
+        }
+    }
+    turn
+}
+
+fn resolve_season(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1666);
+    let players: Vec<PlayerId> = (0..PLAYERS).map(PlayerId).collect();
+    let areas = busy_world(&mut rng, &players);
+    let turns: Vec<PlayerTurn> = players
+        .iter()
+        .map(|player| busy_turn(&mut rng, *player, &areas))
+        .collect();
+    let seeds: Vec<u64> = players.iter().map(|_| rng.gen()).collect();
+    let ruleset = Ruleset::default();
+    let pacts = Pacts::default();
+    let agent_numbers = AgentNumbers::default();
+    let mut treasury = Treasury::default();
+    for player in players.iter() {
+        treasury.deposit(*player, 1000);
+    }
+    let name = if cfg!(feature = "unindexed") {
+        "resolve a 16 player season without the index"
+    } else {
+        "resolve a 16 player season"
+    };
+    c.bench_function(name, |b| {
+        b.iter_batched(
+            || (turns.clone(), seeds.clone(), areas.clone()),
+            |(turns, seeds, areas)| {
+                apply_turns(
+                    12,
+                    &ruleset,
+                    &pacts,
+                    &treasury,
+                    &agent_numbers,
+                    turns,
+                    seeds,
+                    areas,
+                )
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn locate_agents(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1666);
+    let players: Vec<PlayerId> = (0..PLAYERS).map(PlayerId).collect();
+    let areas = busy_world(&mut rng, &players);
+    let agents: Vec<AgentId> = areas
+        .iter()
+        .flat_map(|area| area.agents.iter().map(|agent| agent.id))
+        .collect();
+
+    c.bench_function("locate every agent by scanning", |b| {
+        b.iter(|| {
+            agents
+                .iter()
+                .filter_map(|id| {
+                    areas
+                        .iter()
+                        .find(|area| area.agents.iter().any(|agent| agent.id == *id))
+                        .map(|area| area.world_position)
+                })
+                .count()
+        })
+    });
+
+    let world = WorldState::new(areas.clone(), &AgentNumbers::default());
+    c.bench_function("locate every agent through the index", |b| {
+        b.iter(|| agents.iter().filter_map(|id| world.locate(*id)).count())
+    });
+}
+
+criterion_group!(benches, resolve_season, locate_agents);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Reports how evenly generated maps deal out their starts, worst first.
+//!
+//! Run with `cargo run -p signs_core --example fairness -- [players] [maps]`.
+
+use signs_core::{fairness::FairnessReport, map::generate_seeded_map, prelude::*};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let players: u32 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(4);
+    let maps: u64 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(100);
+
+    let mut reports: Vec<(u64, FairnessReport)> = (0..maps)
+        .map(|seed| {
+            let map = generate_seeded_map(seed, (0..players).map(PlayerId).collect());
+            (seed, FairnessReport::of(&map))
+        })
+        .collect();
+    reports.sort_by(|(_, a), (_, b)| a.balance().total_cmp(&b.balance()));
+
+    let mean = reports
+        .iter()
+        .map(|(_, report)| report.balance())
+        .sum::<f64>()
+        / maps as f64;
+    println!(
+        "{} maps for {} players, mean balance {:.2}\n",
+        maps, players, mean
+    );
+    for (seed, report) in reports.iter().take(3) {
+        println!("Seed {}:\n{}\n", seed, report);
+    }
+}
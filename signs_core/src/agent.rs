@@ -0,0 +1,289 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AgentAction {
+    None,
+    Move(u32, u32, #[serde(skip)] String),
+    Prostelytize,
+    Brutalize,
+    Corrupt,
+    CorruptAgent,
+    Sacrifice,
+    GrandSacrifice,
+    DarkCommunion,
+    Recruit,
+    Search,
+}
+
+pub const CORRUPT_POWER: u32 = 30;
+pub const JOINT_RITUAL_AGENTS: u32 = 2;
+pub const HIDE_BUTTON: &str = "HIDE";
+/// Stamina an agent spends on each attack when brutalizing the locals.
+pub const STAMINA_PER_ATTACK: u32 = 10;
+
+impl AgentAction {
+    pub fn describe(&self) -> String {
+        match self {
+            AgentAction::None => "View next agent in area, if any.".to_string(),
+            AgentAction::Move(_, _, name) => format!("Travel\n\nMove to {}.\nWhen you leave, a powerful follower in this area\nmay become an agent.", name),
+            AgentAction::Prostelytize => {
+                "Prostelytize\n\nSpread the word of darkness to the locals to gain new followers.".to_string()
+            }
+            AgentAction::Brutalize => {
+                "Brutalize\n\nBrutalize the locals, scaring away the weak\nand attracting the strong."
+                    .to_string()
+            }
+            AgentAction::Corrupt => {
+                "Corrupt\n\nAttempt to corrupt a follower,\ngaining access to greater power.".to_string()
+            }
+            AgentAction::CorruptAgent => {
+                "Corrupt Agent\n\nAttempt to corrupt this agent.\nYou will lose a corrupted follower.".to_string()
+            }
+            AgentAction::Sacrifice => {
+                "Sacrifice\n\nSacrifice a local, hoping to unlock\na Sign of Corruption.".to_string()
+            }
+            AgentAction::GrandSacrifice => {
+                "Grand Sacrifice\n\nAll your agents here join in a great offering.\nIt will surely reveal whether a sign holder is near.".to_string()
+            }
+            AgentAction::DarkCommunion => {
+                "Dark Communion\n\nAll your agents here share in the darkness.\nA corrupted agent may corrupt another,\nwithout losing a corrupted follower.".to_string()
+            }
+            AgentAction::Recruit => {
+                "Recruit\n\nRaise a strong follower here into a new agent,\nwhile this agent stays put.".to_string()
+            }
+            AgentAction::Search => {
+                "Search\n\nDig through the ruins for a relic.\nOnly the first to search will find it.".to_string()
+            }
+        }
+    }
+
+    /// A short name for the order, as listed alongside others.
+    pub fn title(&self) -> String {
+        match self {
+            AgentAction::None => "Unassigned".to_string(),
+            AgentAction::Move(_, _, name) => format!("Travel to {}", name),
+            _ => self
+                .describe()
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Joint rituals need several of a player's agents in one area, all assigned together.
+    pub fn is_joint(&self) -> bool {
+        matches!(
+            self,
+            AgentAction::GrandSacrifice | AgentAction::DarkCommunion
+        )
+    }
+
+    pub fn invalid_reasons(&self, agent: &Agent, area: &WorldArea) -> Option<String> {
+        self.allied_invalid_reasons(agent, area, &[])
+    }
+
+    pub fn allied_invalid_reasons(
+        &self,
+        agent: &Agent,
+        area: &WorldArea,
+        allies: &[PlayerId],
+    ) -> Option<String> {
+        match self {
+            AgentAction::None => None,
+            AgentAction::Move(x, y, name) => {
+                if area.has_event(WorldEvent::Storm) {
+                    Some("A storm has closed the roads out of here.".to_string())
+                } else if name.len() <= 3 {
+                    Some("Must select a location to travel to.".to_string())
+                } else if *x == area.world_position.0 && *y == area.world_position.1 {
+                    Some("Must select a different location to travel to.".to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::Prostelytize => {
+                if area.can_prostelytize(agent) {
+                    None
+                } else {
+                    Some("You find no chances in the remaining minds here.".to_string())
+                }
+            }
+            AgentAction::Brutalize => {
+                let power = area.get_allied_power(agent.id.player, allies);
+                if area.get_hostile_agent_power(agent.id.player, allies) > power {
+                    Some("A hertical agent would overpower your followers.".to_string())
+                } else if power > area.get_value() / 5 {
+                    None
+                } else {
+                    Some("The locals are not afraid of your followers.".to_string())
+                }
+            }
+            AgentAction::Corrupt => {
+                if area.get_player_power(agent.id.player) <= CORRUPT_POWER {
+                    Some(format!(
+                        "You need {} power to corrupt a follower.",
+                        CORRUPT_POWER
+                    ))
+                } else if area.corrupted_followers(agent.id.player) > 0 {
+                    Some(HIDE_BUTTON.to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::CorruptAgent => {
+                if agent.corrupted {
+                    Some(format!("{} is already corrupted.", agent.name))
+                } else if area.corrupted_followers(agent.id.player) == 0 {
+                    Some(HIDE_BUTTON.to_string())
+                } else if area.get_possible_sign_holder_count(agent.id) > 0 {
+                    Some("You cannot corrupt agents while there is still a chance to find a sign holder.\nBegin the sacrifices.".to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::Sacrifice => {
+                if area.corrupted_count(agent.id.player) == 0 {
+                    Some("You have no corrupted followers to enact sacrifices.".to_string())
+                } else if area.get_player_power(agent.id.player) <= area.get_value() / 3 {
+                    Some("The locals would stop your public sacrifice.".to_string())
+                } else if area.get_possible_sign_holder_count(agent.id) == 0 {
+                    Some("There are no possible sign holders here.\nRecruit more or search elsewhere.".to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::GrandSacrifice => {
+                if area.get_player_agent_count(agent.id.player) < JOINT_RITUAL_AGENTS {
+                    Some(HIDE_BUTTON.to_string())
+                } else if area.corrupted_count(agent.id.player) == 0 {
+                    Some("You have no corrupted followers to lead the offering.".to_string())
+                } else if area.get_player_power(agent.id.player) <= area.get_value() / 3 {
+                    Some("The locals would stop your public sacrifice.".to_string())
+                } else if area.get_possible_sign_holder_count(agent.id) == 0 {
+                    Some("There are no possible sign holders here.\nRecruit more or search elsewhere.".to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::Search => {
+                if area.kind != AreaKind::Ruins {
+                    Some(HIDE_BUTTON.to_string())
+                } else if !area.relic {
+                    Some("These ruins have already been picked clean.".to_string())
+                } else {
+                    None
+                }
+            }
+            AgentAction::Recruit => {
+                if area.can_promote(agent.id.player) {
+                    None
+                } else {
+                    Some("None of your followers here is strong enough to lead.".to_string())
+                }
+            }
+            AgentAction::DarkCommunion => {
+                if area.get_player_agent_count(agent.id.player) < JOINT_RITUAL_AGENTS {
+                    Some(HIDE_BUTTON.to_string())
+                } else if area.corrupted_agents(agent.id.player) == 0 {
+                    Some("A corrupted agent must lead the communion.".to_string())
+                } else if area.player_agents(agent.id.player).all(|a| a.corrupted) {
+                    Some("Your agents here are all corrupted already.".to_string())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Why the agent could not carry out the action once the season's toll is taken from their
+    /// stamina, as the winter cold takes it before anyone acts.
+    pub fn weary_reason(&self, agent: &Agent, ruleset: &Ruleset) -> Option<String> {
+        let needed = match self {
+            AgentAction::Prostelytize => 1,
+            AgentAction::Brutalize => STAMINA_PER_ATTACK,
+            _ => return None,
+        };
+        if agent.stamina.saturating_sub(ruleset.stamina_toll) < needed {
+            Some(format!(
+                "{} will be too worn out for this once the season takes its toll.",
+                agent.name
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The tithes it takes one agent to carry out the action. Every agent taking part in a
+    /// joint ritual pays in full.
+    pub fn cost(&self, ruleset: &Ruleset) -> u32 {
+        match self {
+            AgentAction::Corrupt | AgentAction::CorruptAgent | AgentAction::DarkCommunion => {
+                ruleset.corruption_cost
+            }
+            AgentAction::Recruit => ruleset.recruit_cost,
+            _ => 0,
+        }
+    }
+
+    pub fn sting(&self) -> &'static str {
+        match self {
+            AgentAction::Brutalize => "Brutalize.wav",
+            AgentAction::Prostelytize | AgentAction::Recruit => "Prostelytize.wav",
+            AgentAction::Sacrifice | AgentAction::GrandSacrifice => "Sacrifice.wav",
+            AgentAction::Corrupt | AgentAction::DarkCommunion => "Corrupt.wav",
+            AgentAction::Move(_, _, _) | AgentAction::Search => "Move.wav",
+            _ => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Agent {
+    pub name: String,
+    pub id: AgentId,
+    // pub world_position: (u32, u32),
+    pub power: u32,
+    pub corrupted: bool,
+    pub stamina: u32,
+    pub signs: u32,
+}
+
+impl Agent {
+    pub fn new(name: String, id: AgentId, _world_position: (u32, u32), power: u32) -> Self {
+        Self {
+            name,
+            id,
+            // world_position,
+            power,
+            corrupted: false,
+            stamina: 100,
+            signs: 0,
+        }
+    }
+
+    pub fn exhaust(&mut self, amount: u32) {
+        self.stamina = self.stamina.saturating_sub(amount);
+    }
+
+    pub fn corrupt(&mut self) {
+        self.power *= 10;
+        self.corrupted = true;
+        self.name = format!("Dark {}", self.name);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AgentId {
+    pub player: PlayerId,
+    pub agent: u32,
+}
+
+impl AgentId {
+    pub fn new(player: u32, agent: u32) -> Self {
+        Self {
+            player: PlayerId(player),
+            agent,
+        }
+    }
+}
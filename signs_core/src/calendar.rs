@@ -0,0 +1,141 @@
+//! The calendar the seasons of play fall on. Every season the moon turns a quarter, and every
+//! three seasons the year turns with it, so each phase has its own weather and its own nights.
+
+use std::fmt;
+
+use crate::prelude::*;
+
+/// How many seasons of play each season of the year lasts.
+pub const SEASONS_PER_QUARTER: i32 = 3;
+/// Stamina every agent loses to the cold before acting in winter.
+pub const WINTER_STAMINA_TOLL: u32 = 20;
+/// Stamina an agent loses on a winter road.
+pub const WINTER_TRAVEL_EXHAUSTION: u32 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum YearSeason {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MoonPhase {
+    New,
+    Waxing,
+    Full,
+    Waning,
+}
+
+/// Where a season of play falls in the year and under which moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Phase {
+    pub year_season: YearSeason,
+    pub moon: MoonPhase,
+}
+
+impl YearSeason {
+    pub fn name(&self) -> &'static str {
+        match self {
+            YearSeason::Spring => "Spring",
+            YearSeason::Summer => "Summer",
+            YearSeason::Autumn => "Autumn",
+            YearSeason::Winter => "Winter",
+        }
+    }
+
+    fn effect(&self) -> &'static str {
+        match self {
+            YearSeason::Spring => "More are born in every town.",
+            YearSeason::Summer => "Full barns pay richer tithes.",
+            YearSeason::Autumn => "Busy with the harvest, folk forget their suspicions sooner.",
+            YearSeason::Winter => "The roads are cruel: agents tire sooner, and more on the move.",
+        }
+    }
+}
+
+impl MoonPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "new moon",
+            MoonPhase::Waxing => "waxing moon",
+            MoonPhase::Full => "full moon",
+            MoonPhase::Waning => "waning moon",
+        }
+    }
+
+    fn effect(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "Dark nights hide your deeds, and suspicion fades faster.",
+            MoonPhase::Waxing => "Sign holders are born more often.",
+            MoonPhase::Full => "Corruption costs less and spreads more readily.",
+            MoonPhase::Waning => "Followers lose heart faster without an agent beside them.",
+        }
+    }
+}
+
+impl Phase {
+    /// The phase of a season of play. Play begins on the new moon of spring.
+    pub fn of(season: i32) -> Self {
+        let elapsed = season - 1;
+        let year_season = match elapsed.div_euclid(SEASONS_PER_QUARTER).rem_euclid(4) {
+            0 => YearSeason::Spring,
+            1 => YearSeason::Summer,
+            2 => YearSeason::Autumn,
+            _ => YearSeason::Winter,
+        };
+        let moon = match elapsed.rem_euclid(4) {
+            0 => MoonPhase::New,
+            1 => MoonPhase::Waxing,
+            2 => MoonPhase::Full,
+            _ => MoonPhase::Waning,
+        };
+        Self { year_season, moon }
+    }
+
+    /// The phase of the season that follows, as the report of a season's end announces it.
+    pub fn after(season: i32) -> Self {
+        Self::of(season + 1)
+    }
+
+    /// What the phase does to the rules, one line for the season and one for the moon.
+    pub fn effects(&self) -> [&'static str; 2] {
+        [self.year_season.effect(), self.moon.effect()]
+    }
+
+    /// The rules as they stand under this phase.
+    pub fn ruleset(&self, ruleset: &Ruleset) -> Ruleset {
+        let mut ruleset = ruleset.clone();
+        match self.year_season {
+            YearSeason::Spring => {
+                ruleset.city_births += 2;
+                ruleset.village_births += 1;
+            }
+            YearSeason::Summer => ruleset.tithe_rate = (ruleset.tithe_rate * 3 / 4).max(1),
+            YearSeason::Autumn => ruleset.suspicion_decay += 1,
+            YearSeason::Winter => {
+                ruleset.stamina_toll += WINTER_STAMINA_TOLL;
+                ruleset.travel_exhaustion += WINTER_TRAVEL_EXHAUSTION;
+            }
+        }
+        match self.moon {
+            MoonPhase::New => ruleset.suspicion_decay += 1,
+            MoonPhase::Waxing => {
+                ruleset.sign_holder_birth_chance = (ruleset.sign_holder_birth_chance * 2.).min(1.)
+            }
+            MoonPhase::Full => {
+                ruleset.corruption_cost = ruleset.corruption_cost.saturating_sub(1);
+                ruleset.contagion_chance = (ruleset.contagion_chance * 2.).min(1.);
+            }
+            MoonPhase::Waning => ruleset.loyalty_decay += 1,
+        }
+        ruleset
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the {} of {}", self.moon.name(), self.year_season.name())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Diplomacy {
+    Propose(PlayerId),
+    Accept(PlayerId),
+    Break(PlayerId),
+}
+
+impl Diplomacy {
+    pub fn target(&self) -> PlayerId {
+        match self {
+            Diplomacy::Propose(player) | Diplomacy::Accept(player) | Diplomacy::Break(player) => {
+                *player
+            }
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Diplomacy::Propose(_) => "Propose pact",
+            Diplomacy::Accept(_) => "Accept pact",
+            Diplomacy::Break(_) => "Break pact",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PactChange {
+    Proposed,
+    Formed,
+    Broken,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pacts {
+    /// Each alliance is stored with the lower player first.
+    pub alliances: Vec<(PlayerId, PlayerId)>,
+    /// Offers still waiting on an answer, from the first player to the second.
+    pub proposals: Vec<(PlayerId, PlayerId)>,
+}
+
+fn pact(a: PlayerId, b: PlayerId) -> (PlayerId, PlayerId) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl Pacts {
+    pub fn are_allied(&self, a: PlayerId, b: PlayerId) -> bool {
+        self.alliances.contains(&pact(a, b))
+    }
+
+    pub fn allies(&self, player: PlayerId) -> Vec<PlayerId> {
+        self.alliances
+            .iter()
+            .filter_map(|(a, b)| {
+                if *a == player {
+                    Some(*b)
+                } else if *b == player {
+                    Some(*a)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn has_proposal(&self, from: PlayerId, to: PlayerId) -> bool {
+        self.proposals.contains(&(from, to))
+    }
+
+    /// The diplomacy this player may send to another, given the current pacts.
+    pub fn available(&self, player: PlayerId, other: PlayerId) -> Diplomacy {
+        if self.are_allied(player, other) || self.has_proposal(player, other) {
+            Diplomacy::Break(other)
+        } else if self.has_proposal(other, player) {
+            Diplomacy::Accept(other)
+        } else {
+            Diplomacy::Propose(other)
+        }
+    }
+
+    /// Breaks resolve first, then acceptances, then new offers, so the order turns arrive in
+    /// never changes the outcome. Two players proposing to each other in the same season
+    /// form the pact as if one had accepted.
+    pub fn apply(&mut self, turns: &[PlayerTurn]) -> Vec<(PlayerId, PlayerId, PactChange)> {
+        let mut changes = Vec::new();
+        for turn in turns {
+            for diplomacy in turn.diplomacy.iter() {
+                if let Diplomacy::Break(other) = diplomacy {
+                    // Breaking also withdraws any offer still on the table.
+                    self.proposals
+                        .retain(|proposal| *proposal != (turn.player_id, *other));
+                    let alliance = pact(turn.player_id, *other);
+                    if self.alliances.contains(&alliance) {
+                        self.alliances.retain(|a| *a != alliance);
+                        changes.push((turn.player_id, *other, PactChange::Broken));
+                    }
+                }
+            }
+        }
+        for turn in turns {
+            for diplomacy in turn.diplomacy.iter() {
+                if let Diplomacy::Accept(other) = diplomacy {
+                    if self.has_proposal(*other, turn.player_id) {
+                        self.proposals
+                            .retain(|proposal| *proposal != (*other, turn.player_id));
+                        self.alliances.push(pact(turn.player_id, *other));
+                        changes.push((turn.player_id, *other, PactChange::Formed));
+                    }
+                }
+            }
+        }
+        for turn in turns {
+            for diplomacy in turn.diplomacy.iter() {
+                if let Diplomacy::Propose(other) = diplomacy {
+                    // A self-addressed offer never crosses itself.
+                    let crossing = *other != turn.player_id
+                        && (self.has_proposal(*other, turn.player_id)
+                            || turns.iter().any(|theirs| {
+                                theirs.player_id == *other
+                                    && theirs.get_diplomacy(turn.player_id)
+                                        == Some(Diplomacy::Propose(turn.player_id))
+                            }));
+                    if crossing && !self.are_allied(turn.player_id, *other) {
+                        let (a, b) = pact(turn.player_id, *other);
+                        self.proposals
+                            .retain(|proposal| *proposal != (a, b) && *proposal != (b, a));
+                        self.alliances.push((a, b));
+                        changes.push((a, b, PactChange::Formed));
+                    } else if *other != turn.player_id
+                        && *other != INQUISITION
+                        && !self.are_allied(turn.player_id, *other)
+                        && !self.has_proposal(turn.player_id, *other)
+                    {
+                        self.proposals.push((turn.player_id, *other));
+                        changes.push((turn.player_id, *other, PactChange::Proposed));
+                    }
+                }
+            }
+        }
+        self.alliances.sort();
+        self.proposals.sort();
+        changes
+    }
+}
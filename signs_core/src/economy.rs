@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::*;
+
+/// The tithes each player has put by.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Treasury {
+    pub balances: BTreeMap<PlayerId, u32>,
+}
+
+impl Treasury {
+    pub fn balance(&self, player: PlayerId) -> u32 {
+        self.balances.get(&player).cloned().unwrap_or(0)
+    }
+
+    pub fn deposit(&mut self, player: PlayerId, amount: u32) {
+        *self.balances.entry(player).or_default() += amount;
+    }
+
+    /// Takes the amount if the player has it, and leaves the balance alone otherwise.
+    pub fn spend(&mut self, player: PlayerId, amount: u32) -> bool {
+        let balance = self.balance(player);
+        if balance < amount {
+            return false;
+        }
+        if amount > 0 {
+            self.balances.insert(player, balance - amount);
+        }
+        true
+    }
+
+    /// Charges every action the turn can afford, in agent order, and calls off the rest.
+    pub fn charge_actions(&mut self, turn: &mut PlayerTurn, ruleset: &Ruleset) {
+        let mut agent_ids: Vec<AgentId> = turn.actions.keys().cloned().collect();
+        agent_ids.sort();
+        for agent_id in agent_ids {
+            let cost = turn.actions[&agent_id].cost(ruleset);
+            if !self.spend(turn.player_id, cost) {
+                turn.set_action(agent_id, AgentAction::None);
+            }
+        }
+    }
+
+    /// Pays out the season's tithes to every player from the areas they hold.
+    pub fn collect<'a>(
+        &mut self,
+        players: &[PlayerId],
+        areas: impl Iterator<Item = &'a WorldArea>,
+        ruleset: &Ruleset,
+    ) {
+        for area in areas {
+            if let Some(player) = controller(area).filter(|player| players.contains(player)) {
+                self.deposit(player, tithe(area, player, ruleset));
+            }
+        }
+    }
+}
+
+/// What one area pays a player each season.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tithe {
+    pub location: (u32, u32),
+    pub location_name: String,
+    pub amount: u32,
+}
+
+/// The player with more power in the area than anyone else, if there is one. The
+/// Inquisition collects no tithes.
+pub fn controller(area: &WorldArea) -> Option<PlayerId> {
+    let mut powers: BTreeMap<PlayerId, u32> = BTreeMap::new();
+    for follower in area.followers.iter() {
+        if let Some(player) = follower.affinity {
+            *powers.entry(player).or_default() += follower.power;
+        }
+    }
+    for agent in area.agents.iter() {
+        *powers.entry(agent.id.player).or_default() += agent.power;
+    }
+    powers.remove(&INQUISITION);
+    let mut powers: Vec<(u32, PlayerId)> = powers
+        .into_iter()
+        .map(|(player, power)| (power, player))
+        .collect();
+    powers.sort_by(|a, b| b.cmp(a));
+    match powers.as_slice() {
+        [(power, player)] if *power > 0 => Some(*player),
+        [(power, player), (runner_up, _), ..] if *power > *runner_up => Some(*player),
+        _ => None,
+    }
+}
+
+fn tithe(area: &WorldArea, player: PlayerId, ruleset: &Ruleset) -> u32 {
+    area.followers
+        .iter()
+        .filter(|f| f.affinity == Some(player))
+        .map(|f| f.power)
+        .sum::<u32>()
+        / ruleset.tithe_rate.max(1)
+}
+
+/// Every area that pays the player tithes, and how much.
+pub fn tithes<'a>(
+    areas: impl Iterator<Item = &'a WorldArea>,
+    player: PlayerId,
+    ruleset: &Ruleset,
+) -> Vec<Tithe> {
+    areas
+        .filter(|area| controller(area) == Some(player))
+        .map(|area| Tithe {
+            location: area.world_position,
+            location_name: area.name.clone(),
+            amount: tithe(area, player, ruleset),
+        })
+        .filter(|tithe| tithe.amount > 0)
+        .collect()
+}
+
+/// The tithes the player will collect at the end of the season.
+pub fn income<'a>(
+    areas: impl Iterator<Item = &'a WorldArea>,
+    player: PlayerId,
+    ruleset: &Ruleset,
+) -> u32 {
+    tithes(areas, player, ruleset)
+        .iter()
+        .map(|tithe| tithe.amount)
+        .sum()
+}
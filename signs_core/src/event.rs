@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use crate::{diplomacy::PactChange, prelude::*};
+
+/// Everyone with any power at a location when something happened there, lowest player first.
+pub type Witnesses = Vec<(PlayerId, u32)>;
+
+/// Everything that happened in a season, as the world saw it.
+///
+/// Nothing here is filtered for any one player. See `report::project` for what each
+/// player gets to hear about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TurnEvent {
+    PactChanged {
+        player: PlayerId,
+        other: PlayerId,
+        change: PactChange,
+    },
+    AgentMoved {
+        location: (u32, u32),
+        location_name: String,
+        agent_id: AgentId,
+        agent_name: String,
+        witnesses: Witnesses,
+    },
+    FollowerPromoted {
+        location: (u32, u32),
+        location_name: String,
+        agent_id: AgentId,
+        agent_name: String,
+    },
+    /// One agent acting alone, or the gathered agents of a joint ritual.
+    AgentActed {
+        location: (u32, u32),
+        location_name: String,
+        agents: Vec<AgentId>,
+        agent_name: String,
+        action: AgentAction,
+        success_amount: u32,
+        fail_amount: u32,
+    },
+    LocalsBrutalized {
+        location: (u32, u32),
+        location_name: String,
+        agent_id: AgentId,
+        agent_name: String,
+        swayed: u32,
+        fled: u32,
+        witnesses: Witnesses,
+    },
+    /// A player paid off the unaligned locals.
+    LocalsBribed {
+        location: (u32, u32),
+        location_name: String,
+        player: PlayerId,
+        swayed: u32,
+    },
+    /// A sacrifice turned up a sign of corruption.
+    SignFound {
+        location: (u32, u32),
+        location_name: String,
+        agent_id: AgentId,
+    },
+    FollowersConverted {
+        location: (u32, u32),
+        location_name: String,
+        player: PlayerId,
+    },
+    /// Followers whose loyalty ran out left their player, for a rival or for no one.
+    FollowersDefected {
+        location: (u32, u32),
+        location_name: String,
+        player: PlayerId,
+        defected_to: Option<PlayerId>,
+        count: u32,
+        reason: LossReason,
+    },
+    /// Newcomers were born or settled between seasons, some of them perhaps sign holders.
+    FollowersBorn {
+        location: (u32, u32),
+        location_name: String,
+        births: u32,
+        sign_holders: u32,
+        witnesses: Witnesses,
+    },
+    /// Corruption crept in from neighbouring areas, without any ritual here.
+    CorruptionSpread {
+        location: (u32, u32),
+        location_name: String,
+        player: PlayerId,
+        count: u32,
+    },
+    InquisitionArrived {
+        location: (u32, u32),
+        location_name: String,
+        witnesses: Witnesses,
+    },
+    FollowersPurged {
+        location: (u32, u32),
+        location_name: String,
+        purged: u32,
+        witnesses: Witnesses,
+    },
+    AgentCaptured {
+        location: (u32, u32),
+        location_name: String,
+        agent_id: AgentId,
+        agent_name: String,
+    },
+    WorldEventDrawn {
+        location: (u32, u32),
+        location_name: String,
+        event: WorldEvent,
+    },
+    GameOver {
+        winner: PlayerId,
+        allies: Vec<PlayerId>,
+        scores: BTreeMap<PlayerId, u32>,
+    },
+    SeasonEnded {
+        season: i32,
+    },
+}
+
+/// Why a player lost followers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LossReason {
+    /// Swayed by another player's preaching.
+    #[default]
+    Heresy,
+    /// Left without an agent for too long.
+    Neglect,
+    /// Driven off by their own player's brutality.
+    Brutality,
+}
+
+/// How much power a player had among the witnesses, if any.
+pub fn witnessed_power(witnesses: &Witnesses, player: PlayerId) -> u32 {
+    witnesses
+        .iter()
+        .find(|(witness, _)| *witness == player)
+        .map(|(_, power)| *power)
+        .unwrap_or(0)
+}
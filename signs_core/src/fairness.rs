@@ -0,0 +1,233 @@
+//! How evenly a map deals out its starts. Generated maps place agents to keep every seat's start
+//! close in value, and the report lets tools and tests check any map, generated or not.
+
+use std::{collections::BTreeMap, fmt};
+
+use rand::seq::SliceRandom;
+
+use crate::prelude::*;
+
+/// How many steps away an area counts as near a start.
+pub const NEARBY_DISTANCE: u32 = 2;
+/// What a nearby sign holder is worth, in locals.
+pub const SIGN_HOLDER_VALUE: u32 = 20;
+/// What each step of room from the nearest rival is worth, in locals.
+pub const RIVAL_DISTANCE_VALUE: u32 = 20;
+/// Rivals further off than this many steps are as good as absent.
+pub const RIVAL_DISTANCE_CAP: u32 = 6;
+/// Fresh deals of the starts to try before keeping the fairest.
+const PLACEMENT_TRIES: usize = 4;
+/// Trades between the richest and poorest seats to try on each deal.
+const TRADE_TRIES: usize = 32;
+
+/// Where each player's agents start.
+pub type Starts = BTreeMap<PlayerId, Vec<(u32, u32)>>;
+
+/// What one seat starts out with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeatReport {
+    pub player: PlayerId,
+    /// Locals in the areas near the seat's agents, each area counted once.
+    pub population: u32,
+    /// Sign holders among those locals.
+    pub sign_holders: u32,
+    /// Steps from the seat's nearest agent to the nearest rival agent, up to the cap.
+    pub rival_distance: u32,
+    /// All of the above, weighed together.
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FairnessReport {
+    pub seats: Vec<SeatReport>,
+}
+
+fn distance(a: (u32, u32), b: (u32, u32)) -> u32 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// What an area adds to a start near it, counted once rather than on every deal tried.
+struct AreaWorth {
+    position: (u32, u32),
+    locals: u32,
+    sign_holders: u32,
+}
+
+fn area_worths(areas: &[WorldArea]) -> Vec<AreaWorth> {
+    areas
+        .iter()
+        .map(|area| AreaWorth {
+            position: area.world_position,
+            locals: area.followers.len() as u32,
+            sign_holders: area.sign_holder_count(),
+        })
+        .collect()
+}
+
+fn seat_reports(areas: &[AreaWorth], starts: &Starts) -> Vec<SeatReport> {
+    starts
+        .iter()
+        .map(|(player, positions)| {
+            let (mut population, mut sign_holders) = (0, 0);
+            for area in areas.iter().filter(|area| {
+                positions
+                    .iter()
+                    .any(|start| distance(*start, area.position) <= NEARBY_DISTANCE)
+            }) {
+                population += area.locals;
+                sign_holders += area.sign_holders;
+            }
+            let rival_distance = starts
+                .iter()
+                .filter(|(rival, _)| *rival != player)
+                .flat_map(|(_, rivals)| rivals.iter())
+                .flat_map(|rival| positions.iter().map(|start| distance(*start, *rival)))
+                .min()
+                .unwrap_or(RIVAL_DISTANCE_CAP)
+                .min(RIVAL_DISTANCE_CAP);
+            SeatReport {
+                player: *player,
+                population,
+                sign_holders,
+                rival_distance,
+                value: population
+                    + sign_holders * SIGN_HOLDER_VALUE
+                    + rival_distance * RIVAL_DISTANCE_VALUE,
+            }
+        })
+        .collect()
+}
+
+impl FairnessReport {
+    /// Reports on the starts of every player with agents on the map.
+    pub fn of(map: &MapDesc) -> Self {
+        let mut starts = Starts::new();
+        for area in map.areas.iter() {
+            for agent in area.agents.iter() {
+                if agent.id.player != INQUISITION {
+                    starts
+                        .entry(agent.id.player)
+                        .or_default()
+                        .push(area.world_position);
+                }
+            }
+        }
+        Self::of_starts(&map.areas, &starts)
+    }
+
+    pub fn of_starts(areas: &[WorldArea], starts: &Starts) -> Self {
+        Self {
+            seats: seat_reports(&area_worths(areas), starts),
+        }
+    }
+
+    /// How far the richest start outweighs the poorest.
+    pub fn spread(&self) -> u32 {
+        let values = self.seats.iter().map(|seat| seat.value);
+        values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+    }
+
+    /// The poorest start's value as a share of the richest's, 1 when every seat starts alike.
+    pub fn balance(&self) -> f64 {
+        let values = self.seats.iter().map(|seat| seat.value);
+        match values.clone().max().unwrap_or(0) {
+            0 => 1.,
+            max => values.min().unwrap_or(0) as f64 / max as f64,
+        }
+    }
+
+    fn richest(&self) -> Option<PlayerId> {
+        self.seats
+            .iter()
+            .max_by_key(|seat| seat.value)
+            .map(|seat| seat.player)
+    }
+
+    fn poorest(&self) -> Option<PlayerId> {
+        self.seats
+            .iter()
+            .min_by_key(|seat| seat.value)
+            .map(|seat| seat.player)
+    }
+}
+
+impl fmt::Display for FairnessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for seat in self.seats.iter() {
+            writeln!(
+                f,
+                "Player {}: worth {} ({} locals, {} sign holders, nearest rival at {} steps)",
+                seat.player.0, seat.value, seat.population, seat.sign_holders, seat.rival_distance
+            )?;
+        }
+        write!(f, "Spread {}, balance {:.2}", self.spread(), self.balance())
+    }
+}
+
+/// Deals each player `per_seat` starting areas from `locations`, none of them shared, keeping the
+/// fairest of several deals. Each deal is evened out by trading the richest seat's starts for
+/// free areas or for the poorest seat's starts.
+pub fn place_starts(
+    rng: &mut StdRng,
+    areas: &[WorldArea],
+    locations: &[(u32, u32)],
+    players: &[PlayerId],
+    per_seat: usize,
+) -> Starts {
+    let areas = area_worths(areas);
+    let mut best: Option<(u32, Starts)> = None;
+    for _ in 0..PLACEMENT_TRIES {
+        let mut free = locations.to_vec();
+        free.shuffle(rng);
+        let mut starts: Starts = players
+            .iter()
+            .map(|player| {
+                let taken = per_seat.min(free.len());
+                (*player, free.drain(..taken).collect())
+            })
+            .collect();
+        let mut report = FairnessReport {
+            seats: seat_reports(&areas, &starts),
+        };
+        for _ in 0..TRADE_TRIES {
+            let (richest, poorest) = match (report.richest(), report.poorest()) {
+                (Some(richest), Some(poorest))
+                    if richest != poorest && !starts[&richest].is_empty() =>
+                {
+                    (richest, poorest)
+                }
+                _ => break,
+            };
+            let mut trial = starts.clone();
+            let mut trial_free = free.clone();
+            let given = rng.gen_range(0..trial[&richest].len());
+            if !trial_free.is_empty() && (trial[&poorest].is_empty() || rng.gen_bool(0.5)) {
+                let taken = rng.gen_range(0..trial_free.len());
+                std::mem::swap(
+                    &mut trial.get_mut(&richest).unwrap()[given],
+                    &mut trial_free[taken],
+                );
+            } else if !trial[&poorest].is_empty() {
+                let taken = rng.gen_range(0..trial[&poorest].len());
+                let from_richest = trial[&richest][given];
+                let from_poorest = trial[&poorest][taken];
+                trial.get_mut(&richest).unwrap()[given] = from_poorest;
+                trial.get_mut(&poorest).unwrap()[taken] = from_richest;
+            }
+            let trial_report = FairnessReport {
+                seats: seat_reports(&areas, &trial),
+            };
+            if trial_report.spread() < report.spread() {
+                starts = trial;
+                free = trial_free;
+                report = trial_report;
+            }
+        }
+        let spread = report.spread();
+        match best {
+            Some((best_spread, _)) if best_spread <= spread => {}
+            _ => best = Some((spread, starts)),
+        }
+    }
+    best.map(|(_, starts)| starts).unwrap_or_default()
+}
@@ -0,0 +1,133 @@
+//! Rehearses a planned action on copies of an area to show how it tends to turn out.
+
+use crate::prelude::*;
+
+/// Enough rehearsals for steady odds, few enough to redo while the cursor hovers.
+pub const FORECAST_ROLLOUTS: u32 = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Forecast {
+    /// The average change in the acting player's living followers here.
+    pub followers_gained: f32,
+    /// How often at least one local is sent fleeing.
+    pub flight_risk: f32,
+    /// How often the acting agents come away with a new sign.
+    pub sign_chance: f32,
+}
+
+impl Forecast {
+    pub fn describe(&self) -> String {
+        let mut lines = vec![format!("Expected followers: {:+.1}", self.followers_gained)];
+        if self.flight_risk > 0. {
+            lines.push(format!(
+                "Chance locals flee: {:.0}%",
+                self.flight_risk * 100.
+            ));
+        }
+        if self.sign_chance > 0. {
+            lines.push(format!("Chance of a sign: {:.0}%", self.sign_chance * 100.));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Plays `action` out `rollouts` times on copies of `area`, exactly as the season would.
+///
+/// Joint rituals are rehearsed with every one of the player's agents in the area, the way
+/// they are assigned. Travel has nothing to forecast.
+pub fn forecast(
+    area: &WorldArea,
+    agent_id: AgentId,
+    action: &AgentAction,
+    target: Option<FollowerId>,
+    allies: &[PlayerId],
+    seed: u64,
+    rollouts: u32,
+) -> Option<Forecast> {
+    if matches!(
+        action,
+        AgentAction::None | AgentAction::Move(..) | AgentAction::Recruit
+    ) {
+        return None;
+    }
+    let player = agent_id.player;
+    let agent_ids: Vec<AgentId> = if action.is_joint() {
+        let mut agent_ids: Vec<AgentId> = area.player_agents(player).map(|a| a.id).collect();
+        agent_ids.sort();
+        agent_ids
+    } else {
+        vec![agent_id]
+    };
+    let followers_before = living_followers(area, player);
+    let fleeing_before = fleeing(area);
+    let signs_before = signs(area, &agent_ids);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut followers_gained = 0;
+    let mut flights = 0;
+    let mut signs_found = 0;
+    for _ in 0..rollouts {
+        let mut rehearsal = area.clone();
+        match action {
+            AgentAction::None | AgentAction::Move(..) | AgentAction::Recruit => {}
+            AgentAction::Prostelytize => {
+                rehearsal.preach(agent_id, allies, &mut rng);
+            }
+            AgentAction::Brutalize => {
+                rehearsal.brutalize_locals(agent_id, allies, &mut rng);
+            }
+            AgentAction::Search => {
+                rehearsal.search_ruins(agent_id);
+            }
+            AgentAction::Corrupt => {
+                rehearsal.corrupt_followers(agent_id, target, &mut rng);
+            }
+            AgentAction::CorruptAgent => {
+                rehearsal.corrupt_agent(agent_id, &mut rng);
+            }
+            AgentAction::Sacrifice => {
+                rehearsal.sacrifice_followers(agent_id, target, &mut rng);
+            }
+            AgentAction::GrandSacrifice => {
+                rehearsal.grand_sacrifice(&agent_ids, &mut rng);
+            }
+            AgentAction::DarkCommunion => {
+                rehearsal.dark_communion(&agent_ids, &mut rng);
+            }
+        }
+        followers_gained += living_followers(&rehearsal, player) as i64 - followers_before as i64;
+        if fleeing(&rehearsal) > fleeing_before {
+            flights += 1;
+        }
+        if signs(&rehearsal, &agent_ids) > signs_before {
+            signs_found += 1;
+        }
+    }
+    let rollouts = rollouts.max(1) as f32;
+    Some(Forecast {
+        followers_gained: followers_gained as f32 / rollouts,
+        flight_risk: flights as f32 / rollouts,
+        sign_chance: signs_found as f32 / rollouts,
+    })
+}
+
+fn living_followers(area: &WorldArea, player: PlayerId) -> usize {
+    area.followers
+        .iter()
+        .filter(|f| f.affinity == Some(player) && f.power > 0)
+        .count()
+}
+
+fn fleeing(area: &WorldArea) -> usize {
+    area.followers
+        .iter()
+        .filter(|f| f.fleeing.is_some())
+        .count()
+}
+
+fn signs(area: &WorldArea, agent_ids: &[AgentId]) -> u32 {
+    area.agents
+        .iter()
+        .filter(|a| agent_ids.contains(&a.id))
+        .map(|a| a.signs)
+        .sum()
+}
@@ -0,0 +1,308 @@
+//! The lie of the land on generated maps: a coast all around, rivers running down to the sea and
+//! roads between the settlements. The sea and the rivers split the land into regions, and the
+//! roads join settlements into networks, which is what travel pays attention to.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use rand::seq::SliceRandom;
+
+use crate::{
+    map::{tile_kind, BRIDGE, RIVER, ROAD, RUINS, WATER},
+    prelude::*,
+};
+
+/// The share of a generated map above the sea.
+const LAND_SHARE: f64 = 0.65;
+/// How far the coastline wanders from a smooth curve, as a share of the island's reach.
+const COAST_JITTER: f64 = 0.35;
+/// Settlements further apart than this many steps get no road between them.
+pub const ROAD_REACH: u32 = 4;
+/// What laying a road across a river, rather than open land, costs the road builders.
+const BRIDGE_COST: u32 = 6;
+
+/// Whether the tile is sea, river or a bridge over one, none of which belong to a region.
+pub fn is_water(tile: u32) -> bool {
+    matches!(tile, WATER | RIVER | BRIDGE)
+}
+
+/// Whether the tile carries a road, bridges included.
+pub fn is_road(tile: u32) -> bool {
+    matches!(tile, ROAD | BRIDGE)
+}
+
+fn neighbours(width: usize, height: usize, idx: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (idx % width, idx / width);
+    [
+        (x > 0).then(|| idx - 1),
+        (x + 1 < width).then(|| idx + 1),
+        (y > 0).then(|| idx - width),
+        (y + 1 < height).then(|| idx + width),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Raises one or two islands out of the sea. Land comes back as tile 0, ready for terrain, and
+/// the edge of the map is always sea.
+pub fn raise_land(rng: &mut StdRng, width: usize, height: usize) -> Vec<u32> {
+    let centres = (0..rng.gen_range(1..=2))
+        .map(|_| {
+            (
+                rng.gen_range(width as f64 * 0.3..=width as f64 * 0.7),
+                rng.gen_range(height as f64 * 0.3..=height as f64 * 0.7),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut heights = (0..width * height)
+        .map(|idx| {
+            let (x, y) = ((idx % width) as f64, (idx / width) as f64);
+            if x == 0. || y == 0. || x as usize == width - 1 || y as usize == height - 1 {
+                return (f64::INFINITY, idx);
+            }
+            let reach = centres
+                .iter()
+                .map(|(cx, cy)| {
+                    (((x - cx) / (width as f64 / 2.)).powi(2)
+                        + ((y - cy) / (height as f64 / 2.)).powi(2))
+                    .sqrt()
+                })
+                .fold(f64::INFINITY, f64::min);
+            (reach + rng.gen::<f64>() * COAST_JITTER, idx)
+        })
+        .collect::<Vec<_>>();
+    heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let land = (width * height) as f64 * LAND_SHARE;
+    let mut tiles = vec![WATER; width * height];
+    for (_, idx) in heights
+        .into_iter()
+        .take(land as usize)
+        .filter(|(reach, _)| reach.is_finite())
+    {
+        tiles[idx] = 0;
+    }
+    tiles
+}
+
+/// Steps from every tile to the nearest sea or river, 0 on the water itself.
+fn distance_to_water(width: usize, height: usize, tiles: &[u32]) -> Vec<u32> {
+    let mut distances = vec![u32::MAX; tiles.len()];
+    let mut queue = VecDeque::new();
+    for (idx, tile) in tiles.iter().enumerate() {
+        if is_water(*tile) {
+            distances[idx] = 0;
+            queue.push_back(idx);
+        }
+    }
+    while let Some(idx) = queue.pop_front() {
+        for next in neighbours(width, height, idx) {
+            if distances[next] == u32::MAX {
+                distances[next] = distances[idx] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Runs rivers from the heart of the land down to the sea, or into another river.
+pub fn carve_rivers(
+    rng: &mut StdRng,
+    width: usize,
+    height: usize,
+    tiles: &mut [u32],
+    count: usize,
+) {
+    for _ in 0..count {
+        let distances = distance_to_water(width, height, tiles);
+        let springs = (0..tiles.len())
+            .filter(|idx| distances[*idx] >= 2 && distances[*idx] != u32::MAX)
+            .collect::<Vec<_>>();
+        let mut at = match choose(rng, &springs) {
+            Some(spring) => spring,
+            None => return,
+        };
+        while distances[at] > 0 {
+            tiles[at] = RIVER;
+            let mut downhill = neighbours(width, height, at)
+                .filter(|next| distances[*next] < distances[at])
+                .collect::<Vec<_>>();
+            downhill.shuffle(rng);
+            at = downhill[0];
+        }
+    }
+}
+
+/// Whether the tile lies on the coast or a riverbank.
+pub fn by_water(width: usize, height: usize, tiles: &[u32], idx: usize) -> bool {
+    neighbours(width, height, idx).any(|next| is_water(tiles[next]))
+}
+
+/// The cheapest way across the land between two tiles, preferring roads already laid, or `None`
+/// if the sea is in the way.
+fn road_path(
+    width: usize,
+    height: usize,
+    tiles: &[u32],
+    from: usize,
+    to: usize,
+) -> Option<Vec<usize>> {
+    let cost = |tile: u32| match tile {
+        WATER => None,
+        RIVER => Some(BRIDGE_COST),
+        tile if is_road(tile) || tile_kind(tile).is_some() => Some(1),
+        _ => Some(2),
+    };
+    let mut costs = vec![u32::MAX; tiles.len()];
+    let mut previous = vec![usize::MAX; tiles.len()];
+    let mut queue = BinaryHeap::new();
+    costs[from] = 0;
+    queue.push(Reverse((0, from)));
+    while let Some(Reverse((spent, idx))) = queue.pop() {
+        if idx == to {
+            let mut path = vec![to];
+            while *path.last().unwrap() != from {
+                path.push(previous[*path.last().unwrap()]);
+            }
+            return Some(path);
+        }
+        if spent > costs[idx] {
+            continue;
+        }
+        for next in neighbours(width, height, idx) {
+            if let Some(step) = cost(tiles[next]) {
+                if spent + step < costs[next] {
+                    costs[next] = spent + step;
+                    previous[next] = idx;
+                    queue.push(Reverse((spent + step, next)));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Joins nearby settlements with the shortest roads that link them all, bridging rivers where
+/// the road has to cross. Nobody builds a road to the ruins, though one may pass through them.
+pub fn lay_roads(width: usize, height: usize, tiles: &mut [u32]) {
+    let settlements = (0..tiles.len())
+        .filter(|idx| tile_kind(tiles[*idx]).is_some() && tiles[*idx] != RUINS)
+        .collect::<Vec<_>>();
+    let position = |idx: usize| ((idx % width) as u32, (idx / width) as u32);
+    let mut links = Vec::new();
+    for (a, from) in settlements.iter().enumerate() {
+        for (b, to) in settlements.iter().enumerate().skip(a + 1) {
+            let (from_x, from_y) = position(*from);
+            let (to_x, to_y) = position(*to);
+            let steps = from_x.abs_diff(to_x) + from_y.abs_diff(to_y);
+            if steps <= ROAD_REACH {
+                links.push((steps, a, b));
+            }
+        }
+    }
+    links.sort();
+    // Each settlement's network, by its first member.
+    let mut networks = (0..settlements.len()).collect::<Vec<_>>();
+    fn network(networks: &mut [usize], at: usize) -> usize {
+        let mut root = at;
+        while networks[root] != root {
+            root = networks[root];
+        }
+        networks[at] = root;
+        root
+    }
+    for (_, a, b) in links {
+        let (root_a, root_b) = (network(&mut networks, a), network(&mut networks, b));
+        if root_a == root_b {
+            continue;
+        }
+        if let Some(path) = road_path(width, height, tiles, settlements[a], settlements[b]) {
+            for idx in path {
+                tiles[idx] = match tiles[idx] {
+                    RIVER => BRIDGE,
+                    tile if tile_kind(tile).is_some() || is_road(tile) => tile,
+                    _ => ROAD,
+                };
+            }
+            networks[root_b] = root_a;
+        }
+    }
+}
+
+/// Numbers the connected stretches of tiles `joined` allows to be walked between, seeding only
+/// from tiles `seeds` allows. Tiles never reached are left `None`.
+fn label(
+    width: usize,
+    height: usize,
+    tiles: &[u32],
+    seeds: impl Fn(u32) -> bool,
+    joined: impl Fn(u32, u32) -> bool,
+) -> Vec<Option<u32>> {
+    let mut labels = vec![None; tiles.len()];
+    let mut next_label = 0;
+    for start in 0..tiles.len() {
+        if labels[start].is_some() || !seeds(tiles[start]) {
+            continue;
+        }
+        labels[start] = Some(next_label);
+        let mut queue = VecDeque::from([start]);
+        while let Some(idx) = queue.pop_front() {
+            for next in neighbours(width, height, idx) {
+                if labels[next].is_none() && joined(tiles[idx], tiles[next]) {
+                    labels[next] = Some(next_label);
+                    queue.push_back(next);
+                }
+            }
+        }
+        next_label += 1;
+    }
+    labels
+}
+
+/// Tells each area which region of land it stands in and which road network, if any, reaches it.
+pub fn fill_geography(width: u32, height: u32, tiles: &[u32], areas: &mut [WorldArea]) {
+    let (width, height) = (width as usize, height as usize);
+    let regions = label(
+        width,
+        height,
+        tiles,
+        |tile| !is_water(tile),
+        |_, next| !is_water(next),
+    );
+    // Settlements join a network through the roads, or by standing side by side, as
+    // `lay_roads` links neighbours without laying anything between them. Ruins only ever join
+    // through a road.
+    let settlement = |tile: u32| tile_kind(tile).is_some();
+    let town = |tile: u32| settlement(tile) && tile != RUINS;
+    let mut roads = label(
+        width,
+        height,
+        tiles,
+        |tile| is_road(tile) || town(tile),
+        |tile, next| {
+            (is_road(tile) && (is_road(next) || settlement(next)))
+                || (settlement(tile) && is_road(next))
+                || (town(tile) && town(next))
+        },
+    );
+    // A settlement with neither a road nor a neighbour is on no network at all.
+    let mut members: HashMap<u32, usize> = HashMap::new();
+    for network in roads.iter().flatten() {
+        *members.entry(*network).or_default() += 1;
+    }
+    for network in roads.iter_mut() {
+        if network.map(|network| members[&network]) == Some(1) {
+            *network = None;
+        }
+    }
+    for area in areas.iter_mut() {
+        let (x, y) = area.world_position;
+        let idx = x as usize + y as usize * width;
+        if idx < tiles.len() {
+            area.region = regions[idx].unwrap_or_default();
+            area.road = roads[idx];
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! The rules of Signs of Corruption, with no engine attached, so servers, tools and tests
+//! can resolve seasons exactly as the game does.
+
+pub mod agent;
+pub mod calendar;
+pub mod diplomacy;
+pub mod economy;
+pub mod event;
+pub mod fairness;
+pub mod forecast;
+pub mod geography;
+pub mod map;
+pub mod names;
+pub mod player;
+pub mod prelude;
+pub mod report;
+pub mod ruleset;
+pub mod scenario;
+pub mod turns;
+pub mod world;
+
+pub const WIN_SIGN_COUNT: u32 = 5;
@@ -0,0 +1,305 @@
+use crate::{
+    fairness::place_starts,
+    geography::{by_water, carve_rivers, fill_geography, lay_roads, raise_land},
+    names::{area_rule, default_theme, theme, NameGrammar, AGENT},
+    prelude::*,
+    WIN_SIGN_COUNT,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MapDesc {
+    pub width: u32,
+    pub height: u32,
+    pub tiles: Vec<u32>,
+    pub areas: Vec<WorldArea>,
+}
+
+impl MapDesc {
+    pub fn get_tile(&self, x: u32, y: u32) -> u32 {
+        self.tiles[(x + y * self.width) as usize]
+    }
+
+    pub fn get_area(&self, x: u32, y: u32) -> Option<&WorldArea> {
+        self.areas.iter().find(|a| a.world_position == (x, y))
+    }
+}
+
+/// How many agents each player starts with on a generated map.
+pub const STARTING_AGENTS: usize = 4;
+
+pub const VILLAGE: u32 = 3;
+pub const CITY: u32 = 4;
+pub const TEMPLE: u32 = 5;
+pub const RUINS: u32 = 6;
+pub const STRONGHOLD: u32 = 7;
+pub const WATER: u32 = 8;
+pub const RIVER: u32 = 9;
+pub const ROAD: u32 = 10;
+pub const BRIDGE: u32 = 11;
+/// How many kinds of tile there are, terrain, settlements and waterways alike.
+pub const TILE_COUNT: u32 = 12;
+
+/// The chance a generated village is a temple instead.
+const TEMPLE_CHANCE: f64 = 0.15;
+/// The chance a generated city is a stronghold instead.
+const STRONGHOLD_CHANCE: f64 = 0.2;
+/// The chance a settlement that landed away from the water looks again for a spot beside it.
+const INLAND_REROLL_CHANCE: f64 = 0.6;
+
+/// The tile an area of the given kind stands on.
+pub fn area_tile(kind: AreaKind) -> u32 {
+    match kind {
+        AreaKind::Village => VILLAGE,
+        AreaKind::City => CITY,
+        AreaKind::Temple => TEMPLE,
+        AreaKind::Ruins => RUINS,
+        AreaKind::Stronghold => STRONGHOLD,
+    }
+}
+
+/// The kind of area standing on the tile, if any does.
+pub fn tile_kind(tile: u32) -> Option<AreaKind> {
+    match tile {
+        VILLAGE => Some(AreaKind::Village),
+        CITY => Some(AreaKind::City),
+        TEMPLE => Some(AreaKind::Temple),
+        RUINS => Some(AreaKind::Ruins),
+        STRONGHOLD => Some(AreaKind::Stronghold),
+        _ => None,
+    }
+}
+
+fn generate_city_population(rng: &mut StdRng, area: &mut WorldArea) {
+    let population = rng.gen_range(80..=100);
+    let upper_class = rng.gen_range(2..=population / 10);
+    let middle_class = rng.gen_range(2..=population / 5);
+    let lower_class = population - upper_class - middle_class;
+    for _ in 0..upper_class {
+        area.add_follower(Follower::new(rng.gen_range(20..30)));
+    }
+    for _ in 0..middle_class {
+        area.add_follower(Follower::new(rng.gen_range(10..20)));
+    }
+    for _ in 0..lower_class {
+        area.add_follower(Follower::new(rng.gen_range(1..10)));
+    }
+    for _ in 0..rng.gen_range(1..=3) {
+        let sign_holder = choose_mut(rng, &mut area.followers).unwrap();
+        sign_holder.sign_holder = true;
+        sign_holder.power += 10;
+    }
+}
+
+fn generate_village_population(rng: &mut StdRng, area: &mut WorldArea) {
+    let population = rng.gen_range(30..=50);
+    let middle_class = rng.gen_range(2..=population / 5);
+    let lower_class = population - middle_class;
+    for _ in 0..middle_class {
+        area.add_follower(Follower::new(rng.gen_range(10..20)));
+    }
+    for _ in 0..lower_class {
+        area.add_follower(Follower::new(rng.gen_range(1..10)));
+    }
+    for _ in 0..rng.gen_range(1..=2) {
+        let sign_holder = choose_mut(rng, &mut area.followers).unwrap();
+        sign_holder.sign_holder = true;
+        sign_holder.power += 15;
+    }
+}
+
+/// Temples are small, but sign holders gather to them.
+fn generate_temple_population(rng: &mut StdRng, area: &mut WorldArea) {
+    let population = rng.gen_range(20..=35);
+    let middle_class = rng.gen_range(2..=population / 4);
+    let lower_class = population - middle_class;
+    for _ in 0..middle_class {
+        area.add_follower(Follower::new(rng.gen_range(10..20)));
+    }
+    for _ in 0..lower_class {
+        area.add_follower(Follower::new(rng.gen_range(1..10)));
+    }
+    for _ in 0..rng.gen_range(3..=4) {
+        let sign_holder = choose_mut(rng, &mut area.followers).unwrap();
+        sign_holder.sign_holder = true;
+        sign_holder.power += 10;
+    }
+}
+
+/// Strongholds are garrisoned by the strong.
+fn generate_stronghold_population(rng: &mut StdRng, area: &mut WorldArea) {
+    let population = rng.gen_range(40..=60);
+    let upper_class = rng.gen_range(population / 5..=population / 3);
+    let lower_class = population - upper_class;
+    for _ in 0..upper_class {
+        area.add_follower(Follower::new(rng.gen_range(20..30)));
+    }
+    for _ in 0..lower_class {
+        area.add_follower(Follower::new(rng.gen_range(5..15)));
+    }
+    let sign_holder = choose_mut(rng, &mut area.followers).unwrap();
+    sign_holder.sign_holder = true;
+    sign_holder.power += 10;
+}
+
+/// Peoples a fresh area on a settlement tile, or `None` if nobody could live there. The area is
+/// named from the grammar, avoiding and then joining the names already taken.
+pub fn generate_area(
+    rng: &mut StdRng,
+    names: &NameGrammar,
+    taken: &mut HashSet<String>,
+    x: u32,
+    y: u32,
+    tile: u32,
+) -> Option<WorldArea> {
+    let kind = tile_kind(tile)?;
+    let mut area = WorldArea::new(&names.unique(rng, area_rule(kind), taken), x, y);
+    match kind {
+        AreaKind::City => generate_city_population(rng, &mut area),
+        AreaKind::Village => generate_village_population(rng, &mut area),
+        AreaKind::Temple => generate_temple_population(rng, &mut area),
+        AreaKind::Ruins => area.relic = true,
+        AreaKind::Stronghold => generate_stronghold_population(rng, &mut area),
+    }
+    area.kind = kind;
+    Some(area)
+}
+
+/// Lists every area's neighbours, nearest first.
+pub fn fill_neighbors(areas: &mut [WorldArea]) {
+    let mut neighbors = Vec::new();
+    for _ in areas.iter() {
+        let mut my_neighbors = Vec::new();
+        for neighbor in areas.iter() {
+            my_neighbors.push(neighbor.world_position);
+        }
+        neighbors.push(my_neighbors);
+    }
+    for (idx, mut neighbors) in neighbors.drain(..).enumerate() {
+        neighbors.sort_by(|a, b| {
+            let a_dist = (a.0 as isize - areas[idx].world_position.0 as isize).abs()
+                + (a.1 as isize - areas[idx].world_position.1 as isize).abs();
+            let b_dist = (b.0 as isize - areas[idx].world_position.0 as isize).abs()
+                + (b.1 as isize - areas[idx].world_position.1 as isize).abs();
+            a_dist.cmp(&b_dist)
+        });
+        areas[idx].nearest_neighbors = neighbors;
+    }
+}
+
+/// Generates the map for these players, naming its places and agents from the rules' theme.
+pub fn generate_map(mut players: Vec<PlayerId>, ruleset: &Ruleset) -> MapDesc {
+    players.sort();
+    let seed = players
+        .iter()
+        .map(|p| p.0 as u64)
+        .reduce(|a, b| (a << 32) ^ b)
+        .unwrap_or(8675309);
+    generate_named_map(seed, players, theme(&ruleset.names))
+}
+
+/// Generates a map from an explicit seed, for tools and tests that need many different worlds.
+pub fn generate_seeded_map(seed: u64, players: Vec<PlayerId>) -> MapDesc {
+    generate_named_map(seed, players, theme(default_theme()))
+}
+
+/// Generates a map from an explicit seed, naming its places and agents from the given theme.
+pub fn generate_named_map(seed: u64, mut players: Vec<PlayerId>, names: &NameGrammar) -> MapDesc {
+    players.sort();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let width = players.len() + rng.gen_range(7..=9);
+    let height = players.len() + rng.gen_range(7..=9);
+    let mut tiles = raise_land(&mut rng, width, height);
+    let rivers = rng.gen_range(1..=2);
+    carve_rivers(&mut rng, width, height, &mut tiles, rivers);
+    let land = (0..width * height)
+        .filter(|idx| tiles[*idx] == 0)
+        .collect::<Vec<_>>();
+    let mut areas = Vec::new();
+    let min_per_player = WIN_SIGN_COUNT as usize + 2;
+    let max_per_player = min_per_player + 2;
+    let population_count =
+        rng.gen_range((players.len() * min_per_player)..(players.len() * max_per_player));
+    for _ in 0..population_count {
+        let mut idx = choose(&mut rng, &land).unwrap();
+        // Folk would rather settle on the coast or a riverbank.
+        if !by_water(width, height, &tiles, idx) && rng.gen_bool(INLAND_REROLL_CHANCE) {
+            idx = choose(&mut rng, &land).unwrap();
+        }
+        if tiles[idx] == VILLAGE {
+            tiles[idx] = CITY;
+        } else if tiles[idx] == 0 {
+            tiles[idx] = VILLAGE;
+        }
+    }
+    for tile in tiles.iter_mut() {
+        if *tile == VILLAGE && rng.gen_bool(TEMPLE_CHANCE) {
+            *tile = TEMPLE;
+        } else if *tile == CITY && rng.gen_bool(STRONGHOLD_CHANCE) {
+            *tile = STRONGHOLD;
+        }
+    }
+    // Up to a ruin for every player, wherever nobody lives.
+    for _ in 0..players.len() {
+        let idx = choose(&mut rng, &land).unwrap();
+        if tiles[idx] == 0 {
+            tiles[idx] = RUINS;
+        }
+    }
+    lay_roads(width, height, &mut tiles);
+    let mut area_names = HashSet::new();
+    for (idx, tile) in tiles.iter_mut().enumerate() {
+        if *tile == 0 {
+            *tile = rng.gen_range(0..3);
+        }
+        if tile_kind(*tile).is_some() {
+            let x = idx % width;
+            let y = idx / width;
+            if let Some(new_area) =
+                generate_area(&mut rng, names, &mut area_names, x as u32, y as u32, *tile)
+            {
+                areas.push(new_area);
+            }
+        }
+    }
+    fill_neighbors(&mut areas);
+    fill_geography(width as u32, height as u32, &tiles, &mut areas);
+    // Nobody starts out in the ruins.
+    let valid_agent_locations = areas
+        .iter()
+        .filter(|a| a.kind != AreaKind::Ruins)
+        .map(|a| a.world_position)
+        .collect::<Vec<(u32, u32)>>();
+    let starts = place_starts(
+        &mut rng,
+        &areas,
+        &valid_agent_locations,
+        &players,
+        STARTING_AGENTS,
+    );
+    let mut agent_names = HashSet::new();
+    for (player, positions) in starts.iter() {
+        for (id, (x, y)) in positions.iter().enumerate() {
+            let agent = Agent::new(
+                names.unique(&mut rng, AGENT, &mut agent_names),
+                AgentId {
+                    player: *player,
+                    agent: id as u32,
+                },
+                (*x, *y),
+                10 + id as u32 * 2,
+            );
+            areas
+                .iter_mut()
+                .find(|area| area.world_position == (*x, *y))
+                .unwrap()
+                .agents
+                .push(agent);
+        }
+    }
+    MapDesc {
+        width: width as u32,
+        height: height as u32,
+        tiles,
+        areas,
+    }
+}
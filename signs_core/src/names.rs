@@ -0,0 +1,235 @@
+//! Names for places and people, spun from a grammar loaded from an asset. Each scenario may ask
+//! for its own theme, and no map repeats a name among its areas or among its agents.
+
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use crate::prelude::*;
+
+/// The themes that ship with the game. The first is used wherever no other is asked for.
+const THEME_SOURCES: &[&str] = &[
+    include_str!("../../assets/names/kingdoms.ron"),
+    include_str!("../../assets/names/fenland.ron"),
+];
+/// How deeply rules may nest before a grammar is taken to be going round in circles.
+const MAX_DEPTH: usize = 8;
+/// How many fresh names to try before numbering one already taken.
+const FRESH_TRIES: usize = 16;
+
+pub const VILLAGE: &str = "village";
+pub const CITY: &str = "city";
+pub const TEMPLE: &str = "temple";
+pub const RUINS: &str = "ruins";
+pub const STRONGHOLD: &str = "stronghold";
+pub const AGENT: &str = "agent";
+pub const INQUISITOR: &str = "inquisitor";
+
+/// The rules every grammar must give, since the game asks for each of them.
+const REQUIRED_RULES: [&str; 7] = [VILLAGE, CITY, TEMPLE, RUINS, STRONGHOLD, AGENT, INQUISITOR];
+
+/// A theme's rules for making names. `{rule}` in an alternative expands another rule.
+///
+/// A grammar is validated as it is loaded, and its rules can't be changed afterwards, so every
+/// grammar in play can be expanded without going wrong.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "GrammarSource")]
+pub struct NameGrammar {
+    pub id: String,
+    pub name: String,
+    rules: BTreeMap<String, Vec<String>>,
+}
+
+/// A grammar as it is written, before it has been validated.
+#[derive(Deserialize)]
+struct GrammarSource {
+    id: String,
+    name: String,
+    rules: BTreeMap<String, Vec<String>>,
+}
+
+impl TryFrom<GrammarSource> for NameGrammar {
+    type Error = String;
+
+    fn try_from(source: GrammarSource) -> Result<Self, String> {
+        let grammar = Self {
+            id: source.id,
+            name: source.name,
+            rules: source.rules,
+        };
+        grammar.validate()?;
+        Ok(grammar)
+    }
+}
+
+/// The rules an alternative refers to, or an error if a brace is left open.
+fn references(alternative: &str) -> Result<Vec<&str>, String> {
+    let mut references = Vec::new();
+    let mut rest = alternative;
+    while let Some(start) = rest.find('{') {
+        match rest[start..].find('}') {
+            Some(end) => {
+                references.push(&rest[start + 1..start + end]);
+                rest = &rest[start + end + 1..];
+            }
+            None => return Err(format!("\"{}\" leaves a brace open", alternative)),
+        }
+    }
+    Ok(references)
+}
+
+/// The rule each kind of area is named by.
+pub fn area_rule(kind: AreaKind) -> &'static str {
+    match kind {
+        AreaKind::Village => VILLAGE,
+        AreaKind::City => CITY,
+        AreaKind::Temple => TEMPLE,
+        AreaKind::Ruins => RUINS,
+        AreaKind::Stronghold => STRONGHOLD,
+    }
+}
+
+/// Roman numerals, for telling apart namesakes.
+fn numeral(mut number: u32) -> String {
+    let mut numeral = String::new();
+    for (value, letters) in [(10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")] {
+        while number >= value {
+            numeral.push_str(letters);
+            number -= value;
+        }
+    }
+    numeral
+}
+
+/// The name, or if it is taken, the name numbered like a king's: "Koran II", "Koran III".
+pub fn distinct(name: String, taken: &HashSet<String>) -> String {
+    if !taken.contains(&name) {
+        return name;
+    }
+    (2..)
+        .map(|number| format!("{} {}", name, numeral(number)))
+        .find(|numbered| !taken.contains(numbered))
+        .unwrap()
+}
+
+impl NameGrammar {
+    pub fn from_ron(source: &str) -> Result<Self, String> {
+        ron::from_str(source).map_err(|err| err.to_string())
+    }
+
+    /// Each rule with the alternatives it picks from.
+    pub fn rules(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.rules
+    }
+
+    /// Checks that every rule the game asks for is given, every rule has alternatives, every
+    /// reference leads to a rule, and no rule refers back to itself.
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in REQUIRED_RULES {
+            if !self.rules.contains_key(rule) {
+                return Err(format!("{}: there is no {} rule", self.id, rule));
+            }
+        }
+        for (rule, alternatives) in self.rules.iter() {
+            if alternatives
+                .iter()
+                .all(|alternative| alternative.is_empty())
+            {
+                return Err(format!("{}: {} has nothing to choose from", self.id, rule));
+            }
+            for alternative in alternatives {
+                for reference in
+                    references(alternative).map_err(|err| format!("{}: {}", self.id, err))?
+                {
+                    if !self.rules.contains_key(reference) {
+                        return Err(format!(
+                            "{}: {} refers to {}, which is not a rule",
+                            self.id, rule, reference
+                        ));
+                    }
+                }
+            }
+        }
+        for rule in self.rules.keys() {
+            self.depth(rule, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Follows every reference down from the rule, failing if it goes too deep.
+    fn depth(&self, rule: &str, depth: usize) -> Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("{}: {} goes round in circles", self.id, rule));
+        }
+        for alternative in self.rules[rule].iter() {
+            for reference in references(alternative)? {
+                self.depth(reference, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spins a name from the rule. Empty alternatives are never picked.
+    ///
+    /// The rule must be one of the grammar's. Every reference within it is known to lead to
+    /// another rule, and every brace to close, as the grammar was validated when it was loaded.
+    pub fn expand(&self, rng: &mut StdRng, rule: &str) -> String {
+        let alternatives = self.rules[rule]
+            .iter()
+            .filter(|alternative| !alternative.is_empty())
+            .collect::<Vec<_>>();
+        let mut rest = alternatives[rng.gen_range(0..alternatives.len())].as_str();
+        let mut name = String::new();
+        while let Some(start) = rest.find('{') {
+            let end = start + rest[start..].find('}').unwrap();
+            name.push_str(&rest[..start]);
+            name.push_str(&self.expand(rng, &rest[start + 1..end]));
+            rest = &rest[end + 1..];
+        }
+        name.push_str(rest);
+        name
+    }
+
+    /// Spins a name from the rule that isn't already taken, and takes it. If the grammar keeps
+    /// coming up with taken names, the last is numbered.
+    pub fn unique(&self, rng: &mut StdRng, rule: &str, taken: &mut HashSet<String>) -> String {
+        let mut name = self.expand(rng, rule);
+        for _ in 1..FRESH_TRIES {
+            if !taken.contains(&name) {
+                break;
+            }
+            name = self.expand(rng, rule);
+        }
+        let name = distinct(name, taken);
+        taken.insert(name.clone());
+        name
+    }
+}
+
+/// Every theme that ships with the game, the default first. A theme that fails to load is
+/// left out rather than taking the game down with it.
+pub fn themes() -> &'static [NameGrammar] {
+    static THEMES: OnceLock<Vec<NameGrammar>> = OnceLock::new();
+    THEMES.get_or_init(|| {
+        THEME_SOURCES
+            .iter()
+            .filter_map(|source| NameGrammar::from_ron(source).ok())
+            .collect()
+    })
+}
+
+/// The theme with the given id, or the default if there is none.
+pub fn theme(id: &str) -> &'static NameGrammar {
+    themes()
+        .iter()
+        .find(|theme| theme.id == id)
+        .unwrap_or(&themes()[0])
+}
+
+/// The id of the theme used wherever no other is asked for.
+pub fn default_theme() -> &'static str {
+    &themes()[0].id
+}
+
+/// A name for an agent, from the theme the rules ask for.
+pub fn generate_agent_name(rng: &mut StdRng, ruleset: &Ruleset) -> String {
+    theme(&ruleset.names).expand(rng, AGENT)
+}
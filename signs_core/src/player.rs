@@ -0,0 +1,398 @@
+use crate::prelude::*;
+
+use super::agent::{HIDE_BUTTON, JOINT_RITUAL_AGENTS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct PlayerId(pub u32);
+
+/// The neutral Inquisition, moved by the simulation rather than by any player.
+pub const INQUISITION: PlayerId = PlayerId(u32::MAX);
+
+impl From<usize> for PlayerId {
+    fn from(id: usize) -> Self {
+        Self(id as u32)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerTurn {
+    pub player_id: PlayerId,
+    pub actions: HashMap<AgentId, AgentAction>,
+    /// Sent after the turn itself on the wire, see `Evokation`.
+    #[serde(skip)]
+    pub diplomacy: Vec<Diplomacy>,
+    /// Followers singled out to be promoted, sacrificed or corrupted by each agent. Also
+    /// sent after the turn itself.
+    #[serde(skip)]
+    pub targets: HashMap<AgentId, FollowerId>,
+    /// Areas where the player pays off the locals this season. Also sent after the turn.
+    #[serde(skip)]
+    pub bribes: Vec<(u32, u32)>,
+}
+
+impl PlayerTurn {
+    pub fn new(player_id: PlayerId) -> Self {
+        let actions = HashMap::default();
+        Self {
+            player_id,
+            actions,
+            diplomacy: Vec::new(),
+            targets: HashMap::new(),
+            bribes: Vec::new(),
+        }
+    }
+
+    pub fn initialize_agent(&mut self, agent_id: AgentId) {
+        if !self.actions.contains_key(&agent_id) && agent_id.player == self.player_id {
+            self.actions.insert(agent_id, AgentAction::None);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.actions.clear();
+        self.diplomacy.clear();
+        self.targets.clear();
+        self.bribes.clear();
+    }
+
+    pub fn get_diplomacy(&self, player: PlayerId) -> Option<Diplomacy> {
+        self.diplomacy
+            .iter()
+            .find(|diplomacy| diplomacy.target() == player)
+            .cloned()
+    }
+
+    pub fn set_diplomacy(&mut self, player: PlayerId, diplomacy: Option<Diplomacy>) {
+        self.diplomacy
+            .retain(|diplomacy| diplomacy.target() != player);
+        self.diplomacy.extend(diplomacy);
+    }
+
+    pub fn set_action(&mut self, agent_id: AgentId, action: AgentAction) {
+        self.actions.insert(agent_id, action);
+    }
+
+    /// Assigns a joint ritual to every agent in the group at once.
+    pub fn set_group_action(&mut self, agent_ids: &[AgentId], action: AgentAction) {
+        for agent_id in agent_ids {
+            self.set_action(*agent_id, action.clone());
+        }
+    }
+
+    /// Pulls an agent out of its joint ritual, calling the ritual off if too few remain.
+    pub fn leave_group_action(&mut self, agent_id: AgentId, agent_ids: &[AgentId]) {
+        let action = match self.get_action(agent_id) {
+            Some(action) if action.is_joint() => action,
+            _ => return,
+        };
+        self.set_action(agent_id, AgentAction::None);
+        let remaining: Vec<AgentId> = agent_ids
+            .iter()
+            .filter(|id| self.get_action(**id).as_ref() == Some(&action))
+            .cloned()
+            .collect();
+        if (remaining.len() as u32) < JOINT_RITUAL_AGENTS {
+            for id in remaining {
+                self.set_action(id, AgentAction::None);
+            }
+        }
+    }
+
+    /// Sends every agent's order back to unassigned, dropping their targets and any bribes.
+    pub fn clear_actions(&mut self) {
+        for action in self.actions.values_mut() {
+            *action = AgentAction::None;
+        }
+        self.targets.clear();
+        self.bribes.clear();
+    }
+
+    /// Singles out a follower for the agent, who otherwise picks one at random.
+    pub fn set_target(&mut self, agent_id: AgentId, target: Option<FollowerId>) {
+        match target {
+            Some(target) => {
+                self.targets.insert(agent_id, target);
+            }
+            None => {
+                self.targets.remove(&agent_id);
+            }
+        }
+    }
+
+    pub fn get_target(&self, agent_id: AgentId) -> Option<FollowerId> {
+        self.targets.get(&agent_id).cloned()
+    }
+
+    pub fn set_bribe(&mut self, location: (u32, u32), bribe: bool) {
+        self.bribes.retain(|bribed| *bribed != location);
+        if bribe {
+            self.bribes.push(location);
+        }
+    }
+
+    pub fn is_bribing(&self, location: (u32, u32)) -> bool {
+        self.bribes.contains(&location)
+    }
+
+    /// The tithes this turn's orders and bribes will cost.
+    pub fn spending(&self, ruleset: &Ruleset) -> u32 {
+        self.actions
+            .values()
+            .map(|action| action.cost(ruleset))
+            .sum::<u32>()
+            + self.bribes.len() as u32 * ruleset.bribe_cost
+    }
+
+    pub fn get_action(&self, agent_id: AgentId) -> Option<AgentAction> {
+        self.actions.get(&agent_id).cloned()
+    }
+
+    pub fn is_unassigned_player_agent(&self, agent_id: AgentId) -> bool {
+        if agent_id.player != self.player_id {
+            return false;
+        }
+        if let Some(action) = self.actions.get(&agent_id) {
+            if let AgentAction::None = action {
+                return true;
+            } else {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn get_unassigned_agents(&self) -> i32 {
+        let mut unassigned_agents = 0;
+        for (_, action) in self.actions.iter() {
+            if let AgentAction::None = action {
+                unassigned_agents += 1;
+            }
+        }
+        unassigned_agents
+    }
+
+    pub fn get_unassigned_agent(&self) -> AgentId {
+        for (agent_id, action) in self.actions.iter() {
+            if let AgentAction::None = action {
+                return *agent_id;
+            }
+        }
+        panic!("No unassigned agents");
+    }
+}
+
+/// The order changes made this season, so they can be taken back and made again.
+///
+/// Each entry holds every order as it stood before one change, however many agents the
+/// change touched, along with the followers singled out and the areas bribed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderHistory {
+    undo: Vec<Orders>,
+    redo: Vec<Orders>,
+}
+
+/// The part of a turn that the order history takes back and makes again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Orders {
+    actions: HashMap<AgentId, AgentAction>,
+    targets: HashMap<AgentId, FollowerId>,
+    bribes: Vec<(u32, u32)>,
+}
+
+impl Orders {
+    fn of(turn: &PlayerTurn) -> Self {
+        Self {
+            actions: turn.actions.clone(),
+            targets: turn.targets.clone(),
+            bribes: turn.bribes.clone(),
+        }
+    }
+
+    /// Puts these orders back into the turn, returning the ones they replace.
+    fn restore(self, turn: &mut PlayerTurn) -> Self {
+        Self {
+            actions: std::mem::replace(&mut turn.actions, self.actions),
+            targets: std::mem::replace(&mut turn.targets, self.targets),
+            bribes: std::mem::replace(&mut turn.bribes, self.bribes),
+        }
+    }
+}
+
+impl OrderHistory {
+    /// Makes a change to the orders, remembering it only if something actually changed.
+    pub fn record<R>(&mut self, turn: &mut PlayerTurn, f: impl FnOnce(&mut PlayerTurn) -> R) -> R {
+        let before = Orders::of(turn);
+        let result = f(turn);
+        if Orders::of(turn) != before {
+            self.undo.push(before);
+            self.redo.clear();
+        }
+        result
+    }
+
+    pub fn undo(&mut self, turn: &mut PlayerTurn) -> bool {
+        match self.undo.pop() {
+            Some(orders) => {
+                self.redo.push(orders.restore(turn));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self, turn: &mut PlayerTurn) -> bool {
+        match self.redo.pop() {
+            Some(orders) => {
+                self.undo.push(orders.restore(turn));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// Switches a standing order to another action once the player is strong enough.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Until {
+    /// The player's power in the agent's area that must be passed.
+    pub power: u32,
+    pub then: AgentAction,
+}
+
+/// An order given again every season until it can no longer be carried out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StandingOrder {
+    pub action: AgentAction,
+    pub until: Option<Until>,
+}
+
+impl StandingOrder {
+    pub fn repeat(action: AgentAction) -> Self {
+        Self {
+            action,
+            until: None,
+        }
+    }
+
+    pub fn until(action: AgentAction, power: u32, then: AgentAction) -> Self {
+        Self {
+            action,
+            until: Some(Until { power, then }),
+        }
+    }
+
+    /// The order this stands for right now, having switched over if its condition is met.
+    pub fn current(&self, agent: &Agent, area: &WorldArea) -> StandingOrder {
+        match &self.until {
+            Some(until) if area.get_player_power(agent.id.player) > until.power => {
+                StandingOrder::repeat(until.then.clone())
+            }
+            _ => self.clone(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.until {
+            Some(until) => format!(
+                "{} until power > {}, then {}",
+                self.action.title(),
+                until.power,
+                until.then.title()
+            ),
+            None => format!("{}, every season", self.action.title()),
+        }
+    }
+}
+
+/// A standing order that could not be given this season.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LapsedOrder {
+    pub order: StandingOrder,
+    pub reason: String,
+}
+
+/// The orders a player has left standing, and those that lapsed this season.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StandingOrders {
+    orders: HashMap<AgentId, StandingOrder>,
+    #[serde(skip)]
+    lapsed: HashMap<AgentId, LapsedOrder>,
+}
+
+impl StandingOrders {
+    pub fn get(&self, agent_id: AgentId) -> Option<&StandingOrder> {
+        self.orders.get(&agent_id)
+    }
+
+    pub fn set(&mut self, agent_id: AgentId, order: Option<StandingOrder>) {
+        match order {
+            Some(order) => {
+                self.orders.insert(agent_id, order);
+                self.lapsed.remove(&agent_id);
+            }
+            None => {
+                self.orders.remove(&agent_id);
+            }
+        }
+    }
+
+    pub fn lapsed(&self, agent_id: AgentId) -> Option<&LapsedOrder> {
+        self.lapsed.get(&agent_id)
+    }
+
+    /// Gives every standing order for the coming season.
+    ///
+    /// Orders that fail `invalid_reasons`, or that the agent is too weary for under the coming
+    /// season's `ruleset`, are dropped and kept as lapsed until the next season. Orders for
+    /// agents no longer on the board are simply dropped.
+    pub fn issue<'a>(
+        &mut self,
+        turn: &mut PlayerTurn,
+        areas: impl IntoIterator<Item = &'a WorldArea>,
+        allies: &[PlayerId],
+        ruleset: &Ruleset,
+    ) {
+        self.lapsed.clear();
+        let mut remaining = HashMap::new();
+        for area in areas {
+            for agent in area.player_agents(turn.player_id) {
+                let order = match self.orders.remove(&agent.id) {
+                    Some(order) => order.current(agent, area),
+                    None => continue,
+                };
+                match order
+                    .action
+                    .allied_invalid_reasons(agent, area, allies)
+                    .or_else(|| order.action.weary_reason(agent, ruleset))
+                {
+                    Some(reason) => {
+                        let reason = if reason == HIDE_BUTTON {
+                            format!("{} is no longer possible here.", order.action.title())
+                        } else {
+                            reason
+                        };
+                        self.lapsed.insert(agent.id, LapsedOrder { order, reason });
+                    }
+                    None => {
+                        turn.set_action(agent.id, order.action.clone());
+                        remaining.insert(agent.id, order);
+                    }
+                }
+            }
+        }
+        self.orders = remaining;
+    }
+}
@@ -0,0 +1,49 @@
+pub use crate::agent::{Agent, AgentAction, AgentId};
+pub use crate::diplomacy::{Diplomacy, Pacts};
+pub use crate::map::{generate_map, MapDesc};
+pub use crate::names::generate_agent_name;
+pub use crate::player::PlayerId;
+pub use crate::player::PlayerTurn;
+pub use crate::player::INQUISITION;
+pub use crate::ruleset::Ruleset;
+pub use crate::world::{AreaKind, Follower, FollowerId, WorldArea, WorldEvent};
+pub use rand::Rng;
+pub use rand::{rngs::StdRng, SeedableRng};
+pub use serde::{Deserialize, Serialize};
+pub use std::collections::{HashMap, HashSet};
+
+pub fn walking_choose<T: Clone>(rng: &mut StdRng, choices: &[T]) -> Option<T> {
+    for choice in choices {
+        if rng.gen_bool(0.5) {
+            return Some(choice.clone());
+        }
+    }
+    None
+}
+
+pub fn choose<T: Clone>(rng: &mut StdRng, choices: &[T]) -> Option<T> {
+    if choices.is_empty() {
+        return None;
+    }
+    let index = rng.gen_range(0..choices.len());
+    Some(choices[index].clone())
+}
+
+pub fn choose_mut<'a, T>(rng: &mut StdRng, choices: &'a mut [T]) -> Option<&'a mut T> {
+    if choices.is_empty() {
+        return None;
+    }
+    let index = rng.gen_range(0..choices.len());
+    Some(&mut choices[index])
+}
+
+pub fn choose_mut_iter<'a, T, I>(rng: &mut StdRng, mut choices: I, size: usize) -> Option<&'a mut T>
+where
+    I: Iterator<Item = &'a mut T>,
+{
+    if size == 0 {
+        return None;
+    }
+    let index = rng.gen_range(0..size);
+    choices.nth(index)
+}
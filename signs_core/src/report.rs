@@ -0,0 +1,831 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    calendar::Phase,
+    diplomacy::PactChange,
+    event::{witnessed_power, LossReason, TurnEvent},
+    prelude::*,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TurnReportEvent {
+    GameStart {
+        player_names: Vec<String>,
+    },
+    AgentAction {
+        location: (u32, u32),
+        location_name: String,
+        agent_name: String,
+        action: AgentAction,
+        success_amount: u32,
+        fail_amount: u32,
+    },
+    Brutalized {
+        location: (u32, u32),
+        location_name: String,
+        dead: u32,
+        fleeing: u32,
+    },
+    Bribed {
+        location: (u32, u32),
+        location_name: String,
+        swayed: u32,
+    },
+    Sacrificed {
+        location: (u32, u32),
+        location_name: String,
+        follower: bool,
+    },
+    PromotedFollower {
+        location: (u32, u32),
+        location_name: String,
+        agent_name: String,
+    },
+    AgentSeen {
+        location: (u32, u32),
+        location_name: String,
+        power: u32,
+    },
+    SignSeen {
+        location: (u32, u32),
+        location_name: String,
+        mine: bool,
+    },
+    FollowersLost {
+        location: (u32, u32),
+        location_name: String,
+        #[serde(default)]
+        reason: LossReason,
+        #[serde(default)]
+        count: u32,
+        #[serde(default)]
+        defected_to: Option<PlayerId>,
+    },
+    Births {
+        location: (u32, u32),
+        location_name: String,
+        births: u32,
+        sign_holders: u32,
+    },
+    CorruptionSpread {
+        location: (u32, u32),
+        location_name: String,
+        count: u32,
+    },
+    InquisitionArrived {
+        location: (u32, u32),
+        location_name: String,
+    },
+    FollowersPurged {
+        location: (u32, u32),
+        location_name: String,
+        purged: u32,
+    },
+    AgentCaptured {
+        location: (u32, u32),
+        location_name: String,
+        agent_name: String,
+    },
+    SeasonEvent {
+        location: (u32, u32),
+        location_name: String,
+        event: WorldEvent,
+    },
+    PactProposed {
+        player: PlayerId,
+    },
+    PactFormed {
+        players: (PlayerId, PlayerId),
+    },
+    Betrayal {
+        betrayer: PlayerId,
+        betrayed: PlayerId,
+    },
+    GameOver {
+        winner: PlayerId,
+        #[serde(default)]
+        allies: Vec<PlayerId>,
+        scores: BTreeMap<PlayerId, u32>,
+    },
+    NewTurn {
+        turn: i32,
+    },
+}
+
+impl TurnReportEvent {
+    pub fn get_title(&self) -> String {
+        match self {
+            TurnReportEvent::GameStart { .. } => "Campaign Start".to_string(),
+            TurnReportEvent::AgentAction {
+                agent_name, action, ..
+            } => match action {
+                AgentAction::Brutalize => "Brutality".to_string(),
+                AgentAction::Corrupt => "Corruption".to_string(),
+                AgentAction::CorruptAgent => "Agent Corruption".to_string(),
+                AgentAction::Sacrifice => "Sacrifice".to_string(),
+                AgentAction::GrandSacrifice => "Grand Sacrifice".to_string(),
+                AgentAction::DarkCommunion => "Dark Communion".to_string(),
+                AgentAction::Prostelytize => "Prostelytizing".to_string(),
+                AgentAction::Recruit => "Recruitment".to_string(),
+                AgentAction::Search => "Searching the ruins".to_string(),
+                AgentAction::Move(_, _, _) => {
+                    format!("{} arrived", agent_name)
+                }
+                AgentAction::None => "???".to_string(),
+            },
+            TurnReportEvent::Brutalized { .. } => "Violence erupts!".to_string(),
+            TurnReportEvent::Bribed { .. } => "Bribes paid".to_string(),
+            TurnReportEvent::FollowersLost { reason, .. } => match reason {
+                LossReason::Heresy => "Heretics!".to_string(),
+                LossReason::Neglect => "Followers drift away".to_string(),
+                LossReason::Brutality => "Followers turn away".to_string(),
+            },
+            TurnReportEvent::Births { .. } => "Newcomers".to_string(),
+            TurnReportEvent::CorruptionSpread { .. } => "Corruption spreads".to_string(),
+            TurnReportEvent::InquisitionArrived { .. } => "The Inquisition!".to_string(),
+            TurnReportEvent::FollowersPurged { .. } => "Purge!".to_string(),
+            TurnReportEvent::AgentCaptured { agent_name, .. } => {
+                format!("{} captured!", agent_name)
+            }
+            TurnReportEvent::Sacrificed { follower, .. } => {
+                if *follower {
+                    "Follower sacrificed!".to_string()
+                } else {
+                    "Body found!".to_string()
+                }
+            }
+            TurnReportEvent::PromotedFollower {
+                location_name: _,
+                agent_name,
+                ..
+            } => {
+                format!("New agent: {}", agent_name)
+            }
+            TurnReportEvent::AgentSeen { .. } => "Agent Spotted!".to_string(),
+            TurnReportEvent::SignSeen { .. } => "Sign of Corruption!".to_string(),
+            TurnReportEvent::SeasonEvent { event, .. } => event.name().to_string(),
+            TurnReportEvent::PactProposed { .. } => "A pact is offered".to_string(),
+            TurnReportEvent::PactFormed { .. } => "A pact is sealed".to_string(),
+            TurnReportEvent::Betrayal { .. } => "Betrayal!".to_string(),
+            TurnReportEvent::GameOver { winner: _, .. } => "Game Over".to_string(),
+            TurnReportEvent::NewTurn { turn } => format!("Season {}", turn),
+        }
+    }
+
+    pub fn get_location(&self) -> Option<(u32, u32)> {
+        match self {
+            TurnReportEvent::GameStart { .. } => None,
+            TurnReportEvent::AgentSeen { location, .. } => Some(*location),
+            TurnReportEvent::PromotedFollower { location, .. } => Some(*location),
+            TurnReportEvent::AgentAction { location, .. } => Some(*location),
+            TurnReportEvent::Brutalized { location, .. } => Some(*location),
+            TurnReportEvent::Bribed { location, .. } => Some(*location),
+            TurnReportEvent::FollowersLost { location, .. } => Some(*location),
+            TurnReportEvent::Births { location, .. } => Some(*location),
+            TurnReportEvent::CorruptionSpread { location, .. } => Some(*location),
+            TurnReportEvent::InquisitionArrived { location, .. } => Some(*location),
+            TurnReportEvent::FollowersPurged { location, .. } => Some(*location),
+            TurnReportEvent::AgentCaptured { location, .. } => Some(*location),
+            TurnReportEvent::Sacrificed { location, .. } => Some(*location),
+            TurnReportEvent::SignSeen { location, .. } => Some(*location),
+            TurnReportEvent::SeasonEvent { location, .. } => Some(*location),
+            TurnReportEvent::PactProposed { .. } => None,
+            TurnReportEvent::PactFormed { .. } => None,
+            TurnReportEvent::Betrayal { .. } => None,
+            TurnReportEvent::GameOver { .. } => None,
+            TurnReportEvent::NewTurn { .. } => None,
+        }
+    }
+
+    pub fn get_sections(&self) -> Vec<String> {
+        match self {
+            TurnReportEvent::GameStart { player_names } => vec![format!(
+                "The campaign has begun! {} players have joined.",
+                player_names.len()
+            )],
+            TurnReportEvent::AgentAction {
+                location_name,
+                agent_name,
+                action,
+                success_amount,
+                fail_amount,
+                ..
+            } => match action {
+                AgentAction::Brutalize => vec![
+                    format!(
+                        "{} brutalized the locals at {}.\n",
+                        agent_name, location_name
+                    ),
+                    if *success_amount > 0 {
+                        format!(
+                            "{} locals were swayed, growing your power\n",
+                            success_amount
+                        )
+                    } else {
+                        "Your brutality failed.\n".to_string()
+                    },
+                    if *fail_amount > 0 {
+                        format!(
+                            "{} locals fled or died, limiting your growth.\n",
+                            fail_amount
+                        )
+                    } else {
+                        String::new()
+                    },
+                ],
+                AgentAction::Corrupt => vec![
+                    format!(
+                        "{} enacted a corruption ritual at {}.\n",
+                        agent_name, location_name
+                    ),
+                    if *success_amount > 0 {
+                        format!("Your ritual succeded.\nAfollower is now corrupted.\nYour power at {} grows.\n", location_name)
+                    } else {
+                        format!(
+                            "Your ritual failed.\nYour power at {} wanes.\n",
+                            location_name
+                        )
+                    },
+                    // Actually, these are signs seen, not failures.
+                    if *fail_amount > 0 {
+                        "Something wondrous happened...\n".to_string()
+                    } else {
+                        String::new()
+                    },
+                ],
+                AgentAction::CorruptAgent => vec![
+                    format!(
+                        "{} enacted a corruption ritual at {}.\n",
+                        agent_name, location_name
+                    ),
+                    if *success_amount > 0 {
+                        format!("Your ritual succeded.\n{} is now corrupted!\n", agent_name)
+                    } else {
+                        "Your ritual failed.\nYour corrupted follower parished.\n".to_string()
+                    },
+                    // Actually, these are signs seen, not failures.
+                    if *fail_amount > 0 {
+                        "Something wondrous happened...\n".to_string()
+                    } else {
+                        String::new()
+                    },
+                ],
+                AgentAction::Sacrifice => vec![
+                    format!(
+                        "{} sacrificed a follower at {}.\n\n",
+                        agent_name, location_name
+                    ),
+                    {
+                        if *success_amount > 0 {
+                            "Your sacrifice has brought untold power!\n".to_string()
+                        } else {
+                            "Your ritual failed.".to_string()
+                        }
+                    },
+                    {
+                        if *fail_amount > 0 {
+                            format!(
+                                "Your actions send {} potential sign holder{} fleeing.\n",
+                                *fail_amount,
+                                if *fail_amount > 1 { "s" } else { "" }
+                            )
+                        } else {
+                            String::new()
+                        }
+                    },
+                ],
+                AgentAction::GrandSacrifice => vec![
+                    format!(
+                        "{} led a grand sacrifice at {}.\n\n",
+                        agent_name, location_name
+                    ),
+                    if *success_amount > 0 {
+                        "A sign holder was offered up!\nIts power is yours.\n".to_string()
+                    } else {
+                        "No sign holder was among your followers.\n".to_string()
+                    },
+                    // Actually, these are the sign holders left in the area.
+                    if *fail_amount > 0 {
+                        format!(
+                            "The ritual reveals {} sign holder{} still hiding here.\n",
+                            *fail_amount,
+                            if *fail_amount > 1 { "s" } else { "" }
+                        )
+                    } else {
+                        "The ritual reveals no sign holder left here.\n".to_string()
+                    },
+                ],
+                AgentAction::DarkCommunion => vec![
+                    format!(
+                        "{} shared a dark communion at {}.\n",
+                        agent_name, location_name
+                    ),
+                    if *success_amount > 0 {
+                        "The darkness took hold.\nAnother agent is corrupted!\n".to_string()
+                    } else {
+                        "The darkness did not take hold.\n".to_string()
+                    },
+                ],
+                AgentAction::Search => vec![
+                    format!("{} searched the ruins of {}.\n\n", agent_name, location_name),
+                    if *success_amount > 0 {
+                        "A relic was unearthed!\nIt bears a Sign of Corruption.\n".to_string()
+                    } else {
+                        "Nothing of worth was left to find.\n".to_string()
+                    },
+                ],
+                AgentAction::Recruit => vec![format!(
+                    "{} raised a new agent at {}.",
+                    agent_name, location_name
+                )],
+                AgentAction::Prostelytize => vec![
+                    format!("{} has heard the darkness.\n\n", location_name),
+                    format!("{} followers were gained.\n", success_amount),
+                    format!("{} were too strong to convert, yet.\n", fail_amount),
+                ],
+                AgentAction::Move(_, _, _) => {
+                    vec![format!("{} arrived at\n{}", agent_name, location_name)]
+                }
+                AgentAction::None => vec![format!("???")],
+            },
+            TurnReportEvent::Brutalized {
+                location_name,
+                dead,
+                fleeing,
+                ..
+            } => vec![
+                format!("Heretics enact brutality at {}.\n", location_name),
+                format!("{} locals joined the attackers.\n", dead),
+                format!("{} locals fled or died.\n", fleeing),
+            ],
+            TurnReportEvent::Bribed {
+                location_name,
+                swayed,
+                ..
+            } => vec![
+                format!("Tithes changed hands at {}.\n\n", location_name),
+                format!("{} locals took the money and joined you.\n", swayed),
+            ],
+            TurnReportEvent::Sacrificed {
+                location_name,
+                follower,
+                ..
+            } => {
+                if *follower {
+                    vec![format!("A follower was sacrificed at {}.", location_name)]
+                } else {
+                    vec![format!("A sacrifice was made at {}.", location_name)]
+                }
+            }
+            TurnReportEvent::FollowersLost {
+                location_name,
+                reason,
+                count,
+                defected_to,
+                ..
+            } => {
+                let plural = if *count > 1 { "s" } else { "" };
+                let gone = match defected_to {
+                    Some(rival) => format!("They now follow Player {}.\n", rival.0 + 1),
+                    None => "They now follow no one.\n".to_string(),
+                };
+                match reason {
+                    LossReason::Heresy => vec![format!(
+                        "Your followers are being swayed by heretical ideas at {}.",
+                        location_name
+                    )],
+                    LossReason::Neglect => vec![
+                        format!(
+                            "{} follower{} at {} lost faith without an agent to guide them.\n\n",
+                            count, plural, location_name
+                        ),
+                        gone,
+                    ],
+                    LossReason::Brutality => vec![
+                        format!(
+                            "{} follower{} at {} turned from your brutality.\n\n",
+                            count, plural, location_name
+                        ),
+                        gone,
+                    ],
+                }
+            }
+            TurnReportEvent::Births {
+                location_name,
+                births,
+                sign_holders,
+                ..
+            } => vec![
+                format!(
+                    "{} newcomer{} settled at {}, loyal to no one yet.\n",
+                    births,
+                    if *births > 1 { "s" } else { "" },
+                    location_name
+                ),
+                if *sign_holders > 0 {
+                    "One of them was born bearing a sign.\n".to_string()
+                } else {
+                    String::new()
+                },
+            ],
+            TurnReportEvent::CorruptionSpread {
+                location_name,
+                count,
+                ..
+            } => vec![
+                format!(
+                    "The darkness has crept into {} of your followers at {}.\n\n",
+                    count, location_name
+                ),
+                format!("No ritual was performed there.\nIt seeped in from nearby.\n"),
+            ],
+            TurnReportEvent::InquisitionArrived { location_name, .. } => vec![
+                format!("Inquisitors have arrived at {}.\n\n", location_name),
+                format!("Rumors of dark rites have reached the faithful.\nTread carefully.\n"),
+            ],
+            TurnReportEvent::FollowersPurged {
+                location_name,
+                purged,
+                ..
+            } => vec![format!(
+                "The Inquisition purged {} corrupted soul{} at {}.",
+                purged,
+                if *purged > 1 { "s" } else { "" },
+                location_name
+            )],
+            TurnReportEvent::AgentCaptured {
+                location_name,
+                agent_name,
+                ..
+            } => vec![
+                format!(
+                    "{} was seized by the Inquisition at {}.\n\n",
+                    agent_name, location_name
+                ),
+                format!("Their power and their signs are lost to you.\n"),
+            ],
+            TurnReportEvent::AgentSeen { location_name, .. } => vec![
+                format!("An unknown traveler arrives at {}.\n", location_name),
+                format!("Their heretical words capture the attention of your followers.\n"),
+            ],
+            TurnReportEvent::PromotedFollower {
+                location_name,
+                agent_name,
+                ..
+            } => {
+                vec![format!(
+                    "With the absense of leadership at {}, {} has been promoted to an agent!",
+                    location_name, agent_name
+                )]
+            }
+            TurnReportEvent::SignSeen {
+                location_name,
+                mine,
+                ..
+            } => {
+                if *mine {
+                    vec![format!(
+                        "You discovered a Sign of Corruption at {}!\n\nThe coming darkness is nearer!",
+                        location_name
+                    )]
+                } else {
+                    vec![format!(
+                        "The heretics at {} have discovered something...",
+                        location_name
+                    )]
+                }
+            }
+            TurnReportEvent::SeasonEvent {
+                location_name,
+                event,
+                ..
+            } => vec![
+                format!("{} at {}!\n\n", event.name(), location_name),
+                format!("{}\n", event.describe()),
+            ],
+            TurnReportEvent::PactProposed { player } => vec![
+                format!("Player {} offers you a pact.\n\n", player.0 + 1),
+                format!("Accept it before you next evoke the darkness,\nor let it stand unanswered.\n"),
+            ],
+            TurnReportEvent::PactFormed { players } => vec![
+                format!(
+                    "Player {} and Player {} have sealed a pact.\n\n",
+                    players.0 .0 + 1,
+                    players.1 .0 + 1
+                ),
+                format!("Their agents will not steal each other's followers,\nand they may share in victory.\n"),
+            ],
+            TurnReportEvent::Betrayal { betrayer, betrayed } => vec![
+                format!(
+                    "Player {} has broken their pact with Player {}.\n\n",
+                    betrayer.0 + 1,
+                    betrayed.0 + 1
+                ),
+                format!("Their followers are fair game once more.\n"),
+            ],
+            TurnReportEvent::GameOver {
+                winner,
+                allies,
+                scores,
+            } => {
+                if allies.is_empty() {
+                    vec![format!(
+                        "Game Over! Player {} wins with {} points!",
+                        winner.0 + 1,
+                        scores.get(winner).unwrap()
+                    )]
+                } else {
+                    vec![format!(
+                        "Game Over! Player {} and their allies win with {} points!",
+                        winner.0 + 1,
+                        scores.get(winner).unwrap_or(&0)
+                            + allies
+                                .iter()
+                                .map(|ally| scores.get(ally).unwrap_or(&0))
+                                .sum::<u32>()
+                    )]
+                }
+            }
+            TurnReportEvent::NewTurn { turn } => {
+                let phase = Phase::after(*turn);
+                let [year_effect, moon_effect] = phase.effects();
+                vec![
+                    format!("A new season begins!\n\n"),
+                    format!(
+                        "It has been {} seasons since your campaign has begun.\n\n",
+                        turn
+                    ),
+                    format!(
+                        "It is {}.\n{}\n{}",
+                        phase, year_effect, moon_effect
+                    ),
+                ]
+            }
+        }
+    }
+}
+
+/// What one player hears of a season: their own agents' deeds, and whatever happened
+/// where they had enough power to notice.
+pub fn project(events: &[TurnEvent], player: PlayerId) -> Vec<TurnReportEvent> {
+    events
+        .iter()
+        .flat_map(|event| project_event(event, player))
+        .collect()
+}
+
+fn project_event(event: &TurnEvent, player: PlayerId) -> Vec<TurnReportEvent> {
+    match event {
+        TurnEvent::PactChanged {
+            player: proposer,
+            other,
+            change,
+        } => match change {
+            PactChange::Proposed if player == *other => {
+                vec![TurnReportEvent::PactProposed { player: *proposer }]
+            }
+            PactChange::Formed if player == *proposer || player == *other => {
+                vec![TurnReportEvent::PactFormed {
+                    players: (*other, *proposer),
+                }]
+            }
+            // Everyone hears of a betrayal.
+            PactChange::Broken => vec![TurnReportEvent::Betrayal {
+                betrayer: *proposer,
+                betrayed: *other,
+            }],
+            _ => vec![],
+        },
+        TurnEvent::AgentMoved {
+            location,
+            location_name,
+            agent_id,
+            agent_name,
+            witnesses,
+        } => {
+            if player == agent_id.player {
+                vec![TurnReportEvent::AgentAction {
+                    location: *location,
+                    location_name: location_name.clone(),
+                    agent_name: agent_name.clone(),
+                    action: AgentAction::Move(location.0, location.1, location_name.clone()),
+                    success_amount: 0,
+                    fail_amount: 0,
+                }]
+            } else if witnessed_power(witnesses, player) > 10 {
+                vec![TurnReportEvent::AgentSeen {
+                    location: *location,
+                    location_name: location_name.clone(),
+                    power: 0,
+                }]
+            } else {
+                vec![]
+            }
+        }
+        TurnEvent::FollowerPromoted {
+            location,
+            location_name,
+            agent_id,
+            agent_name,
+        } if player == agent_id.player => vec![TurnReportEvent::PromotedFollower {
+            location: *location,
+            location_name: location_name.clone(),
+            agent_name: agent_name.clone(),
+        }],
+        TurnEvent::AgentActed {
+            location,
+            location_name,
+            agents,
+            agent_name,
+            action,
+            success_amount,
+            fail_amount,
+        } => {
+            let sign_seen = |mine| TurnReportEvent::SignSeen {
+                location: *location,
+                location_name: location_name.clone(),
+                mine,
+            };
+            let body_found = || TurnReportEvent::Sacrificed {
+                location: *location,
+                location_name: location_name.clone(),
+                follower: false,
+            };
+            if agents.iter().any(|agent_id| agent_id.player == player) {
+                let mut reports = vec![TurnReportEvent::AgentAction {
+                    location: *location,
+                    location_name: location_name.clone(),
+                    agent_name: agent_name.clone(),
+                    action: action.clone(),
+                    success_amount: *success_amount,
+                    fail_amount: *fail_amount,
+                }];
+                match action {
+                    AgentAction::Corrupt if *fail_amount > 0 => reports.push(sign_seen(true)),
+                    AgentAction::GrandSacrifice if *success_amount > 0 => {
+                        reports.push(sign_seen(true))
+                    }
+                    _ => {}
+                }
+                reports
+            } else {
+                match action {
+                    // A corruption reports the signs it turned up as its failures.
+                    AgentAction::Corrupt if *fail_amount > 0 => vec![sign_seen(false)],
+                    AgentAction::Sacrifice if *fail_amount > 0 => vec![body_found()],
+                    AgentAction::GrandSacrifice if *success_amount > 0 => {
+                        vec![sign_seen(false)]
+                    }
+                    AgentAction::GrandSacrifice => vec![body_found()],
+                    _ => vec![],
+                }
+            }
+        }
+        TurnEvent::LocalsBrutalized {
+            location,
+            location_name,
+            agent_id,
+            agent_name,
+            swayed,
+            fled,
+            witnesses,
+        } => {
+            if player == agent_id.player {
+                vec![TurnReportEvent::AgentAction {
+                    location: *location,
+                    location_name: location_name.clone(),
+                    agent_name: agent_name.clone(),
+                    action: AgentAction::Brutalize,
+                    success_amount: *swayed,
+                    fail_amount: *fled,
+                }]
+            } else if *fled > 0 && witnessed_power(witnesses, player) > 0 {
+                vec![TurnReportEvent::Brutalized {
+                    location: *location,
+                    location_name: location_name.clone(),
+                    dead: *swayed,
+                    fleeing: *fled,
+                }]
+            } else {
+                vec![]
+            }
+        }
+        TurnEvent::LocalsBribed {
+            location,
+            location_name,
+            player: briber,
+            swayed,
+        } if player == *briber => vec![TurnReportEvent::Bribed {
+            location: *location,
+            location_name: location_name.clone(),
+            swayed: *swayed,
+        }],
+        TurnEvent::SignFound {
+            location,
+            location_name,
+            agent_id,
+        } => vec![TurnReportEvent::SignSeen {
+            location: *location,
+            location_name: location_name.clone(),
+            mine: player == agent_id.player,
+        }],
+        TurnEvent::FollowersConverted {
+            location,
+            location_name,
+            player: converted,
+        } if player == *converted => vec![TurnReportEvent::FollowersLost {
+            location: *location,
+            location_name: location_name.clone(),
+            reason: LossReason::Heresy,
+            count: 0,
+            defected_to: None,
+        }],
+        TurnEvent::FollowersDefected {
+            location,
+            location_name,
+            player: left,
+            defected_to,
+            count,
+            reason,
+        } if player == *left => vec![TurnReportEvent::FollowersLost {
+            location: *location,
+            location_name: location_name.clone(),
+            reason: *reason,
+            count: *count,
+            defected_to: *defected_to,
+        }],
+        // Only those with some standing there notice the newcomers.
+        TurnEvent::FollowersBorn {
+            location,
+            location_name,
+            births,
+            sign_holders,
+            witnesses,
+        } if witnessed_power(witnesses, player) > 0 => vec![TurnReportEvent::Births {
+            location: *location,
+            location_name: location_name.clone(),
+            births: *births,
+            sign_holders: *sign_holders,
+        }],
+        TurnEvent::CorruptionSpread {
+            location,
+            location_name,
+            player: tainted,
+            count,
+        } if player == *tainted => vec![TurnReportEvent::CorruptionSpread {
+            location: *location,
+            location_name: location_name.clone(),
+            count: *count,
+        }],
+        TurnEvent::InquisitionArrived {
+            location,
+            location_name,
+            witnesses,
+        } if witnessed_power(witnesses, player) > 0 => {
+            vec![TurnReportEvent::InquisitionArrived {
+                location: *location,
+                location_name: location_name.clone(),
+            }]
+        }
+        TurnEvent::FollowersPurged {
+            location,
+            location_name,
+            purged,
+            witnesses,
+        } if witnessed_power(witnesses, player) > 0 => vec![TurnReportEvent::FollowersPurged {
+            location: *location,
+            location_name: location_name.clone(),
+            purged: *purged,
+        }],
+        TurnEvent::AgentCaptured {
+            location,
+            location_name,
+            agent_id,
+            agent_name,
+        } if player == agent_id.player => vec![TurnReportEvent::AgentCaptured {
+            location: *location,
+            location_name: location_name.clone(),
+            agent_name: agent_name.clone(),
+        }],
+        TurnEvent::WorldEventDrawn {
+            location,
+            location_name,
+            event,
+        } => vec![TurnReportEvent::SeasonEvent {
+            location: *location,
+            location_name: location_name.clone(),
+            event: *event,
+        }],
+        TurnEvent::GameOver {
+            winner,
+            allies,
+            scores,
+        } => vec![TurnReportEvent::GameOver {
+            winner: *winner,
+            allies: allies.clone(),
+            scores: scores.clone(),
+        }],
+        TurnEvent::SeasonEnded { season } => vec![TurnReportEvent::NewTurn { turn: *season }],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{names::default_theme, prelude::*, WIN_SIGN_COUNT};
+
+/// A scripted way to end the game, on top of the usual race for signs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WinCondition {
+    /// The first player to hold this many signs wins, in place of the usual count.
+    Signs(u32),
+    /// Whoever holds the area once the season ends wins.
+    HoldArea { position: (u32, u32), season: i32 },
+    /// Whoever holds the most signs once the season ends wins.
+    MostSigns { season: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ruleset {
+    /// The most followers a city may hold before births there stop.
+    pub city_population_limit: u32,
+    /// The most followers a village, temple or stronghold may hold before births there stop.
+    pub village_population_limit: u32,
+    /// The most newcomers a city may gain each season.
+    pub city_births: u32,
+    /// The most newcomers a village, temple or stronghold may gain each season.
+    pub village_births: u32,
+    /// The chance each season that one of an area's newcomers is born a sign holder.
+    pub sign_holder_birth_chance: f64,
+    /// The most sign holders an area may have before no more are born there.
+    pub sign_holder_limit: u32,
+    /// Suspicion at which an area sends for an inquisitor, and keeps one once there.
+    pub suspicion_threshold: u32,
+    /// Suspicion every area loses each season.
+    pub suspicion_decay: u32,
+    /// Suspicion an area gains from each act of brutality there.
+    pub brutalize_suspicion: u32,
+    /// Suspicion an area gains from each sacrifice there, grand or not.
+    pub sacrifice_suspicion: u32,
+    /// Suspicion an area gains from each corruption there, by ritual, communion or contagion.
+    pub corrupt_suspicion: u32,
+    /// The most inquisitors on the board at once.
+    pub inquisitor_limit: u32,
+    /// Power a new inquisitor arrives with, on top of the area's suspicion.
+    pub inquisitor_power: u32,
+    /// How many corrupted followers the inquisitors in an area purge each season.
+    pub inquisitor_purges: u32,
+    /// Suspicion the inquisitors in an area put to rest each season.
+    pub inquisitor_calm: u32,
+    /// How many world events are drawn for each season.
+    pub events_per_season: u32,
+    /// Signs two allied players must hold between them to share a victory.
+    pub alliance_sign_count: u32,
+    /// Loyalty a follower loses each season with none of their player's agents in the area, and
+    /// regains with one there.
+    pub loyalty_decay: u32,
+    /// Loyalty a player's followers in an area lose each time one of that player's agents
+    /// brutalizes the locals there.
+    pub brutality_disloyalty: u32,
+    /// The chance each season that a corrupted follower taints a follower of the same
+    /// player in a neighbouring area.
+    pub contagion_chance: f64,
+    /// Follower power it takes in a controlled area to raise one tithe a season.
+    pub tithe_rate: u32,
+    /// What a corruption ritual costs, of a follower or of an agent.
+    pub corruption_cost: u32,
+    /// What bribing the locals in one area costs.
+    pub bribe_cost: u32,
+    /// How many unaligned locals a bribe sways.
+    pub bribe_sway: u32,
+    /// What raising a follower into a new agent costs.
+    pub recruit_cost: u32,
+    /// The most agents a player may have on the board. Followers are neither promoted nor
+    /// recruited beyond it.
+    pub agent_limit: u32,
+    /// Stamina every agent loses before acting each season.
+    pub stamina_toll: u32,
+    /// Stamina an agent loses on the road when it moves, still missing the season after.
+    pub travel_exhaustion: u32,
+    /// Stamina an agent loses crossing the sea or a river off the road, into another region.
+    pub crossing_exhaustion: u32,
+    /// Stamina a road between two settlements spares an agent travelling along it.
+    pub road_bonus: u32,
+    /// Scripted endings a scenario adds to the game.
+    pub win_conditions: Vec<WinCondition>,
+    /// The name theme agents raised in play are named from.
+    pub names: String,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self {
+            city_population_limit: 120,
+            village_population_limit: 60,
+            city_births: 6,
+            village_births: 3,
+            sign_holder_birth_chance: 0.05,
+            sign_holder_limit: 3,
+            suspicion_threshold: 30,
+            suspicion_decay: 2,
+            brutalize_suspicion: 6,
+            sacrifice_suspicion: 10,
+            corrupt_suspicion: 3,
+            inquisitor_limit: 3,
+            inquisitor_power: 20,
+            inquisitor_purges: 2,
+            inquisitor_calm: 5,
+            events_per_season: 2,
+            alliance_sign_count: 8,
+            loyalty_decay: 1,
+            brutality_disloyalty: 2,
+            contagion_chance: 0.05,
+            tithe_rate: 20,
+            corruption_cost: 2,
+            bribe_cost: 3,
+            bribe_sway: 2,
+            recruit_cost: 5,
+            agent_limit: 8,
+            stamina_toll: 0,
+            travel_exhaustion: 0,
+            crossing_exhaustion: 15,
+            road_bonus: 20,
+            win_conditions: Vec::new(),
+            names: default_theme().to_string(),
+        }
+    }
+}
+
+impl Ruleset {
+    pub fn population_limit(&self, kind: AreaKind) -> u32 {
+        match kind {
+            AreaKind::City => self.city_population_limit,
+            AreaKind::Village | AreaKind::Temple | AreaKind::Stronghold => {
+                self.village_population_limit
+            }
+            AreaKind::Ruins => 0,
+        }
+    }
+
+    /// How many signs it takes to win outright.
+    pub fn win_sign_count(&self) -> u32 {
+        self.win_conditions
+            .iter()
+            .find_map(|condition| match condition {
+                WinCondition::Signs(count) => Some(*count),
+                _ => None,
+            })
+            .unwrap_or(WIN_SIGN_COUNT)
+    }
+
+    /// Stamina an agent loses travelling between the two areas. Roads spare travellers, and
+    /// crossing into another region off the road tires them.
+    pub fn travel_cost(&self, source: &WorldArea, target: &WorldArea) -> u32 {
+        if source.road.is_some() && source.road == target.road {
+            self.travel_exhaustion.saturating_sub(self.road_bonus)
+        } else if source.region != target.region {
+            self.travel_exhaustion + self.crossing_exhaustion
+        } else {
+            self.travel_exhaustion
+        }
+    }
+
+    pub fn births(&self, kind: AreaKind) -> u32 {
+        match kind {
+            AreaKind::City => self.city_births,
+            AreaKind::Village | AreaKind::Temple | AreaKind::Stronghold => self.village_births,
+            AreaKind::Ruins => 0,
+        }
+    }
+}
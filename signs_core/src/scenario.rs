@@ -0,0 +1,308 @@
+//! Hand-authored maps, written in RON, to play in place of a generated one.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    geography::fill_geography,
+    map::{fill_neighbors, tile_kind, MapDesc, TILE_COUNT},
+    names::{theme, themes, AGENT},
+    prelude::*,
+    ruleset::WinCondition,
+};
+
+/// A map laid out by hand, with every seat's starting agents and any scripted endings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Names the scenario in lobby handshakes, so every player loads the same one.
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Rows of tile ids, from the top of the map down.
+    pub tiles: Vec<Vec<u32>>,
+    pub areas: Vec<ScenarioArea>,
+    /// Each seat's starting agents. The scenario is played by exactly this many players.
+    pub seats: Vec<Vec<ScenarioAgent>>,
+    #[serde(default)]
+    pub win_conditions: Vec<WinCondition>,
+    /// The name theme unnamed agents, and everyone raised in play, are named from. Left out, the
+    /// default theme.
+    #[serde(default)]
+    pub names: Option<String>,
+}
+
+/// An area standing on one of the scenario's settlement tiles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioArea {
+    pub name: String,
+    pub position: (u32, u32),
+    #[serde(default)]
+    pub population: Vec<Crowd>,
+    /// How many of the strongest locals hold a sign.
+    #[serde(default)]
+    pub sign_holders: u32,
+}
+
+/// A number of locals of the same power.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crowd {
+    pub count: u32,
+    pub power: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioAgent {
+    /// Left out, the agent is named at random.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub position: (u32, u32),
+    pub power: u32,
+}
+
+impl Scenario {
+    pub fn from_ron(source: &str) -> Result<Self, String> {
+        let scenario: Scenario = ron::from_str(source).map_err(|err| err.to_string())?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Writes a map up as a scenario, seating every player with agents on it. Locals keep their
+    /// power, but only the number of sign holders is kept, and they go to the strongest.
+    pub fn from_map_desc(id: &str, name: &str, map: &MapDesc) -> Self {
+        let mut areas: Vec<&WorldArea> = map.areas.iter().collect();
+        areas.sort_by_key(|area| (area.world_position.1, area.world_position.0));
+        let mut seats: BTreeMap<PlayerId, BTreeMap<u32, ScenarioAgent>> = BTreeMap::new();
+        for area in areas.iter() {
+            for agent in area.agents.iter() {
+                seats.entry(agent.id.player).or_default().insert(
+                    agent.id.agent,
+                    ScenarioAgent {
+                        name: Some(agent.name.clone()),
+                        position: area.world_position,
+                        power: agent.power,
+                    },
+                );
+            }
+        }
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            tiles: map
+                .tiles
+                .chunks(map.width.max(1) as usize)
+                .map(|row| row.to_vec())
+                .collect(),
+            areas: areas
+                .into_iter()
+                .map(|area| {
+                    let mut crowds: BTreeMap<u32, u32> = BTreeMap::new();
+                    for follower in area.followers.iter() {
+                        *crowds.entry(follower.power).or_default() += 1;
+                    }
+                    ScenarioArea {
+                        name: area.name.clone(),
+                        position: area.world_position,
+                        population: crowds
+                            .into_iter()
+                            .rev()
+                            .map(|(power, count)| Crowd { count, power })
+                            .collect(),
+                        sign_holders: area.followers.iter().filter(|f| f.sign_holder).count()
+                            as u32,
+                    }
+                })
+                .collect(),
+            seats: seats
+                .into_values()
+                .map(|agents| agents.into_values().collect())
+                .collect(),
+            win_conditions: Vec::new(),
+            names: None,
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.tiles.first().map(|row| row.len()).unwrap_or(0) as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    fn tile(&self, (x, y): (u32, u32)) -> Option<u32> {
+        self.tiles
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .cloned()
+    }
+
+    /// Checks that the grid is rectangular and made of known tiles, every area stands on a
+    /// settlement tile of its own, every agent starts in an area, no two areas or named agents
+    /// share a name, and the name theme exists.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tiles.is_empty()
+            || self
+                .tiles
+                .iter()
+                .any(|row| row.len() != self.tiles[0].len())
+        {
+            return Err(format!("{}: every row of tiles must be as long", self.id));
+        }
+        let mut positions = HashSet::new();
+        let mut area_names = HashSet::new();
+        for area in self.areas.iter() {
+            if !area_names.insert(&area.name) {
+                return Err(format!("{}: two areas are called {}", self.id, area.name));
+            }
+            if tile_kind(self.tile(area.position).unwrap_or(0)).is_none() {
+                return Err(format!(
+                    "{}: {} does not stand on a settlement tile",
+                    self.id, area.name
+                ));
+            }
+            if !positions.insert(area.position) {
+                return Err(format!(
+                    "{}: two areas stand at {:?}",
+                    self.id, area.position
+                ));
+            }
+        }
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if *tile >= TILE_COUNT {
+                    return Err(format!(
+                        "{}: there is no tile {} at {:?}",
+                        self.id,
+                        tile,
+                        (x, y)
+                    ));
+                }
+                if tile_kind(*tile).is_some() && !positions.contains(&(x as u32, y as u32)) {
+                    return Err(format!(
+                        "{}: the settlement at {:?} has no area",
+                        self.id,
+                        (x, y)
+                    ));
+                }
+            }
+        }
+        if self.seats.is_empty() {
+            return Err(format!("{}: the scenario has no seats", self.id));
+        }
+        let mut agent_names = HashSet::new();
+        for agent in self.seats.iter().flatten() {
+            if !positions.contains(&agent.position) {
+                return Err(format!(
+                    "{}: an agent starts outside any area at {:?}",
+                    self.id, agent.position
+                ));
+            }
+            if let Some(name) = &agent.name {
+                if !agent_names.insert(name) {
+                    return Err(format!("{}: two agents are called {}", self.id, name));
+                }
+            }
+        }
+        if let Some(names) = &self.names {
+            if !themes().iter().any(|theme| &theme.id == names) {
+                return Err(format!("{}: there is no name theme {}", self.id, names));
+            }
+        }
+        Ok(())
+    }
+
+    /// Lays the scenario out for the players, who take its seats in order.
+    pub fn map_desc(&self, mut players: Vec<PlayerId>) -> Result<MapDesc, String> {
+        players.sort();
+        if players.len() != self.seats.len() {
+            return Err(format!(
+                "{} is for {} players, not {}",
+                self.name,
+                self.seats.len(),
+                players.len()
+            ));
+        }
+        let seed = players.iter().map(|p| p.0 as u64).fold(
+            self.id
+                .bytes()
+                .fold(0u64, |a, b| a.rotate_left(8) ^ b as u64),
+            |a, b| (a << 32) ^ b,
+        );
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut areas: Vec<WorldArea> = self
+            .areas
+            .iter()
+            .map(|desc| {
+                let (x, y) = desc.position;
+                let mut area = WorldArea::new(&desc.name, x, y);
+                area.kind = tile_kind(self.tile(desc.position).unwrap_or(0)).unwrap_or_default();
+                area.relic = area.kind == AreaKind::Ruins;
+                for crowd in desc.population.iter() {
+                    for _ in 0..crowd.count {
+                        area.add_follower(Follower::new(crowd.power));
+                    }
+                }
+                let mut strongest: Vec<&mut Follower> = area.followers.iter_mut().collect();
+                strongest.sort_by_key(|f| std::cmp::Reverse(f.power));
+                for sign_holder in strongest.into_iter().take(desc.sign_holders as usize) {
+                    sign_holder.sign_holder = true;
+                }
+                area
+            })
+            .collect();
+        let names = theme(self.names.as_deref().unwrap_or_default());
+        let mut agent_names: HashSet<String> = self
+            .seats
+            .iter()
+            .flatten()
+            .filter_map(|desc| desc.name.clone())
+            .collect();
+        for (player, agents) in players.iter().zip(self.seats.iter()) {
+            for (id, desc) in agents.iter().enumerate() {
+                let name = match &desc.name {
+                    Some(name) => name.clone(),
+                    None => names.unique(&mut rng, AGENT, &mut agent_names),
+                };
+                let agent = Agent::new(
+                    name,
+                    AgentId {
+                        player: *player,
+                        agent: id as u32,
+                    },
+                    desc.position,
+                    desc.power,
+                );
+                areas
+                    .iter_mut()
+                    .find(|area| area.world_position == desc.position)
+                    .unwrap()
+                    .add_agent(agent);
+            }
+        }
+        fill_neighbors(&mut areas);
+        let tiles: Vec<u32> = self.tiles.iter().flatten().cloned().collect();
+        fill_geography(self.width(), self.height(), &tiles, &mut areas);
+        Ok(MapDesc {
+            width: self.width(),
+            height: self.height(),
+            tiles,
+            areas,
+        })
+    }
+
+    /// The rules to play the scenario under.
+    pub fn ruleset(&self, ruleset: &Ruleset) -> Ruleset {
+        let mut ruleset = ruleset.clone();
+        ruleset.win_conditions = self.win_conditions.clone();
+        if let Some(names) = &self.names {
+            ruleset.names = names.clone();
+        }
+        ruleset
+    }
+}
@@ -0,0 +1,1047 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::*;
+
+use super::{
+    agent::JOINT_RITUAL_AGENTS,
+    calendar::Phase,
+    economy::{controller, Treasury},
+    event::{LossReason, TurnEvent},
+    names::{theme, INQUISITOR},
+    ruleset::WinCondition,
+    world::{draw_world_events, AgentNumbers, WorldState},
+};
+
+/// Where an agent acted, who they were, and the two tallies their action came to.
+type ActionResult = (u32, u32, AgentId, u32, u32);
+/// The same for agents acting together in one ritual.
+type JointResult = (u32, u32, Vec<AgentId>, u32, u32);
+/// A follower won from another player: who lost them, and where.
+type Convert = (PlayerId, u32, u32);
+type SingleActionFn<'a> =
+    dyn Fn(&mut WorldArea, AgentId, Option<FollowerId>, &mut StdRng) -> (u32, u32) + 'a;
+type JointActionFn<'a> = dyn Fn(&mut WorldArea, &[AgentId], &mut StdRng) -> (u32, u32) + 'a;
+
+pub struct TurnResults {
+    pub events: Vec<TurnEvent>,
+    pub new_world_areas: HashMap<(u32, u32), WorldArea>,
+    pub pacts: Pacts,
+    pub treasury: Treasury,
+    pub agent_numbers: AgentNumbers,
+}
+
+impl TurnResults {
+    pub fn get_new_world_area(&self, position: (u32, u32)) -> Option<WorldArea> {
+        self.new_world_areas.get(&position).cloned()
+    }
+}
+
+pub fn combined_seed(season: i32, seeds: &[u64]) -> u64 {
+    seeds.iter().fold(season as u64, |combined, seed| {
+        combined.rotate_left(13) ^ seed
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn apply_turns(
+    season: i32,
+    ruleset: &Ruleset,
+    pacts: &Pacts,
+    treasury: &Treasury,
+    agent_numbers: &AgentNumbers,
+    mut turns: Vec<PlayerTurn>,
+    mut seeds: Vec<u64>,
+    world_areas: Vec<WorldArea>,
+) -> TurnResults {
+    // The calendar bends the rules to the time of year and the moon.
+    let ruleset = &Phase::of(season).ruleset(ruleset);
+    let mut events = Vec::new();
+    // Events name agents as they were when the season began.
+    let names: HashMap<AgentId, String> = world_areas
+        .iter()
+        .flat_map(|area| area.agents.iter())
+        .map(|agent| (agent.id, agent.name.clone()))
+        .collect();
+    let mut world = WorldState::new(world_areas, agent_numbers);
+    if ruleset.stamina_toll > 0 {
+        for location in world.positions() {
+            world.update(location, |area| {
+                for agent in area.agents.iter_mut() {
+                    agent.exhaust(ruleset.stamina_toll);
+                }
+            });
+        }
+    }
+    // We just want a consistent arrangement. Seeds don't have to match their original players.
+    turns.sort_by_key(|a| a.player_id);
+    seeds.sort();
+    let mut rngs = seeds
+        .iter()
+        .map(|seed| StdRng::seed_from_u64(*seed))
+        .collect::<Vec<_>>();
+    // Anything not driven by a player rolls from every player's seed, so all clients agree.
+    let mut world_rng = StdRng::seed_from_u64(combined_seed(season, &seeds));
+
+    // Pacts change before any agent acts, so a betrayal takes effect this very season.
+    let mut pacts = pacts.clone();
+    let pact_changes = pacts.apply(&turns);
+    events.extend(
+        pact_changes
+            .into_iter()
+            .map(|(player, other, change)| TurnEvent::PactChanged {
+                player,
+                other,
+                change,
+            }),
+    );
+
+    // Orders a player cannot pay for are called off before anyone acts.
+    let mut treasury = treasury.clone();
+    for turn in turns.iter_mut() {
+        treasury.charge_actions(turn, ruleset);
+    }
+
+    let promoted_followers = promote_followers(&turns, &mut rngs, &mut world, ruleset);
+
+    let moved_agents = move_agents(&turns, &mut world, ruleset);
+    events.extend(
+        moved_agents
+            .iter()
+            .map(|(x, y, agent_id, _)| TurnEvent::AgentMoved {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                agent_id: *agent_id,
+                agent_name: names[agent_id].clone(),
+                witnesses: world.witnesses((*x, *y)),
+            }),
+    );
+    let recruits = recruit_agents(&turns, &mut rngs, &mut world, &mut treasury, ruleset);
+    events.extend(promoted_followers.into_iter().chain(recruits).map(
+        |(x, y, agent_id, agent_name)| TurnEvent::FollowerPromoted {
+            location: (x, y),
+            location_name: world.area((x, y)).name.clone(),
+            agent_id,
+            agent_name,
+        },
+    ));
+
+    let corrupted_agents = single_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &|action| *action == AgentAction::CorruptAgent,
+        &|world_area: &mut WorldArea, agent_id, _, rng| world_area.corrupt_agent(agent_id, rng),
+    );
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::CorruptAgent,
+        single_results(&corrupted_agents),
+    ));
+
+    let communions = joint_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &mut treasury,
+        AgentAction::DarkCommunion.cost(ruleset),
+        &|action| *action == AgentAction::DarkCommunion,
+        &|world_area: &mut WorldArea, agent_ids, rng| world_area.dark_communion(agent_ids, rng),
+    );
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::DarkCommunion,
+        communions.iter().cloned(),
+    ));
+
+    let corruptions = corrupt_followers(&turns, &mut rngs, &mut world);
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::Corrupt,
+        single_results(&corruptions),
+    ));
+
+    let (prostelyizes, converts) = prostelytize_followers(&turns, &mut rngs, &pacts, &mut world);
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::Prostelytize,
+        single_results(&prostelyizes),
+    ));
+
+    let sacrifices = single_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &|action| *action == AgentAction::Sacrifice,
+        &|world_area: &mut WorldArea, agent_id, target, rng| {
+            world_area.sacrifice_followers(agent_id, target, rng)
+        },
+    );
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::Sacrifice,
+        single_results(&sacrifices),
+    ));
+    events.extend(sacrifices.iter().flat_map(|(x, y, agent_id, signs, _)| {
+        if *signs > 0 {
+            Some(TurnEvent::SignFound {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                agent_id: *agent_id,
+            })
+        } else {
+            None
+        }
+    }));
+
+    let grand_sacrifices = joint_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &mut treasury,
+        AgentAction::GrandSacrifice.cost(ruleset),
+        &|action| *action == AgentAction::GrandSacrifice,
+        &|world_area: &mut WorldArea, agent_ids, rng| world_area.grand_sacrifice(agent_ids, rng),
+    );
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::GrandSacrifice,
+        grand_sacrifices.iter().cloned(),
+    ));
+
+    let searches = single_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &|action| *action == AgentAction::Search,
+        &|world_area: &mut WorldArea, agent_id, _, _| world_area.search_ruins(agent_id),
+    );
+    events.extend(acted(
+        &world,
+        &names,
+        AgentAction::Search,
+        single_results(&searches),
+    ));
+    events.extend(searches.iter().flat_map(|(x, y, agent_id, signs, _)| {
+        if *signs > 0 {
+            Some(TurnEvent::SignFound {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                agent_id: *agent_id,
+            })
+        } else {
+            None
+        }
+    }));
+
+    let brutalities = single_action(
+        &turns,
+        &mut rngs,
+        &mut world,
+        &|action| *action == AgentAction::Brutalize,
+        &|world_area: &mut WorldArea, agent_id, _, rng| {
+            world_area.brutalize_locals(agent_id, &pacts.allies(agent_id.player), rng)
+        },
+    );
+    events.extend(brutalities.iter().map(|(x, y, agent_id, swayed, fled)| {
+        TurnEvent::LocalsBrutalized {
+            location: (*x, *y),
+            location_name: world.area((*x, *y)).name.clone(),
+            agent_id: *agent_id,
+            agent_name: names[agent_id].clone(),
+            swayed: *swayed,
+            fled: *fled,
+            witnesses: world.witnesses((*x, *y)),
+        }
+    }));
+
+    for (x, y, agent_id, ..) in brutalities.iter() {
+        let player = agent_id.player;
+        let defection = world.update((*x, *y), |area| {
+            area.weaken_loyalty(player, ruleset.brutality_disloyalty)
+        });
+        if let Some((defected_to, count)) = defection {
+            events.push(TurnEvent::FollowersDefected {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                player,
+                defected_to,
+                count,
+                reason: LossReason::Brutality,
+            });
+        }
+    }
+
+    for turn in turns.iter() {
+        let player = turn.player_id;
+        for location in turn.bribes.iter() {
+            // Someone has to hand over the money.
+            if world.get(*location).is_none()
+                || world.agent_count(*location, player) == 0
+                || !treasury.spend(player, ruleset.bribe_cost)
+            {
+                continue;
+            }
+            let swayed = world.update(*location, |area| {
+                area.bribe_locals(player, ruleset.bribe_sway)
+            });
+            events.push(TurnEvent::LocalsBribed {
+                location: *location,
+                location_name: world.area(*location).name.clone(),
+                player,
+                swayed,
+            });
+        }
+    }
+
+    events.extend(
+        converts
+            .iter()
+            .map(|(player, x, y)| TurnEvent::FollowersConverted {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                player: *player,
+            }),
+    );
+
+    raise_suspicion(
+        &mut world,
+        corrupted_agents.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.corrupt_suspicion,
+    );
+    raise_suspicion(
+        &mut world,
+        communions.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.corrupt_suspicion,
+    );
+    raise_suspicion(
+        &mut world,
+        corruptions.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.corrupt_suspicion,
+    );
+    raise_suspicion(
+        &mut world,
+        sacrifices.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.sacrifice_suspicion,
+    );
+    raise_suspicion(
+        &mut world,
+        grand_sacrifices.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.sacrifice_suspicion,
+    );
+    raise_suspicion(
+        &mut world,
+        brutalities.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.brutalize_suspicion,
+    );
+
+    let contagion = spread_corruption(&mut world_rng, ruleset, &mut world);
+    events.extend(
+        contagion
+            .iter()
+            .map(|(x, y, player, count)| TurnEvent::CorruptionSpread {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                player: *player,
+                count: *count,
+            }),
+    );
+    raise_suspicion(
+        &mut world,
+        contagion.iter().map(|(x, y, ..)| (*x, *y)),
+        ruleset.corrupt_suspicion,
+    );
+
+    let inquisition = run_inquisition(&mut world_rng, ruleset, &mut world);
+    events.extend(
+        inquisition
+            .arrivals
+            .iter()
+            .map(|(x, y)| TurnEvent::InquisitionArrived {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                witnesses: world.witnesses((*x, *y)),
+            }),
+    );
+    events.extend(
+        inquisition
+            .purges
+            .iter()
+            .map(|(x, y, purged)| TurnEvent::FollowersPurged {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                purged: *purged,
+                witnesses: world.witnesses((*x, *y)),
+            }),
+    );
+    events.extend(
+        inquisition
+            .captures
+            .into_iter()
+            .map(|(x, y, agent_id, agent_name)| TurnEvent::AgentCaptured {
+                location: (x, y),
+                location_name: world.area((x, y)).name.clone(),
+                agent_id,
+                agent_name,
+            }),
+    );
+
+    let locations = world.positions();
+    for location in locations.iter() {
+        world.update(*location, |area| {
+            // Reset stamina, though the road still tells on those who travelled.
+            for agent in &mut area.agents {
+                agent.stamina = 100 + agent.power;
+                if let Some((_, _, _, cost)) =
+                    moved_agents.iter().find(|(_, _, id, _)| *id == agent.id)
+                {
+                    agent.exhaust(*cost);
+                }
+            }
+            // Kill all the dead followers.
+            area.followers.retain(|follower| follower.power > 0);
+            for follower in area.flee() {
+                area.add_follower(follower);
+            }
+        });
+    }
+
+    // Followers left alone drift away.
+    for location in locations.iter() {
+        let defections = world.update(*location, |area| area.tend_loyalty(ruleset.loyalty_decay));
+        for (player, defected_to, count) in defections {
+            events.push(TurnEvent::FollowersDefected {
+                location: *location,
+                location_name: world.area(*location).name.clone(),
+                player,
+                defected_to,
+                count,
+                reason: LossReason::Neglect,
+            });
+        }
+    }
+
+    // Births and newcomers.
+    for location in locations.iter() {
+        let (births, sign_holders) = world.update(*location, |area| {
+            area.grow_population(&mut world_rng, ruleset)
+        });
+        if births > 0 {
+            events.push(TurnEvent::FollowersBorn {
+                location: *location,
+                location_name: world.area(*location).name.clone(),
+                births,
+                sign_holders,
+                witnesses: world.witnesses(*location),
+            });
+        }
+    }
+
+    // Tithes come in from the areas each player now holds.
+    let players: Vec<PlayerId> = turns.iter().map(|turn| turn.player_id).collect();
+    treasury.collect(&players, world.areas(), ruleset);
+
+    // Draw the events for the coming season.
+    let world_events = draw_world_events(&mut world_rng, ruleset, &mut world);
+    events.extend(
+        world_events
+            .iter()
+            .map(|(x, y, event)| TurnEvent::WorldEventDrawn {
+                location: (*x, *y),
+                location_name: world.area((*x, *y)).name.clone(),
+                event: *event,
+            }),
+    );
+
+    if let Some((winner, allies)) = check_winners(season, &world, &pacts, ruleset) {
+        events.push(TurnEvent::GameOver {
+            winner,
+            allies,
+            scores: world.scores(),
+        });
+    } else {
+        events.push(TurnEvent::SeasonEnded { season });
+    }
+
+    TurnResults {
+        events,
+        agent_numbers: world.agent_numbers().clone(),
+        new_world_areas: world.into_areas(),
+        pacts,
+        treasury,
+    }
+}
+
+fn single_results(results: &[ActionResult]) -> impl Iterator<Item = JointResult> + '_ {
+    results
+        .iter()
+        .map(|(x, y, agent_id, success, fail)| (*x, *y, vec![*agent_id], *success, *fail))
+}
+
+fn acted<'a>(
+    world: &'a WorldState,
+    names: &'a HashMap<AgentId, String>,
+    action: AgentAction,
+    results: impl Iterator<Item = JointResult> + 'a,
+) -> impl Iterator<Item = TurnEvent> + 'a {
+    results.map(
+        move |(x, y, agents, success_amount, fail_amount)| TurnEvent::AgentActed {
+            location: (x, y),
+            location_name: world.area((x, y)).name.clone(),
+            agent_name: agent_names(names, &agents),
+            agents,
+            action: action.clone(),
+            success_amount,
+            fail_amount,
+        },
+    )
+}
+
+fn can_travel(world: &WorldState, source: (u32, u32), target: (u32, u32)) -> bool {
+    match (world.get(source), world.get(target)) {
+        (Some(source), Some(target)) => {
+            !source.has_event(WorldEvent::Storm) && !target.has_event(WorldEvent::Storm)
+        }
+        _ => false,
+    }
+}
+
+fn move_agents(
+    turns: &[PlayerTurn],
+    world: &mut WorldState,
+    ruleset: &Ruleset,
+) -> Vec<(u32, u32, AgentId, u32)> {
+    let mut results = Vec::new();
+    for turn in turns {
+        let mut movement_actions = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if let AgentAction::Move(x, y, _) = action {
+                    Some((agent_id, x, y))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        movement_actions.sort_by(|a, b| {
+            let a = *a.0;
+            let b = *b.0;
+            a.cmp(&b)
+        });
+        for (agent_id, x, y) in movement_actions {
+            if let Some(source) = world.locate(*agent_id) {
+                if !can_travel(world, source, (*x, *y)) {
+                    continue;
+                }
+                let cost = ruleset.travel_cost(world.area(source), world.area((*x, *y)));
+                world.move_agent(*agent_id, (*x, *y));
+                results.push((*x, *y, *agent_id, cost));
+            }
+        }
+    }
+    results
+}
+
+fn promote_followers(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    world: &mut WorldState,
+    ruleset: &Ruleset,
+) -> Vec<(u32, u32, AgentId, String)> {
+    let names = theme(&ruleset.names);
+    let mut results = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut movement_actions = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if let AgentAction::Move(x, y, _) = action {
+                    Some((agent_id, x, y))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        movement_actions.sort_by(|a, b| {
+            let a = *a.0;
+            let b = *b.0;
+            a.cmp(&b)
+        });
+        for (agent_id, x, y) in movement_actions {
+            if let Some(source) = world.locate(*agent_id) {
+                if !can_travel(world, source, (*x, *y)) {
+                    continue;
+                }
+                if world.agent_count(source, agent_id.player) == 1
+                    && world.player_agent_count(agent_id.player) < ruleset.agent_limit
+                {
+                    // I'm the only agent here, so I can promote in my absence!
+                    let new_agent_id = world.next_agent_id(agent_id.player);
+                    let taken = world.agent_names();
+                    if let Some((new_agent_id, new_name)) = world.update(source, |area| {
+                        area.promote_follower(
+                            rng,
+                            names,
+                            &taken,
+                            new_agent_id,
+                            turn.get_target(*agent_id),
+                        )
+                    }) {
+                        results.push((*x, *y, new_agent_id, new_name));
+                    }
+                }
+            }
+        }
+    }
+    results
+}
+
+/// Agents ordered to recruit raise a follower beside them, up to the player's agent limit.
+/// Recruits already paid for are refunded when no agent is raised.
+fn recruit_agents(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    world: &mut WorldState,
+    treasury: &mut Treasury,
+    ruleset: &Ruleset,
+) -> Vec<(u32, u32, AgentId, String)> {
+    let names = theme(&ruleset.names);
+    let mut results = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut recruiters: Vec<AgentId> = turn
+            .actions
+            .iter()
+            .filter(|(_, action)| **action == AgentAction::Recruit)
+            .map(|(agent_id, _)| *agent_id)
+            .collect();
+        recruiters.sort();
+        for agent_id in recruiters {
+            let location = match world.locate(agent_id) {
+                Some(location)
+                    if world.player_agent_count(agent_id.player) < ruleset.agent_limit =>
+                {
+                    location
+                }
+                _ => {
+                    treasury.deposit(agent_id.player, AgentAction::Recruit.cost(ruleset));
+                    continue;
+                }
+            };
+            let new_agent_id = world.next_agent_id(agent_id.player);
+            let taken = world.agent_names();
+            match world.update(location, |area| {
+                area.promote_follower(rng, names, &taken, new_agent_id, turn.get_target(agent_id))
+            }) {
+                Some((new_agent_id, new_name)) => {
+                    results.push((location.0, location.1, new_agent_id, new_name))
+                }
+                None => treasury.deposit(agent_id.player, AgentAction::Recruit.cost(ruleset)),
+            }
+        }
+    }
+    results
+}
+
+fn corrupt_followers(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    world: &mut WorldState,
+) -> Vec<ActionResult> {
+    let mut results = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut corruption_actions = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if let AgentAction::Corrupt = action {
+                    Some(agent_id)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        corruption_actions.sort();
+        for agent_id in corruption_actions {
+            if let Some(source) = world.locate(*agent_id) {
+                let (success_amount, signs_seen) = world.update(source, |area| {
+                    area.corrupt_followers(*agent_id, turn.get_target(*agent_id), rng)
+                });
+                results.push((source.0, source.1, *agent_id, success_amount, signs_seen));
+            }
+        }
+    }
+    results
+}
+
+fn single_action(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    world: &mut WorldState,
+    action_predicate: &dyn Fn(&AgentAction) -> bool,
+    action_fn: &SingleActionFn<'_>,
+) -> Vec<ActionResult> {
+    let mut results = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut acting_agents = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if action_predicate(action) {
+                    Some(agent_id)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        acting_agents.sort();
+        for agent_id in acting_agents {
+            if let Some(source) = world.locate(*agent_id) {
+                let (success_amount, signs_seen) = world.update(source, |area| {
+                    action_fn(area, *agent_id, turn.get_target(*agent_id), rng)
+                });
+                results.push((source.0, source.1, *agent_id, success_amount, signs_seen));
+            }
+        }
+    }
+    results
+}
+
+/// Holds each player's joint rituals. A ritual with too few agents is called off, and each of
+/// them gets back the `cost` they paid.
+fn joint_action(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    world: &mut WorldState,
+    treasury: &mut Treasury,
+    cost: u32,
+    action_predicate: &dyn Fn(&AgentAction) -> bool,
+    action_fn: &JointActionFn<'_>,
+) -> Vec<JointResult> {
+    let mut results = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut acting_agents = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if action_predicate(action) {
+                    Some(*agent_id)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        acting_agents.sort();
+        // Gather the agents into one ritual per area.
+        let mut rituals: Vec<((u32, u32), Vec<AgentId>)> = Vec::new();
+        for agent_id in acting_agents {
+            if let Some(source) = world.locate(agent_id) {
+                match rituals.iter_mut().find(|(location, _)| *location == source) {
+                    Some((_, agent_ids)) => agent_ids.push(agent_id),
+                    None => rituals.push((source, vec![agent_id])),
+                }
+            }
+        }
+        for (source, agent_ids) in rituals {
+            if (agent_ids.len() as u32) < JOINT_RITUAL_AGENTS {
+                treasury.deposit(turn.player_id, cost * agent_ids.len() as u32);
+                continue;
+            }
+            let (success_amount, fail_amount) =
+                world.update(source, |area| action_fn(area, &agent_ids, rng));
+            results.push((source.0, source.1, agent_ids, success_amount, fail_amount));
+        }
+    }
+    results
+}
+
+fn agent_names(names: &HashMap<AgentId, String>, agent_ids: &[AgentId]) -> String {
+    agent_ids
+        .iter()
+        .map(|agent_id| names[agent_id].clone())
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+fn prostelytize_followers(
+    turns: &[PlayerTurn],
+    rngs: &mut [StdRng],
+    pacts: &Pacts,
+    world: &mut WorldState,
+) -> (Vec<ActionResult>, Vec<Convert>) {
+    let mut results = Vec::new();
+    let mut converts: Vec<Convert> = Vec::new();
+    for (turn, rng) in turns.iter().zip(rngs.iter_mut()) {
+        let mut corruption_actions = turn
+            .actions
+            .iter()
+            .filter_map(|(agent_id, action)| {
+                if let AgentAction::Prostelytize = action {
+                    Some(agent_id)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        corruption_actions.sort();
+        let allies = pacts.allies(turn.player_id);
+        for agent_id in corruption_actions {
+            if let Some(source) = world.locate(*agent_id) {
+                let (successes, failures, converted) =
+                    world.update(source, |area| area.preach(*agent_id, &allies, rng));
+                for converted in converted {
+                    if !converts.contains(&(converted, source.0, source.1)) {
+                        converts.push((converted, source.0, source.1));
+                    }
+                }
+                results.push((source.0, source.1, *agent_id, successes, failures));
+            }
+        }
+    }
+    (results, converts)
+}
+
+fn raise_suspicion(
+    world: &mut WorldState,
+    locations: impl Iterator<Item = (u32, u32)>,
+    amount: u32,
+) {
+    for location in locations {
+        if world.get(location).is_some() {
+            world.update(location, |area| area.raise_suspicion(amount));
+        }
+    }
+}
+
+/// Each corrupted follower may taint a follower of the same player in a random neighbouring
+/// area. Only corruption present at the start of the step spreads, so it travels at most one
+/// area a season. Returns how many were tainted in each area, by player.
+fn spread_corruption(
+    rng: &mut StdRng,
+    ruleset: &Ruleset,
+    world: &mut WorldState,
+) -> Vec<(u32, u32, PlayerId, u32)> {
+    let mut sources = Vec::new();
+    for location in world.positions() {
+        sources.extend(
+            world
+                .area(location)
+                .followers
+                .iter()
+                .filter(|f| f.corrupted && f.power > 0)
+                .filter_map(|f| f.affinity)
+                .map(|player| (player, location)),
+        );
+    }
+    let mut tainted: BTreeMap<((u32, u32), PlayerId), u32> = BTreeMap::new();
+    for (player, location) in sources {
+        if !rng.gen_bool(ruleset.contagion_chance.clamp(0., 1.)) {
+            continue;
+        }
+        let neighbor = match choose(rng, &world.area(location).nearest_neighbors) {
+            Some(neighbor) if world.get(neighbor).is_some() => neighbor,
+            _ => continue,
+        };
+        if world.update(neighbor, |area| area.catch_corruption(player, rng)) {
+            *tainted.entry((neighbor, player)).or_default() += 1;
+        }
+    }
+    tainted
+        .into_iter()
+        .map(|(((x, y), player), count)| (x, y, player, count))
+        .collect()
+}
+
+#[derive(Default)]
+struct InquisitionResults {
+    arrivals: Vec<(u32, u32)>,
+    purges: Vec<(u32, u32, u32)>,
+    captures: Vec<(u32, u32, AgentId, String)>,
+}
+
+fn run_inquisition(
+    rng: &mut StdRng,
+    ruleset: &Ruleset,
+    world: &mut WorldState,
+) -> InquisitionResults {
+    let mut results = InquisitionResults::default();
+    let locations = world.positions();
+
+    // Inquisitors hunt wherever they already stand.
+    for location in locations.iter() {
+        if world.agent_count(*location, INQUISITION) == 0 {
+            continue;
+        }
+        let inquisitor_power = world.player_power(*location, INQUISITION);
+        let (purged, captures) = world.update(*location, |area| {
+            let purged = area.purge_corrupted(ruleset.inquisitor_purges);
+            let captures = area.hunt_agents(inquisitor_power, rng);
+            area.suspicion = area.suspicion.saturating_sub(ruleset.inquisitor_calm);
+            (purged, captures)
+        });
+        if purged > 0 {
+            results.purges.push((location.0, location.1, purged));
+        }
+        for (agent_id, agent_name) in captures {
+            results
+                .captures
+                .push((location.0, location.1, agent_id, agent_name));
+        }
+    }
+    for location in locations.iter() {
+        world.update(*location, |area| {
+            area.suspicion = area.suspicion.saturating_sub(ruleset.suspicion_decay)
+        });
+    }
+
+    // Calmed areas send their inquisitors on to the most suspicious neighbor, or home.
+    for location in locations.iter() {
+        if world.area(*location).suspicion >= ruleset.suspicion_threshold {
+            continue;
+        }
+        let inquisitors: Vec<AgentId> = world
+            .area(*location)
+            .player_agents(INQUISITION)
+            .map(|agent| agent.id)
+            .collect();
+        if inquisitors.is_empty() {
+            continue;
+        }
+        let target = world
+            .area(*location)
+            .nearest_neighbors
+            .iter()
+            .filter(|neighbor| {
+                world
+                    .get(**neighbor)
+                    .map(|area| {
+                        area.suspicion >= ruleset.suspicion_threshold
+                            && world.agent_count(**neighbor, INQUISITION) == 0
+                    })
+                    .unwrap_or(false)
+            })
+            .max_by_key(|neighbor| world.area(**neighbor).suspicion)
+            .cloned();
+        for inquisitor in inquisitors {
+            match target {
+                Some(target) => {
+                    world.move_agent(inquisitor, target);
+                    // Inquisitors arriving together are only news once.
+                    if !results.arrivals.contains(&target) {
+                        results.arrivals.push(target);
+                    }
+                }
+                None => {
+                    world.remove_agent(inquisitor);
+                }
+            }
+        }
+    }
+
+    // Send for new inquisitors where suspicion runs high.
+    let mut inquisitor_count = world.inquisitor_count();
+    let mut agent_names = world.agent_names();
+    for location in locations.iter() {
+        if inquisitor_count >= ruleset.inquisitor_limit {
+            break;
+        }
+        let area = world.area(*location);
+        if area.suspicion >= ruleset.suspicion_threshold
+            && world.agent_count(*location, INQUISITION) == 0
+        {
+            let power = ruleset.inquisitor_power + area.suspicion;
+            let agent_id = world.next_agent_id(INQUISITION);
+            world.add_agent(
+                *location,
+                Agent::new(
+                    theme(&ruleset.names).unique(rng, INQUISITOR, &mut agent_names),
+                    agent_id,
+                    *location,
+                    power,
+                ),
+            );
+            inquisitor_count += 1;
+            results.arrivals.push(*location);
+        }
+    }
+    results
+}
+
+/// The player who holds the most signs, if nobody ties them.
+fn sole_leader(scores: &BTreeMap<PlayerId, u32>) -> Option<PlayerId> {
+    let max_score = scores.values().max()?;
+    let mut leaders = scores.iter().filter(|(_, score)| *score == max_score);
+    match (leaders.next(), leaders.next()) {
+        (Some((leader, _)), None) => Some(*leader),
+        _ => None,
+    }
+}
+
+fn check_winners(
+    season: i32,
+    world: &WorldState,
+    pacts: &Pacts,
+    ruleset: &Ruleset,
+) -> Option<(PlayerId, Vec<PlayerId>)> {
+    let scores = world.scores();
+    for condition in ruleset.win_conditions.iter() {
+        let winner = match condition {
+            WinCondition::HoldArea {
+                position,
+                season: deadline,
+            } if season >= *deadline => world.get(*position).and_then(controller),
+            WinCondition::MostSigns { season: deadline } if season >= *deadline => {
+                sole_leader(&scores)
+            }
+            _ => None,
+        };
+        if let Some(winner) = winner {
+            return Some((winner, Vec::new()));
+        }
+    }
+    let max_score = scores.values().max().unwrap_or(&0);
+    if *max_score >= ruleset.win_sign_count() {
+        let winners: Vec<PlayerId> = scores
+            .iter()
+            .filter(|(_, score)| *score == max_score)
+            .map(|(player, _)| *player)
+            .collect();
+        if winners.len() == 1 {
+            return winners.first().map(|winner| (*winner, Vec::new()));
+        }
+    }
+    // Allies need more signs between them to share a victory.
+    let alliance_scores: Vec<((PlayerId, PlayerId), u32)> = pacts
+        .alliances
+        .iter()
+        .map(|(a, b)| {
+            (
+                (*a, *b),
+                scores.get(a).unwrap_or(&0) + scores.get(b).unwrap_or(&0),
+            )
+        })
+        .collect();
+    let max_alliance_score = alliance_scores
+        .iter()
+        .map(|(_, score)| *score)
+        .max()
+        .unwrap_or(0);
+    if max_alliance_score < ruleset.alliance_sign_count {
+        return None;
+    }
+    let winners: Vec<(PlayerId, PlayerId)> = alliance_scores
+        .iter()
+        .filter(|(_, score)| *score == max_alliance_score)
+        .map(|(alliance, _)| *alliance)
+        .collect();
+    if winners.len() == 1 {
+        winners.first().map(|(a, b)| (*a, vec![*b]))
+    } else {
+        None
+    }
+}
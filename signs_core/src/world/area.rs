@@ -0,0 +1,994 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use crate::{
+    agent::STAMINA_PER_ATTACK,
+    names::{distinct, NameGrammar},
+    prelude::*,
+};
+
+use super::follower::FULL_LOYALTY;
+use super::world_event::{
+    AGENT_CORRUPTION, CORRUPTION_FAILURE, ECLIPSE_AGENT_CORRUPTION, ECLIPSE_CORRUPTION_FAILURE,
+    FESTIVAL_PERSUASION,
+};
+
+pub const SIGN_HOLDER_MINIMUM: u32 = 10;
+/// How many more sign holders a temple keeps than the ruleset allows elsewhere.
+pub const TEMPLE_SIGN_HOLDERS: u32 = 2;
+/// How much more suspicious temple-goers are of dark deeds than other folk.
+pub const TEMPLE_SUSPICION: u32 = 2;
+/// Stronghold walls divide the blows a brutalizing agent can land.
+pub const STRONGHOLD_WALLS: u32 = 2;
+/// The power an agent gains from a relic, along with its sign.
+pub const RELIC_POWER: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AreaKind {
+    #[default]
+    Village,
+    City,
+    Temple,
+    Ruins,
+    Stronghold,
+}
+
+impl AreaKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AreaKind::Village => "Village",
+            AreaKind::City => "City",
+            AreaKind::Temple => "Temple",
+            AreaKind::Ruins => "Ruins",
+            AreaKind::Stronghold => "Stronghold",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WorldArea {
+    pub name: String,
+    #[serde(default)]
+    pub kind: AreaKind,
+    pub world_position: (u32, u32),
+    pub nearest_neighbors: Vec<(u32, u32)>,
+    pub followers: Vec<Follower>,
+    pub agents: Vec<Agent>,
+    #[serde(default)]
+    pub suspicion: u32,
+    #[serde(default)]
+    pub events: Vec<WorldEvent>,
+    /// How many followers born here have been given an identity.
+    #[serde(default)]
+    pub followers_numbered: u32,
+    /// Whether a relic still lies here waiting to be found. Only ruins hold one.
+    #[serde(default)]
+    pub relic: bool,
+    /// The stretch of land the area stands in. The sea and rivers divide one region from another.
+    #[serde(default)]
+    pub region: u32,
+    /// The road network the area lies on, if any road reaches it.
+    #[serde(default)]
+    pub road: Option<u32>,
+}
+
+impl WorldArea {
+    pub fn new(name: &str, x: u32, y: u32) -> Self {
+        WorldArea {
+            name: name.to_string(),
+            kind: AreaKind::default(),
+            world_position: (x, y),
+            nearest_neighbors: Vec::new(),
+            followers: Vec::new(),
+            agents: Vec::new(),
+            suspicion: 0,
+            events: Vec::new(),
+            followers_numbered: 0,
+            relic: false,
+            region: 0,
+            road: None,
+        }
+    }
+
+    /// Adds a follower, giving them an identity if they are newly born.
+    pub fn add_follower(&mut self, mut follower: Follower) {
+        if !follower.id.is_known() {
+            follower.id = self.next_follower_id();
+        }
+        self.followers.push(follower);
+    }
+
+    /// Numbers every follower still without an identity, as in saves from before them.
+    pub fn identify_followers(&mut self) {
+        for index in 0..self.followers.len() {
+            if !self.followers[index].id.is_known() {
+                self.followers[index].id = self.next_follower_id();
+            }
+        }
+    }
+
+    fn next_follower_id(&mut self) -> FollowerId {
+        self.followers_numbered += 1;
+        FollowerId {
+            home: self.world_position,
+            number: self.followers_numbered,
+        }
+    }
+
+    pub fn get_follower(&self, id: FollowerId) -> Option<&Follower> {
+        self.followers.iter().find(|follower| follower.id == id)
+    }
+
+    pub fn flee(&mut self) -> Vec<Follower> {
+        let fleeing = self
+            .followers
+            .iter()
+            .filter(|f| f.fleeing.is_some())
+            .cloned()
+            .collect();
+        self.followers.retain(|f| f.fleeing.is_none());
+        fleeing
+    }
+
+    pub fn grow_population(&mut self, rng: &mut StdRng, ruleset: &Ruleset) -> (u32, u32) {
+        let limit = ruleset.population_limit(self.kind) as usize;
+        if self.followers.len() >= limit {
+            return (0, 0);
+        }
+        let births = rng
+            .gen_range(0..=ruleset.births(self.kind))
+            .min((limit - self.followers.len()) as u32);
+        if births == 0 {
+            return (0, 0);
+        }
+        let newcomers = self.followers.len();
+        for _ in 0..births {
+            // Newcomers arrive without any loyalties.
+            self.add_follower(Follower::new(rng.gen_range(1..10)));
+        }
+        let sign_holder_limit = if self.kind == AreaKind::Temple {
+            ruleset.sign_holder_limit + TEMPLE_SIGN_HOLDERS
+        } else {
+            ruleset.sign_holder_limit
+        };
+        if self.sign_holder_count() < sign_holder_limit
+            && rng.gen_bool(ruleset.sign_holder_birth_chance.clamp(0., 1.))
+        {
+            let sign_holder = choose_mut(rng, &mut self.followers[newcomers..]).unwrap();
+            sign_holder.sign_holder = true;
+            sign_holder.power += 10;
+            (births, 1)
+        } else {
+            (births, 0)
+        }
+    }
+
+    pub fn sign_holder_count(&self) -> u32 {
+        self.followers.iter().filter(|f| f.sign_holder).count() as u32
+    }
+
+    pub fn has_event(&self, event: WorldEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    pub fn strike(&mut self, event: WorldEvent) {
+        if event == WorldEvent::Plague {
+            for follower in self.followers.iter_mut() {
+                follower.power -= follower.power / 3;
+            }
+        }
+        self.events.push(event);
+    }
+
+    pub fn add_agent(&mut self, agent: Agent) {
+        self.agents.push(agent);
+    }
+
+    pub fn get_agent_powers(&self) -> HashMap<PlayerId, u32> {
+        self.agents.iter().map(|a| (a.id.player, a.power)).collect()
+    }
+
+    pub fn get_agent_mut(&mut self, agent_id: AgentId) -> Option<&mut Agent> {
+        self.agents.iter_mut().find(|a| a.id == agent_id)
+    }
+
+    pub fn get_agent_power(&self, agent_id: AgentId) -> Option<u32> {
+        self.agents
+            .iter()
+            .find(|a| a.id == agent_id)
+            .map(|a| a.power)
+    }
+
+    pub fn get_possible_sign_holder_count(&self, agent_id: AgentId) -> usize {
+        self.followers
+            .iter()
+            .filter(can_be_sign_holder(agent_id))
+            .count()
+    }
+
+    pub fn get_agent_stamina(&self, agent_id: AgentId) -> Option<u32> {
+        self.agents
+            .iter()
+            .find(|a| a.id == agent_id)
+            .map(|a| a.stamina)
+    }
+
+    pub fn remove_agent(&mut self, agent_id: AgentId) -> Agent {
+        let agent = self
+            .agents
+            .iter()
+            .find(|a| a.id == agent_id)
+            .unwrap()
+            .clone();
+        self.agents.retain(|a| a.id != agent_id);
+        agent
+    }
+
+    /// Whether the player has a follower here strong and untainted enough to become an agent.
+    pub fn can_promote(&self, player: PlayerId) -> bool {
+        self.followers.iter().any(|f| is_promotable(f, player))
+    }
+
+    /// Raises one of the player's strong followers to an agent, who keeps their name unless an
+    /// agent already goes by it.
+    pub fn promote_follower(
+        &mut self,
+        rng: &mut StdRng,
+        names: &NameGrammar,
+        taken: &HashSet<String>,
+        agent_id: AgentId,
+        target: Option<FollowerId>,
+    ) -> Option<(AgentId, String)> {
+        let promoted = pick_follower(&mut self.followers, target, rng, |f| {
+            is_promotable(f, agent_id.player)
+        })?;
+        let power = promoted.power;
+        let agent_name = distinct(promoted.name(names), taken);
+        promoted.power = 0;
+        self.add_agent(Agent::new(agent_name.clone(), agent_id, (0, 0), power));
+        Some((agent_id, agent_name))
+    }
+
+    pub fn brutalize_locals(
+        &mut self,
+        agent_id: AgentId,
+        allies: &[PlayerId],
+        rng: &mut StdRng,
+    ) -> (u32, u32) {
+        let agent_powers = self.get_agent_powers();
+        let is_hostile = |follower: &Follower| {
+            follower.affinity != Some(agent_id.player)
+                && !follower
+                    .affinity
+                    .map(|player| allies.contains(&player))
+                    .unwrap_or(false)
+        };
+        let allied_agent_power: u32 = self
+            .agents
+            .iter()
+            .filter(|a| allies.contains(&a.id.player))
+            .map(|a| a.power)
+            .sum();
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id);
+        if agent.is_none() {
+            return (0, 0);
+        }
+        let agent = agent.unwrap();
+        let mut player_power = self
+            .followers
+            .iter()
+            .filter(|follower| follower.affinity.is_some() && !is_hostile(follower))
+            .map(|follower| follower.power)
+            .sum::<u32>()
+            + allied_agent_power
+            + agent.power;
+        let mut attacks_left = agent.stamina / STAMINA_PER_ATTACK;
+        if self.kind == AreaKind::Stronghold {
+            attacks_left /= STRONGHOLD_WALLS;
+        }
+        let mut swayed = 0;
+        let mut flee = 0;
+        while attacks_left > 0 && player_power > 0 {
+            attacks_left -= 1;
+            let non_player_local_count = self
+                .followers
+                .iter()
+                .filter(|follower| is_hostile(follower))
+                .count();
+            if non_player_local_count == 0 {
+                break;
+            }
+            let local = choose_mut_iter(
+                rng,
+                self.followers
+                    .iter_mut()
+                    .filter(|follower| is_hostile(follower)),
+                non_player_local_count,
+            )
+            .unwrap();
+            player_power = player_power.saturating_sub(local.power);
+            if let Some(other_player) = local.affinity {
+                if agent_powers.contains_key(&other_player) {
+                    player_power = player_power.saturating_sub(agent_powers[&other_player]);
+                } else {
+                    local.fleeing = walking_choose(rng, self.nearest_neighbors.as_slice());
+                    flee += 1;
+                }
+            } else if rng.gen_bool((player_power as f64 / 100.).clamp(0.1, 0.9)) {
+                local.power /= 2;
+                local.join(Some(agent.id.player));
+                swayed += 1;
+            } else if !local.sign_holder {
+                local.fleeing = walking_choose(rng, self.nearest_neighbors.as_slice());
+                local.power = local.power.saturating_sub(player_power / 2).clamp(4, 8);
+                flee += 1;
+            } else {
+                // Continue to attack harder, despite the sign holder.
+                player_power += local.power;
+            }
+        }
+        for _ in 0..swayed {
+            let follower_count = self
+                .followers
+                .iter()
+                .filter(|follower| follower.affinity == Some(agent.id.player))
+                .count();
+            if follower_count == 0 {
+                break;
+            }
+            let follower = choose_mut_iter(
+                rng,
+                self.followers
+                    .iter_mut()
+                    .filter(|follower| follower.affinity == Some(agent.id.player)),
+                follower_count,
+            );
+            follower.unwrap().power += rng.gen_range(2..=5);
+            agent.power += rng.gen_range(1..=3);
+        }
+        (swayed, flee)
+    }
+
+    /// Digs through the ruins for their relic, returning whether it was found. Only the first
+    /// agent to search finds anything.
+    pub fn search_ruins(&mut self, agent_id: AgentId) -> (u32, u32) {
+        if !self.relic {
+            return (0, 0);
+        }
+        match self.agents.iter_mut().find(|a| a.id == agent_id) {
+            Some(agent) => {
+                self.relic = false;
+                agent.signs += 1;
+                agent.power += RELIC_POWER;
+                (1, 0)
+            }
+            None => (0, 0),
+        }
+    }
+
+    /// Raises suspicion here, more so in a temple.
+    pub fn raise_suspicion(&mut self, amount: u32) {
+        self.suspicion += if self.kind == AreaKind::Temple {
+            amount * TEMPLE_SUSPICION
+        } else {
+            amount
+        };
+    }
+
+    pub fn sacrifice_followers(
+        &mut self,
+        agent_id: AgentId,
+        target: Option<FollowerId>,
+        rng: &mut StdRng,
+    ) -> (u32, u32) {
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id);
+        if agent.is_none() {
+            return (0, 0);
+        }
+        let agent = agent.unwrap();
+        let eligible = can_be_sign_holder(agent_id);
+        let sacrificed_follower =
+            match pick_follower(&mut self.followers, target, rng, |f| eligible(&f)) {
+                Some(follower) => follower,
+                None => return (0, 0),
+            };
+        let signs = {
+            if sacrificed_follower.sign_holder {
+                agent.power += sacrificed_follower.power;
+                agent.signs += 1;
+                sacrificed_follower.power = 0;
+                1
+            } else {
+                agent.power += sacrificed_follower.power / 2;
+                sacrificed_follower.power = 0;
+                0
+            }
+        };
+        let mut fleeing = 0;
+        // Send other sign holders fleeing.
+        for follower in self.followers.iter_mut() {
+            if follower.affinity != Some(agent_id.player)
+                && follower.sign_holder
+                && rng.gen_bool(0.33 + signs as f64 * 0.33)
+            {
+                follower.fleeing = walking_choose(rng, self.nearest_neighbors.as_slice());
+                if follower.fleeing.is_some() {
+                    fleeing += 1;
+                }
+            }
+        }
+        (signs, fleeing)
+    }
+
+    pub fn corrupt_followers(
+        &mut self,
+        agent_id: AgentId,
+        target: Option<FollowerId>,
+        rng: &mut StdRng,
+    ) -> (u32, u32) {
+        let failure_chance = if self.has_event(WorldEvent::Eclipse) {
+            ECLIPSE_CORRUPTION_FAILURE
+        } else {
+            CORRUPTION_FAILURE
+        };
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id);
+        if agent.is_none() {
+            return (0, 0);
+        }
+        let agent = agent.unwrap();
+        let corrupted_follower = match pick_follower(&mut self.followers, target, rng, |f| {
+            f.affinity == Some(agent_id.player)
+        }) {
+            Some(follower) => follower,
+            None => return (0, 0),
+        };
+        if corrupted_follower.sign_holder {
+            corrupted_follower.corrupted = true;
+            corrupted_follower.power *= 10;
+            agent.signs += 1;
+            corrupted_follower.sign_holder = false;
+            (1, 1)
+        } else if rng.gen_bool(failure_chance) {
+            corrupted_follower.power /= 10;
+            (0, 0)
+        } else {
+            corrupted_follower.corrupted = true;
+            corrupted_follower.power *= 10;
+            (1, 0)
+        }
+    }
+
+    fn agent_corruption_chance(&self) -> f64 {
+        if self.has_event(WorldEvent::Eclipse) {
+            ECLIPSE_AGENT_CORRUPTION
+        } else {
+            AGENT_CORRUPTION
+        }
+    }
+
+    pub fn corrupt_agent(&mut self, agent_id: AgentId, rng: &mut StdRng) -> (u32, u32) {
+        let success_chance = self.agent_corruption_chance();
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id);
+        if agent.is_none() {
+            return (0, 0);
+        }
+        let agent = agent.unwrap();
+        let corrupted_follower = self
+            .followers
+            .iter_mut()
+            .find(|follower| follower.affinity == Some(agent_id.player) && follower.corrupted);
+        if let Some(corrupted_follower) = corrupted_follower {
+            corrupted_follower.power = 0;
+            if rng.gen_bool(success_chance) {
+                agent.corrupt();
+                (1, 0)
+            } else {
+                (0, 0)
+            }
+        } else {
+            (0, 0)
+        }
+    }
+
+    /// Returns the signs gained and how many sign holders remain in the area.
+    pub fn grand_sacrifice(&mut self, agent_ids: &[AgentId], rng: &mut StdRng) -> (u32, u32) {
+        let leader_id = match agent_ids.first() {
+            Some(leader_id) => *leader_id,
+            None => return (0, 0),
+        };
+        let candidates = self.get_possible_sign_holder_count(leader_id);
+        if candidates == 0 || !self.agents.iter().any(|a| a.id == leader_id) {
+            return (0, self.sign_holder_count());
+        }
+        // With every agent watching, a sign holder among the offered cannot go unnoticed.
+        let sign_holders = self
+            .followers
+            .iter()
+            .filter(can_be_sign_holder(leader_id))
+            .filter(|f| f.sign_holder)
+            .count();
+        let sacrificed = if sign_holders > 0 {
+            choose_mut_iter(
+                rng,
+                self.followers
+                    .iter_mut()
+                    .filter(can_be_sign_holder_mut(leader_id))
+                    .filter(|f| f.sign_holder),
+                sign_holders,
+            )
+        } else {
+            choose_mut_iter(
+                rng,
+                self.followers
+                    .iter_mut()
+                    .filter(can_be_sign_holder_mut(leader_id)),
+                candidates,
+            )
+        }
+        .unwrap();
+        let power = sacrificed.power;
+        let signs = if sacrificed.sign_holder { 1 } else { 0 };
+        sacrificed.power = 0;
+        sacrificed.sign_holder = false;
+        let leader = self.get_agent_mut(leader_id).unwrap();
+        if signs > 0 {
+            leader.power += power;
+            leader.signs += 1;
+        } else {
+            leader.power += power / 2;
+        }
+        (signs, self.sign_holder_count())
+    }
+
+    pub fn dark_communion(&mut self, agent_ids: &[AgentId], rng: &mut StdRng) -> (u32, u32) {
+        let success_chance = self.agent_corruption_chance();
+        let led = self
+            .agents
+            .iter()
+            .any(|a| a.corrupted && agent_ids.contains(&a.id));
+        let initiate = self
+            .agents
+            .iter_mut()
+            .find(|a| !a.corrupted && agent_ids.contains(&a.id));
+        match initiate {
+            Some(initiate) if led && rng.gen_bool(success_chance) => {
+                initiate.corrupt();
+                (1, 0)
+            }
+            _ => (0, 0),
+        }
+    }
+
+    /// Corruption seeping in from a neighbouring area takes one of the player's untouched
+    /// followers here, if there are any.
+    pub fn catch_corruption(&mut self, player: PlayerId, rng: &mut StdRng) -> bool {
+        let count = self
+            .followers
+            .iter()
+            .filter(|f| f.affinity == Some(player) && !f.corrupted && f.power > 0)
+            .count();
+        let follower = choose_mut_iter(
+            rng,
+            self.followers
+                .iter_mut()
+                .filter(|f| f.affinity == Some(player) && !f.corrupted && f.power > 0),
+            count,
+        );
+        match follower {
+            Some(follower) => {
+                follower.corrupted = true;
+                follower.power *= 10;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn inquisitor_count(&self) -> u32 {
+        self.get_player_agent_count(INQUISITION)
+    }
+
+    pub fn purge_corrupted(&mut self, limit: u32) -> u32 {
+        let mut purged = 0;
+        for follower in self
+            .followers
+            .iter_mut()
+            .filter(|f| f.corrupted && f.power > 0)
+        {
+            if purged >= limit {
+                break;
+            }
+            follower.power = 0;
+            purged += 1;
+        }
+        purged
+    }
+
+    /// Inquisitors wielding `inquisitor_power` between them try to wear down every other
+    /// agent here, returning those they caught.
+    pub fn hunt_agents(
+        &mut self,
+        inquisitor_power: u32,
+        rng: &mut StdRng,
+    ) -> Vec<(AgentId, String)> {
+        let mut captured = Vec::new();
+        for agent in self
+            .agents
+            .iter_mut()
+            .filter(|agent| agent.id.player != INQUISITION)
+        {
+            if rng.gen_range(0..=inquisitor_power) > rng.gen_range(0..=agent.power) {
+                agent.power = agent.power.saturating_sub(inquisitor_power / 2);
+                if agent.power == 0 {
+                    captured.push((agent.id, agent.name.clone()));
+                }
+            }
+        }
+        // The faithful lose their nerve while the Inquisition watches.
+        for follower in self.followers.iter_mut().filter(|f| f.affinity.is_some()) {
+            follower.power -= follower.power / 4;
+        }
+        self.agents
+            .retain(|agent| !captured.iter().any(|(agent_id, _)| *agent_id == agent.id));
+        captured
+    }
+
+    pub fn persuasion(&self, agent: &Agent) -> u32 {
+        if self.has_event(WorldEvent::Festival) {
+            agent.power + FESTIVAL_PERSUASION
+        } else {
+            agent.power
+        }
+    }
+
+    pub fn can_prostelytize(&self, agent: &Agent) -> bool {
+        let persuasion = self.persuasion(agent);
+        self.followers.iter().any(|follower| {
+            follower.power < persuasion
+                && !follower.corrupted
+                && follower.affinity != Some(agent.id.player)
+        })
+    }
+
+    /// Prostelytizes until the agent tires or is rebuffed, returning the successes, the
+    /// failures and every player who lost a follower along the way.
+    pub fn preach(
+        &mut self,
+        agent_id: AgentId,
+        allies: &[PlayerId],
+        rng: &mut StdRng,
+    ) -> (u32, u32, Vec<PlayerId>) {
+        let mut successes = 0;
+        let mut failures = 0;
+        let mut converted_players = Vec::new();
+        while self.get_agent_stamina(agent_id).unwrap_or_default() > 0 && failures == 0 {
+            let (success_amount, fail_amount, converted) =
+                self.prostelytize_followers(agent_id, allies, rng);
+            successes += success_amount;
+            failures += fail_amount;
+            converted_players.extend(converted);
+        }
+        (successes, failures, converted_players)
+    }
+
+    /// Buys the allegiance of up to `sway` unaligned locals, the cheapest first. Returns how
+    /// many took the money.
+    pub fn bribe_locals(&mut self, player: PlayerId, sway: u32) -> u32 {
+        let mut locals: Vec<&mut Follower> = self
+            .followers
+            .iter_mut()
+            .filter(|f| f.affinity.is_none() && f.power > 0)
+            .collect();
+        locals.sort_by_key(|f| (f.power, f.id));
+        let mut swayed = 0;
+        for local in locals.into_iter().take(sway as usize) {
+            local.join(Some(player));
+            swayed += 1;
+        }
+        swayed
+    }
+
+    pub fn prostelytize_followers(
+        &mut self,
+        agent_id: AgentId,
+        allies: &[PlayerId],
+        rng: &mut StdRng,
+    ) -> (u32, u32, Option<PlayerId>) {
+        let festival = if self.has_event(WorldEvent::Festival) {
+            FESTIVAL_PERSUASION
+        } else {
+            0
+        };
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id);
+        if agent.is_none() {
+            return (0, 0, None);
+        }
+        let agent = agent.unwrap();
+        agent.exhaust(agent.power);
+        if self.followers.is_empty() {
+            return (0, 0, None);
+        }
+        let follower = choose_mut(rng, self.followers.as_mut_slice()).unwrap();
+        if follower.affinity.is_none() {
+            if follower.power < agent.power + festival {
+                follower.join(Some(agent_id.player));
+                agent.power += (follower.power / 2).clamp(3, 10);
+                (1, 0, None)
+            } else {
+                agent.power += 3;
+                (0, 1, None)
+            }
+        } else if follower.affinity == Some(agent_id.player) {
+            agent.power += (follower.power / 2).clamp(3, 10);
+            follower.power += (agent.power / 5).clamp(1, 3);
+            (0, 0, None)
+        } else if allies.contains(&follower.affinity.unwrap()) {
+            // Allies leave each other's faithful alone.
+            (0, 0, None)
+        } else {
+            if rng.gen_range(0..=agent.power) > follower.power {
+                // Double loss for stealing!
+                agent.exhaust(follower.power);
+                agent.power += (follower.power / 2).clamp(3, 5);
+                let converted_player = follower.affinity;
+                follower.join(Some(agent_id.player));
+                (1, 0, converted_player)
+            } else {
+                // Double loss for stealing, but don't end your turn!
+                agent.exhaust(follower.power);
+                (0, 0, None)
+            }
+        }
+    }
+
+    /// Wears down the loyalty of the player's followers here, returning who any that broke
+    /// defected to and how many went.
+    pub fn weaken_loyalty(
+        &mut self,
+        player: PlayerId,
+        amount: u32,
+    ) -> Option<(Option<PlayerId>, u32)> {
+        for follower in self
+            .followers
+            .iter_mut()
+            .filter(|f| f.affinity == Some(player) && !f.corrupted)
+        {
+            follower.loyalty = follower.loyalty.saturating_sub(amount);
+        }
+        self.defect(player)
+    }
+
+    /// Followers whose player has an agent here grow loyal again, while the rest drift.
+    /// Corrupted followers are bound to their player regardless. Returns every defection
+    /// along with the player who was left.
+    pub fn tend_loyalty(&mut self, amount: u32) -> Vec<(PlayerId, Option<PlayerId>, u32)> {
+        let tended: Vec<PlayerId> = self.agents.iter().map(|a| a.id.player).collect();
+        let mut broken = BTreeSet::new();
+        for follower in self.followers.iter_mut().filter(|f| !f.corrupted) {
+            if let Some(player) = follower.affinity {
+                if tended.contains(&player) {
+                    follower.loyalty = (follower.loyalty + amount).min(FULL_LOYALTY);
+                } else {
+                    follower.loyalty = follower.loyalty.saturating_sub(amount);
+                    if follower.loyalty == 0 {
+                        broken.insert(player);
+                    }
+                }
+            }
+        }
+        broken
+            .into_iter()
+            .filter_map(|player| {
+                self.defect(player)
+                    .map(|(defected_to, count)| (player, defected_to, count))
+            })
+            .collect()
+    }
+
+    /// Sends the player's followers who have lost all loyalty to the strongest other player
+    /// here, or to no one at all.
+    fn defect(&mut self, player: PlayerId) -> Option<(Option<PlayerId>, u32)> {
+        let is_disloyal =
+            |f: &Follower| f.affinity == Some(player) && !f.corrupted && f.loyalty == 0;
+        let count = self.followers.iter().filter(|f| is_disloyal(f)).count() as u32;
+        if count == 0 {
+            return None;
+        }
+        let defected_to = self.strongest_rival(player);
+        for follower in self.followers.iter_mut().filter(|f| is_disloyal(f)) {
+            follower.join(defected_to);
+        }
+        Some((defected_to, count))
+    }
+
+    /// The player besides `player` with the most power here, lowest id first on a tie.
+    fn strongest_rival(&self, player: PlayerId) -> Option<PlayerId> {
+        let mut rivals: BTreeMap<PlayerId, u32> = BTreeMap::new();
+        let powers = self
+            .followers
+            .iter()
+            .filter_map(|f| f.affinity.map(|rival| (rival, f.power)))
+            .chain(self.agents.iter().map(|a| (a.id.player, a.power)));
+        for (rival, power) in powers {
+            if rival != player && rival != INQUISITION {
+                *rivals.entry(rival).or_default() += power;
+            }
+        }
+        rivals
+            .into_iter()
+            .map(|(rival, power)| (power, rival))
+            .filter(|(power, _)| *power > 0)
+            .max_by_key(|(power, rival)| (*power, Reverse(*rival)))
+            .map(|(_, rival)| rival)
+    }
+
+    pub fn get_player_agent_count(&self, player: PlayerId) -> u32 {
+        self.agents.iter().filter(|a| a.id.player == player).count() as u32
+    }
+
+    pub fn get_nth_player_agent(&self, player: PlayerId, mut agent: u32) -> Option<&Agent> {
+        let count = self.get_player_agent_count(player);
+        while agent >= count && count != 0 {
+            agent -= count;
+        }
+        self.agents
+            .iter()
+            .filter(|a| a.id.player == player)
+            .nth(agent as usize)
+    }
+
+    pub fn get_unassigned_player_agent(&self, player_turn: &PlayerTurn) -> Option<u32> {
+        self.agents
+            .iter()
+            .filter(|a| a.id.player == player_turn.player_id)
+            .enumerate()
+            .filter(|(_, a)| player_turn.is_unassigned_player_agent(a.id))
+            .map(|(idx, _)| idx as u32)
+            .next()
+    }
+
+    pub fn get_player_followers(&self, player: PlayerId) -> u32 {
+        self.followers
+            .iter()
+            .filter(|f| f.affinity == Some(player))
+            .count() as u32
+    }
+
+    pub fn get_player_power(&self, player: PlayerId) -> u32 {
+        let follower_power: u32 = self
+            .followers
+            .iter()
+            .filter(|f| f.affinity == Some(player))
+            .map(|f| f.power)
+            .sum();
+        let agent_power: u32 = self
+            .agents
+            .iter()
+            .filter(|a| a.id.player == player)
+            .map(|f| f.power)
+            .sum();
+        follower_power + agent_power
+    }
+
+    pub fn get_allied_power(&self, player: PlayerId, allies: &[PlayerId]) -> u32 {
+        self.get_player_power(player)
+            + allies
+                .iter()
+                .map(|ally| self.get_player_power(*ally))
+                .sum::<u32>()
+    }
+
+    pub fn get_hostile_agent_power(&self, player: PlayerId, allies: &[PlayerId]) -> u32 {
+        self.agents
+            .iter()
+            .filter(|a| a.id.player != player && !allies.contains(&a.id.player))
+            .map(|f| f.power)
+            .sum()
+    }
+
+    pub fn get_non_player_follower_power(&self, player: PlayerId) -> u32 {
+        self.followers
+            .iter()
+            .filter(|f| f.affinity.is_some() && f.affinity != Some(player))
+            .map(|f| f.power)
+            .sum()
+    }
+
+    pub fn get_non_player_agent_power(&self, player: PlayerId) -> u32 {
+        self.agents
+            .iter()
+            .filter(|a| a.id.player != player)
+            .map(|f| f.power)
+            .sum()
+    }
+
+    pub fn corrupted_followers(&self, player: PlayerId) -> u32 {
+        self.followers
+            .iter()
+            .filter(|f| f.affinity == Some(player) && f.corrupted)
+            .count() as u32
+    }
+
+    pub fn corrupted_agents(&self, player: PlayerId) -> u32 {
+        self.agents
+            .iter()
+            .filter(|a| a.id.player == player && a.corrupted)
+            .count() as u32
+    }
+
+    pub fn corrupted_count(&self, player: PlayerId) -> u32 {
+        self.corrupted_followers(player) + self.corrupted_agents(player)
+    }
+
+    pub fn get_value(&self) -> u32 {
+        self.followers.iter().map(|f| f.power).sum()
+    }
+
+    pub fn player_agents(&self, player: PlayerId) -> impl Iterator<Item = &Agent> {
+        self.agents.iter().filter(move |a| a.id.player == player)
+    }
+
+    pub fn sign_count(&self, player: PlayerId) -> u32 {
+        self.agents
+            .iter()
+            .filter_map(|a| {
+                if a.id.player == player {
+                    Some(a.signs)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
+fn is_promotable(follower: &Follower, player: PlayerId) -> bool {
+    follower.power > SIGN_HOLDER_MINIMUM
+        && follower.affinity == Some(player)
+        && !follower.corrupted
+        && !follower.sign_holder
+}
+
+/// The targeted follower if they qualify, otherwise one chosen at random from those who do.
+fn pick_follower<'a>(
+    followers: &'a mut [Follower],
+    target: Option<FollowerId>,
+    rng: &mut StdRng,
+    eligible: impl Fn(&Follower) -> bool,
+) -> Option<&'a mut Follower> {
+    let targeted = target.filter(FollowerId::is_known).and_then(|target| {
+        followers
+            .iter()
+            .position(|follower| follower.id == target && eligible(follower))
+    });
+    if let Some(index) = targeted {
+        return Some(&mut followers[index]);
+    }
+    let count = followers
+        .iter()
+        .filter(|follower| eligible(follower))
+        .count();
+    choose_mut_iter(
+        rng,
+        followers.iter_mut().filter(|follower| eligible(follower)),
+        count,
+    )
+}
+
+fn can_be_sign_holder_mut(agent_id: AgentId) -> impl Fn(&&mut Follower) -> bool {
+    move |follower| {
+        follower.affinity == Some(agent_id.player)
+            && follower.power > SIGN_HOLDER_MINIMUM
+            && !follower.corrupted
+    }
+}
+
+fn can_be_sign_holder(agent_id: AgentId) -> impl Fn(&&Follower) -> bool {
+    move |follower| {
+        follower.affinity == Some(agent_id.player)
+            && follower.power > SIGN_HOLDER_MINIMUM
+            && !follower.corrupted
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    names::{NameGrammar, AGENT},
+    prelude::*,
+};
+
+/// Who a follower is, wherever they wander. Followers are numbered by the area they were
+/// born in, counting from one.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct FollowerId {
+    pub home: (u32, u32),
+    pub number: u32,
+}
+
+impl FollowerId {
+    /// Followers from saves older than identities have yet to be numbered.
+    pub fn is_known(&self) -> bool {
+        self.number != 0
+    }
+}
+
+/// The loyalty of a follower who has just taken a side.
+pub const FULL_LOYALTY: u32 = 8;
+
+fn full_loyalty() -> u32 {
+    FULL_LOYALTY
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Follower {
+    #[serde(default)]
+    pub id: FollowerId,
+    pub sign_holder: bool,
+    pub corrupted: bool,
+    #[serde(skip)]
+    pub fleeing: Option<(u32, u32)>,
+    pub affinity: Option<PlayerId>,
+    pub power: u32,
+    /// Worn down while their player neglects or brutalizes them. At zero they defect.
+    #[serde(default = "full_loyalty")]
+    pub loyalty: u32,
+}
+
+impl Follower {
+    pub fn new(power: u32) -> Self {
+        Self {
+            id: FollowerId::default(),
+            sign_holder: false,
+            corrupted: false,
+            fleeing: None,
+            affinity: None,
+            power,
+            loyalty: FULL_LOYALTY,
+        }
+    }
+
+    /// Takes a side, or none, with their loyalty renewed.
+    pub fn join(&mut self, player: Option<PlayerId>) {
+        self.affinity = player;
+        self.loyalty = FULL_LOYALTY;
+    }
+
+    /// Always the same for the same follower under the same theme, without having to be stored.
+    pub fn name(&self, names: &NameGrammar) -> String {
+        let FollowerId {
+            home: (x, y),
+            number,
+        } = self.id;
+        let mut rng =
+            StdRng::seed_from_u64(((x as u64) << 48) ^ ((y as u64) << 32) ^ number as u64);
+        names.expand(&mut rng, AGENT)
+    }
+}
@@ -0,0 +1,8 @@
+mod area;
+mod follower;
+mod state;
+mod world_event;
+pub use area::*;
+pub use follower::*;
+pub use state::*;
+pub use world_event::*;
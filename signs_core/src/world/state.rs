@@ -0,0 +1,303 @@
+use std::{borrow::Cow, cell::OnceCell, collections::BTreeMap};
+
+use crate::{event::Witnesses, prelude::*};
+
+/// What one player has standing in an area.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Presence {
+    follower_power: u32,
+    agents: u32,
+    agent_power: u32,
+    signs: u32,
+}
+
+/// The world while a season resolves.
+///
+/// Areas are kept in position order, with every agent indexed by the area it stands in.
+/// Per-player totals are worked out the first time an area is asked for them and thrown
+/// away whenever that area changes. Agents and followers are tallied apart, since agents
+/// come and go far more often and are far fewer.
+///
+/// With the `unindexed` feature, agents are found by scanning the map and totals are counted
+/// afresh on every call, as seasons were resolved before the index. It is only there to give
+/// the season bench a baseline.
+pub struct WorldState {
+    areas: Vec<WorldArea>,
+    slots: HashMap<(u32, u32), usize>,
+    agent_slots: HashMap<AgentId, usize>,
+    agent_tallies: Vec<OnceCell<Tally>>,
+    follower_tallies: Vec<OnceCell<Tally>>,
+    numbers: AgentNumbers,
+}
+
+type Tally = Vec<(PlayerId, Presence)>;
+
+/// The number each player's next new agent takes. Numbers only ever go up, so an agent taken
+/// off the board never hands their id down to a newcomer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentNumbers(BTreeMap<PlayerId, u32>);
+
+impl AgentNumbers {
+    /// Keeps clear of an agent already on the board, as on fresh maps and in older saves.
+    fn cover(&mut self, agent_id: AgentId) {
+        let next = self.0.entry(agent_id.player).or_insert(0);
+        *next = (*next).max(agent_id.agent + 1);
+    }
+
+    fn take(&mut self, player: PlayerId) -> AgentId {
+        let next = self.0.entry(player).or_insert(0);
+        let agent = *next;
+        *next += 1;
+        AgentId { player, agent }
+    }
+}
+
+impl WorldState {
+    pub fn new(mut areas: Vec<WorldArea>, numbers: &AgentNumbers) -> Self {
+        areas.sort_by_key(|area| area.world_position);
+        let mut slots = HashMap::new();
+        let mut agent_slots = HashMap::new();
+        let mut numbers = numbers.clone();
+        for (slot, area) in areas.iter().enumerate() {
+            slots.insert(area.world_position, slot);
+            for agent in area.agents.iter() {
+                agent_slots.insert(agent.id, slot);
+                numbers.cover(agent.id);
+            }
+        }
+        WorldState {
+            agent_tallies: areas.iter().map(|_| OnceCell::new()).collect(),
+            follower_tallies: areas.iter().map(|_| OnceCell::new()).collect(),
+            areas,
+            slots,
+            agent_slots,
+            numbers,
+        }
+    }
+
+    pub fn get(&self, position: (u32, u32)) -> Option<&WorldArea> {
+        self.slots.get(&position).map(|slot| &self.areas[*slot])
+    }
+
+    pub fn area(&self, position: (u32, u32)) -> &WorldArea {
+        &self.areas[self.slots[&position]]
+    }
+
+    pub fn areas(&self) -> impl Iterator<Item = &WorldArea> {
+        self.areas.iter()
+    }
+
+    /// Every position on the map, in order.
+    pub fn positions(&self) -> Vec<(u32, u32)> {
+        self.areas.iter().map(|area| area.world_position).collect()
+    }
+
+    #[cfg(not(feature = "unindexed"))]
+    pub fn locate(&self, agent_id: AgentId) -> Option<(u32, u32)> {
+        let slot = self.agent_slots.get(&agent_id)?;
+        Some(self.areas[*slot].world_position)
+    }
+
+    #[cfg(feature = "unindexed")]
+    pub fn locate(&self, agent_id: AgentId) -> Option<(u32, u32)> {
+        self.areas
+            .iter()
+            .find(|area| area.agents.iter().any(|agent| agent.id == agent_id))
+            .map(|area| area.world_position)
+    }
+
+    /// Changes one area. Agents joining or leaving the area are picked up by the index.
+    pub fn update<R>(&mut self, position: (u32, u32), f: impl FnOnce(&mut WorldArea) -> R) -> R {
+        let slot = self.slots[&position];
+        if cfg!(feature = "unindexed") {
+            return f(&mut self.areas[slot]);
+        }
+        let before: Vec<AgentId> = self.areas[slot].agents.iter().map(|a| a.id).collect();
+        let result = f(&mut self.areas[slot]);
+        let agents = &self.areas[slot].agents;
+        if agents.len() != before.len()
+            || agents.iter().zip(before.iter()).any(|(a, id)| a.id != *id)
+        {
+            for agent_id in before {
+                if self.agent_slots.get(&agent_id) == Some(&slot) {
+                    self.agent_slots.remove(&agent_id);
+                }
+            }
+            for agent in agents.iter() {
+                self.agent_slots.insert(agent.id, slot);
+            }
+        }
+        self.agent_tallies[slot].take();
+        self.follower_tallies[slot].take();
+        result
+    }
+
+    pub fn add_agent(&mut self, position: (u32, u32), agent: Agent) {
+        let slot = self.slots[&position];
+        self.agent_slots.insert(agent.id, slot);
+        self.areas[slot].add_agent(agent);
+        self.agent_tallies[slot].take();
+    }
+
+    pub fn remove_agent(&mut self, agent_id: AgentId) -> Option<Agent> {
+        let slot = self.slots[&self.locate(agent_id)?];
+        self.agent_slots.remove(&agent_id);
+        self.agent_tallies[slot].take();
+        Some(self.areas[slot].remove_agent(agent_id))
+    }
+
+    pub fn move_agent(&mut self, agent_id: AgentId, target: (u32, u32)) {
+        if let Some(agent) = self.remove_agent(agent_id) {
+            self.add_agent(target, agent);
+        }
+    }
+
+    /// Everyone with any power here right now.
+    pub fn witnesses(&self, position: (u32, u32)) -> Witnesses {
+        let slot = self.slots[&position];
+        let mut witnesses: Witnesses = self
+            .agent_tally(slot)
+            .iter()
+            .map(|(player, presence)| (*player, presence.agent_power))
+            .collect();
+        for (player, presence) in self.follower_tally(slot).iter() {
+            match witnesses.iter_mut().find(|(other, _)| other == player) {
+                Some((_, power)) => *power += presence.follower_power,
+                None => witnesses.push((*player, presence.follower_power)),
+            }
+        }
+        witnesses.retain(|(_, power)| *power > 0);
+        witnesses.sort();
+        witnesses
+    }
+
+    pub fn agent_count(&self, position: (u32, u32), player: PlayerId) -> u32 {
+        self.agent_presence(self.slots[&position], player).agents
+    }
+
+    /// The power of the player's agents and followers here together.
+    pub fn player_power(&self, position: (u32, u32), player: PlayerId) -> u32 {
+        let slot = self.slots[&position];
+        self.agent_presence(slot, player).agent_power
+            + find(&self.follower_tally(slot), player).follower_power
+    }
+
+    /// How many agents the player has on the board.
+    pub fn player_agent_count(&self, player: PlayerId) -> u32 {
+        (0..self.areas.len())
+            .map(|slot| self.agent_presence(slot, player).agents)
+            .sum()
+    }
+
+    /// Hands out the id for the player's next new agent, one the player has never held.
+    pub fn next_agent_id(&mut self, player: PlayerId) -> AgentId {
+        self.numbers.take(player)
+    }
+
+    /// The numbers to hand out from next season on.
+    pub fn agent_numbers(&self) -> &AgentNumbers {
+        &self.numbers
+    }
+
+    /// The names every agent on the board goes by, so nobody new takes one of them.
+    pub fn agent_names(&self) -> HashSet<String> {
+        self.areas
+            .iter()
+            .flat_map(|area| area.agents.iter())
+            .map(|agent| agent.name.clone())
+            .collect()
+    }
+
+    pub fn inquisitor_count(&self) -> u32 {
+        (0..self.areas.len())
+            .map(|slot| self.agent_presence(slot, INQUISITION).agents)
+            .sum()
+    }
+
+    /// Signs held by every player with an agent on the board.
+    pub fn scores(&self) -> BTreeMap<PlayerId, u32> {
+        let mut scores = BTreeMap::new();
+        for slot in 0..self.areas.len() {
+            for (player, presence) in self.agent_tally(slot).iter() {
+                if *player != INQUISITION {
+                    *scores.entry(*player).or_insert(0) += presence.signs;
+                }
+            }
+        }
+        scores
+    }
+
+    pub fn into_areas(self) -> HashMap<(u32, u32), WorldArea> {
+        self.areas
+            .into_iter()
+            .map(|area| (area.world_position, area))
+            .collect()
+    }
+
+    fn agent_presence(&self, slot: usize, player: PlayerId) -> Presence {
+        find(&self.agent_tally(slot), player)
+    }
+
+    #[cfg(not(feature = "unindexed"))]
+    fn agent_tally(&self, slot: usize) -> Cow<'_, Tally> {
+        Cow::Borrowed(self.agent_tallies[slot].get_or_init(|| tally_agents(&self.areas[slot])))
+    }
+
+    #[cfg(not(feature = "unindexed"))]
+    fn follower_tally(&self, slot: usize) -> Cow<'_, Tally> {
+        Cow::Borrowed(
+            self.follower_tallies[slot].get_or_init(|| tally_followers(&self.areas[slot])),
+        )
+    }
+
+    #[cfg(feature = "unindexed")]
+    fn agent_tally(&self, slot: usize) -> Cow<'_, Tally> {
+        Cow::Owned(tally_agents(&self.areas[slot]))
+    }
+
+    #[cfg(feature = "unindexed")]
+    fn follower_tally(&self, slot: usize) -> Cow<'_, Tally> {
+        Cow::Owned(tally_followers(&self.areas[slot]))
+    }
+}
+
+fn find(tally: &Tally, player: PlayerId) -> Presence {
+    tally
+        .iter()
+        .find(|(other, _)| *other == player)
+        .map(|(_, presence)| *presence)
+        .unwrap_or_default()
+}
+
+fn entry(tally: &mut Tally, player: PlayerId) -> &mut Presence {
+    let index = match tally.iter().position(|(other, _)| *other == player) {
+        Some(index) => index,
+        None => {
+            tally.push((player, Presence::default()));
+            tally.len() - 1
+        }
+    };
+    &mut tally[index].1
+}
+
+fn tally_agents(area: &WorldArea) -> Tally {
+    let mut tally = Vec::new();
+    for agent in area.agents.iter() {
+        let presence = entry(&mut tally, agent.id.player);
+        presence.agents += 1;
+        presence.agent_power += agent.power;
+        presence.signs += agent.signs;
+    }
+    tally
+}
+
+fn tally_followers(area: &WorldArea) -> Tally {
+    let mut tally = Vec::new();
+    for follower in area.followers.iter() {
+        if let Some(player) = follower.affinity {
+            let presence = entry(&mut tally, player);
+            presence.follower_power += follower.power;
+        }
+    }
+    tally
+}
@@ -0,0 +1,97 @@
+use crate::prelude::*;
+
+use super::WorldState;
+
+pub const FESTIVAL_PERSUASION: u32 = 10;
+pub const CORRUPTION_FAILURE: f64 = 0.333;
+pub const ECLIPSE_CORRUPTION_FAILURE: f64 = 0.15;
+pub const AGENT_CORRUPTION: f64 = 0.333;
+pub const ECLIPSE_AGENT_CORRUPTION: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WorldEvent {
+    Plague,
+    Festival,
+    Eclipse,
+    Storm,
+}
+
+const EVENT_DECK: [(WorldEvent, u32); 4] = [
+    (WorldEvent::Plague, 2),
+    (WorldEvent::Festival, 3),
+    (WorldEvent::Eclipse, 2),
+    (WorldEvent::Storm, 3),
+];
+
+impl WorldEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorldEvent::Plague => "Plague",
+            WorldEvent::Festival => "Festival",
+            WorldEvent::Eclipse => "Eclipse",
+            WorldEvent::Storm => "Storm",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            WorldEvent::Plague => "Sickness sweeps the streets.\nEvery follower here grows weaker.",
+            WorldEvent::Festival => {
+                "The locals celebrate.\nTheir minds are open to new words this season."
+            }
+            WorldEvent::Eclipse => {
+                "The sun is swallowed.\nCorruption rituals here are more likely to succeed."
+            }
+            WorldEvent::Storm => "A storm closes the roads.\nNo agent may travel to or from here.",
+        }
+    }
+
+    /// Plagues need crowded streets, while storms only cut off the open country.
+    pub fn can_strike(&self, area: &WorldArea) -> bool {
+        match self {
+            WorldEvent::Plague => area.kind == AreaKind::City,
+            WorldEvent::Storm => area.kind == AreaKind::Village,
+            WorldEvent::Festival | WorldEvent::Eclipse => true,
+        }
+    }
+
+    fn draw(rng: &mut StdRng) -> WorldEvent {
+        let total: u32 = EVENT_DECK.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for (event, weight) in EVENT_DECK {
+            if roll < weight {
+                return event;
+            }
+            roll -= weight;
+        }
+        unreachable!("Event deck roll out of range")
+    }
+}
+
+pub fn draw_world_events(
+    rng: &mut StdRng,
+    ruleset: &Ruleset,
+    world: &mut WorldState,
+) -> Vec<(u32, u32, WorldEvent)> {
+    let locations = world.positions();
+    for location in locations.iter() {
+        world.update(*location, |area| area.events.clear());
+    }
+    let mut results = Vec::new();
+    for _ in 0..ruleset.events_per_season {
+        let event = WorldEvent::draw(rng);
+        let candidates: Vec<(u32, u32)> = locations
+            .iter()
+            .filter(|location| {
+                let area = world.area(**location);
+                event.can_strike(area) && !area.has_event(event)
+            })
+            .cloned()
+            .collect();
+        if let Some(location) = choose(rng, &candidates) {
+            world.update(location, |area| area.strike(event));
+            results.push((location.0, location.1, event));
+        }
+    }
+    results
+}
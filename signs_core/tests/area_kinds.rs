@@ -0,0 +1,82 @@
+//! Temples, ruins and strongholds: where they appear and how they change the rules.
+
+mod common;
+
+use common::{add_followers, agent, village, OWNER};
+use signs_core::{
+    map::{area_tile, generate_seeded_map},
+    prelude::*,
+};
+
+fn agent_id() -> AgentId {
+    AgentId::new(0, 0)
+}
+
+fn with_agent(mut area: WorldArea, power: u32) -> WorldArea {
+    area.add_agent(agent(OWNER, 0, power));
+    area
+}
+
+#[test]
+fn generated_areas_stand_on_their_own_tiles() {
+    let mut kinds = HashSet::new();
+    for seed in 0..40 {
+        let map = generate_seeded_map(seed, vec![OWNER, PlayerId(1), PlayerId(2)]);
+        for area in map.areas.iter() {
+            let (x, y) = area.world_position;
+            assert_eq!(map.get_tile(x, y), area_tile(area.kind));
+            if area.kind == AreaKind::Ruins {
+                assert!(area.relic);
+                assert!(area.followers.is_empty());
+                assert!(area.agents.is_empty());
+            }
+            kinds.insert(area.kind);
+        }
+    }
+    assert_eq!(kinds.len(), 5);
+}
+
+#[test]
+fn ruins_give_up_their_relic_once() {
+    let mut ruins = WorldArea::new("Ruins of Ashford", 0, 0);
+    ruins.kind = AreaKind::Ruins;
+    ruins.relic = true;
+    let mut ruins = with_agent(ruins, 10);
+    assert!(AgentAction::Search
+        .invalid_reasons(&ruins.agents[0], &ruins)
+        .is_none());
+
+    assert_eq!(ruins.search_ruins(agent_id()), (1, 0));
+    assert_eq!(ruins.sign_count(OWNER), 1);
+    assert!(!ruins.relic);
+    assert_eq!(ruins.search_ruins(agent_id()), (0, 0));
+    assert_eq!(ruins.sign_count(OWNER), 1);
+    assert!(AgentAction::Search
+        .invalid_reasons(&ruins.agents[0], &ruins)
+        .is_some());
+}
+
+#[test]
+fn temples_grow_suspicious_faster() {
+    let mut village = village();
+    let mut temple = village.clone();
+    temple.kind = AreaKind::Temple;
+    village.raise_suspicion(3);
+    temple.raise_suspicion(3);
+    assert!(temple.suspicion > village.suspicion);
+}
+
+#[test]
+fn stronghold_walls_hold_off_brutality() {
+    let mut village = village();
+    add_followers(&mut village, 40, 1, None);
+    let mut stronghold = village.clone();
+    stronghold.kind = AreaKind::Stronghold;
+
+    let brutalize = |area: WorldArea| {
+        let mut area = with_agent(area, 200);
+        let (swayed, fled) = area.brutalize_locals(agent_id(), &[], &mut StdRng::seed_from_u64(3));
+        swayed + fled
+    };
+    assert!(brutalize(stronghold) < brutalize(village));
+}
@@ -0,0 +1,106 @@
+//! Seasons of play fall on a calendar of moons and seasons of the year, each with its own rules.
+
+mod common;
+
+use common::{add_followers, agent, village, OWNER};
+use signs_core::{
+    calendar::{MoonPhase, Phase, YearSeason, WINTER_STAMINA_TOLL, WINTER_TRAVEL_EXHAUSTION},
+    event::TurnEvent,
+    player::{StandingOrder, StandingOrders},
+    prelude::*,
+    report::project,
+};
+
+#[test]
+fn the_moon_turns_every_season_and_the_year_every_three() {
+    let phases: Vec<(YearSeason, MoonPhase)> = (1..=7)
+        .map(|season| {
+            let phase = Phase::of(season);
+            (phase.year_season, phase.moon)
+        })
+        .collect();
+    assert_eq!(
+        phases,
+        [
+            (YearSeason::Spring, MoonPhase::New),
+            (YearSeason::Spring, MoonPhase::Waxing),
+            (YearSeason::Spring, MoonPhase::Full),
+            (YearSeason::Summer, MoonPhase::Waning),
+            (YearSeason::Summer, MoonPhase::New),
+            (YearSeason::Summer, MoonPhase::Waxing),
+            (YearSeason::Autumn, MoonPhase::Full),
+        ]
+    );
+    assert_eq!(Phase::of(13), Phase::of(1));
+    assert_eq!(Phase::of(3).to_string(), "the full moon of Spring");
+}
+
+#[test]
+fn full_moons_make_corruption_cheaper() {
+    let ruleset = Ruleset::default();
+    let full = Phase::of(3).ruleset(&ruleset);
+    assert!(full.corruption_cost < ruleset.corruption_cost);
+    assert!(full.contagion_chance > ruleset.contagion_chance);
+    assert_eq!(
+        Phase::of(2).ruleset(&ruleset).corruption_cost,
+        ruleset.corruption_cost
+    );
+}
+
+#[test]
+fn winter_tires_agents_and_travellers_most() {
+    let ruleset = Ruleset::default();
+    let winter = (1..)
+        .find(|season| Phase::of(*season).year_season == YearSeason::Winter)
+        .unwrap();
+    let cold = Phase::of(winter).ruleset(&ruleset);
+    assert_eq!(cold.stamina_toll, WINTER_STAMINA_TOLL);
+    assert_eq!(cold.travel_exhaustion, WINTER_TRAVEL_EXHAUSTION);
+    let mild = Phase::of(1).ruleset(&ruleset);
+    assert_eq!((mild.stamina_toll, mild.travel_exhaustion), (0, 0));
+}
+
+#[test]
+fn the_new_season_is_announced_under_its_own_phase() {
+    // The last season of spring ends, and summer begins under a waning moon.
+    let events = [TurnEvent::SeasonEnded { season: 3 }];
+    let reports = project(&events, PlayerId(0));
+    let text = reports[0].get_sections().concat();
+    assert_eq!(Phase::after(3), Phase::of(4));
+    assert!(text.contains(&Phase::of(4).to_string()), "{}", text);
+    assert!(!text.contains("Spring"), "{}", text);
+}
+
+#[test]
+fn orders_warn_of_the_winter_toll() {
+    let ruleset = Ruleset::default();
+    let winter = (1..)
+        .find(|season| Phase::of(*season).year_season == YearSeason::Winter)
+        .unwrap();
+    let cold = Phase::of(winter).ruleset(&ruleset);
+    let mut area = village();
+    add_followers(&mut area, 3, 1, None);
+    let mut traveller = agent(OWNER, 0, 10);
+    // Worn down by a long road last season.
+    traveller.stamina = WINTER_STAMINA_TOLL / 2;
+    area.add_agent(traveller.clone());
+
+    for action in [AgentAction::Prostelytize, AgentAction::Brutalize] {
+        assert!(action.invalid_reasons(&traveller, &area).is_none());
+        assert!(action.weary_reason(&traveller, &ruleset).is_none());
+        assert!(action.weary_reason(&traveller, &cold).is_some());
+    }
+    assert!(AgentAction::Recruit
+        .weary_reason(&traveller, &cold)
+        .is_none());
+
+    let mut standing = StandingOrders::default();
+    standing.set(
+        traveller.id,
+        Some(StandingOrder::repeat(AgentAction::Prostelytize)),
+    );
+    let mut turn = PlayerTurn::new(OWNER);
+    standing.issue(&mut turn, [&area], &[], &cold);
+    assert_eq!(turn.get_action(traveller.id), None);
+    assert!(standing.lapsed(traveller.id).is_some());
+}
@@ -0,0 +1,47 @@
+//! Fixtures shared by the rule tests: the players, the village and the agent most of them use.
+
+// Each test binary only uses some of these.
+#![allow(dead_code)]
+
+use signs_core::prelude::*;
+
+pub const OWNER: PlayerId = PlayerId(0);
+pub const RIVAL: PlayerId = PlayerId(1);
+pub const STRANGER: PlayerId = PlayerId(2);
+
+pub const VILLAGE: &str = "Ashford";
+pub const AGENT: &str = "Mordecai";
+
+/// An empty village at the corner of the map.
+pub fn village() -> WorldArea {
+    WorldArea::new(VILLAGE, 0, 0)
+}
+
+/// Adds `count` fully loyal followers of the given power, siding with `affinity`.
+pub fn add_followers(area: &mut WorldArea, count: usize, power: u32, affinity: Option<PlayerId>) {
+    for _ in 0..count {
+        let mut follower = Follower::new(power);
+        follower.join(affinity);
+        area.add_follower(follower);
+    }
+}
+
+/// A village with `count` of the owner's followers in it.
+pub fn owned_village(count: usize) -> WorldArea {
+    let mut area = village();
+    add_followers(&mut area, count, 10, Some(OWNER));
+    area
+}
+
+/// The player's agent with the given number, fresh and unwearied.
+pub fn agent(player: PlayerId, agent: u32, power: u32) -> Agent {
+    Agent::new(AGENT.to_string(), AgentId { player, agent }, (0, 0), power)
+}
+
+/// The default rules without the world events deck, so only the orders change anything.
+pub fn quiet() -> Ruleset {
+    Ruleset {
+        events_per_season: 0,
+        ..Ruleset::default()
+    }
+}
@@ -0,0 +1,94 @@
+//! Corruption creeps from area to area among a player's own followers.
+
+mod common;
+
+use common::{add_followers, quiet, OWNER, RIVAL};
+use signs_core::{
+    economy::Treasury,
+    event::TurnEvent,
+    prelude::*,
+    report::project,
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+fn area(name: &str, x: u32, corrupted: usize, untouched: usize) -> WorldArea {
+    let mut area = WorldArea::new(name, x, 0);
+    area.nearest_neighbors = vec![(1 - x, 0)];
+    add_followers(&mut area, corrupted + untouched, 10, Some(OWNER));
+    for follower in area.followers.iter_mut().take(corrupted) {
+        follower.corrupted = true;
+    }
+    area
+}
+
+fn season(contagion_chance: f64) -> TurnResults {
+    let ruleset = Ruleset {
+        contagion_chance,
+        ..quiet()
+    };
+    apply_turns(
+        1,
+        &ruleset,
+        &Pacts::default(),
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        vec![PlayerTurn::new(OWNER), PlayerTurn::new(RIVAL)],
+        vec![7, 11],
+        vec![area("Ashford", 0, 1, 0), area("Blackmoor", 1, 0, 2)],
+    )
+}
+
+fn spread(results: &TurnResults) -> Vec<(String, PlayerId, u32)> {
+    results
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TurnEvent::CorruptionSpread {
+                location_name,
+                player,
+                count,
+                ..
+            } => Some((location_name.clone(), *player, *count)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn corruption_reaches_a_neighbour_without_a_ritual() {
+    let results = season(1.);
+    assert_eq!(spread(&results), [("Blackmoor".to_string(), OWNER, 1)]);
+    let blackmoor = &results.new_world_areas[&(1, 0)];
+    assert_eq!(blackmoor.corrupted_followers(OWNER), 1);
+    assert_eq!(blackmoor.get_player_followers(OWNER), 2);
+    assert_eq!(
+        results.new_world_areas[&(0, 0)].corrupted_followers(OWNER),
+        1
+    );
+
+    let titles: Vec<String> = project(&results.events, OWNER)
+        .iter()
+        .map(|report| report.get_title())
+        .collect();
+    assert!(titles.contains(&"Corruption spreads".to_string()));
+    assert!(!project(&results.events, RIVAL)
+        .iter()
+        .any(|report| report.get_title() == "Corruption spreads"));
+}
+
+#[test]
+fn corruption_stays_put_without_contagion() {
+    let results = season(0.);
+    assert!(spread(&results).is_empty());
+    assert_eq!(
+        results.new_world_areas[&(1, 0)].corrupted_followers(OWNER),
+        0
+    );
+}
+
+#[test]
+fn chances_out_of_range_are_held_to_certainty_or_never() {
+    assert_eq!(spread(&season(2.)), spread(&season(1.)));
+    assert!(spread(&season(-1.)).is_empty());
+}
@@ -0,0 +1,137 @@
+//! Pacts between players: how offers, acceptances and betrayals resolve within a season, and
+//! what allies spare and share.
+
+mod common;
+
+use common::{add_followers, agent, quiet, village, OWNER, RIVAL, STRANGER};
+use signs_core::{
+    diplomacy::PactChange,
+    economy::Treasury,
+    event::TurnEvent,
+    prelude::*,
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+fn turn(player: PlayerId, diplomacy: Diplomacy) -> PlayerTurn {
+    let mut turn = PlayerTurn::new(player);
+    turn.set_diplomacy(diplomacy.target(), Some(diplomacy));
+    turn
+}
+
+#[test]
+fn crossing_proposals_form_a_pact() {
+    let turns = [
+        turn(RIVAL, Diplomacy::Propose(OWNER)),
+        turn(OWNER, Diplomacy::Propose(RIVAL)),
+    ];
+    for turns in [turns.to_vec(), turns.iter().rev().cloned().collect()] {
+        let mut pacts = Pacts::default();
+        let changes = pacts.apply(&turns);
+        assert!(pacts.are_allied(OWNER, RIVAL));
+        assert!(pacts.proposals.is_empty());
+        assert_eq!(changes, [(OWNER, RIVAL, PactChange::Formed)]);
+    }
+}
+
+#[test]
+fn an_offer_to_oneself_goes_nowhere() {
+    let mut pacts = Pacts::default();
+    let changes = pacts.apply(&[turn(OWNER, Diplomacy::Propose(OWNER))]);
+    assert!(changes.is_empty());
+    assert_eq!(pacts, Pacts::default());
+}
+
+#[test]
+fn a_proposal_crossing_an_old_offer_accepts_it() {
+    let mut pacts = Pacts::default();
+    pacts.apply(&[turn(OWNER, Diplomacy::Propose(RIVAL))]);
+    let changes = pacts.apply(&[
+        turn(RIVAL, Diplomacy::Propose(OWNER)),
+        turn(STRANGER, Diplomacy::Propose(OWNER)),
+    ]);
+    assert_eq!(pacts.alliances, [(OWNER, RIVAL)]);
+    assert_eq!(pacts.proposals, [(STRANGER, OWNER)]);
+    assert_eq!(
+        changes,
+        [
+            (OWNER, RIVAL, PactChange::Formed),
+            (STRANGER, OWNER, PactChange::Proposed)
+        ]
+    );
+}
+
+fn season(pacts: &Pacts, turns: Vec<PlayerTurn>, seed: u64, area: &WorldArea) -> TurnResults {
+    let seeds = turns.iter().map(|_| seed).collect();
+    apply_turns(
+        1,
+        &quiet(),
+        pacts,
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        turns,
+        seeds,
+        vec![area.clone()],
+    )
+}
+
+fn allied() -> Pacts {
+    Pacts {
+        alliances: vec![(OWNER, RIVAL)],
+        ..Pacts::default()
+    }
+}
+
+fn followers_of(results: &TurnResults, player: PlayerId) -> usize {
+    results.new_world_areas[&(0, 0)]
+        .followers
+        .iter()
+        .filter(|follower| follower.affinity == Some(player))
+        .count()
+}
+
+#[test]
+fn allies_never_preach_away_each_others_faithful() {
+    let mut area = village();
+    add_followers(&mut area, 6, 3, Some(RIVAL));
+    add_followers(&mut area, 6, 3, Some(STRANGER));
+    area.add_agent(agent(OWNER, 0, 40));
+    let mut turn = PlayerTurn::new(OWNER);
+    turn.set_action(AgentId::new(0, 0), AgentAction::Prostelytize);
+
+    let mut strangers_converted = 0;
+    for seed in 0..20 {
+        let results = season(&allied(), vec![turn.clone()], seed, &area);
+        assert_eq!(
+            results.new_world_areas,
+            season(&allied(), vec![turn.clone()], seed, &area).new_world_areas
+        );
+        assert_eq!(followers_of(&results, RIVAL), 6);
+        strangers_converted += 6 - followers_of(&results, STRANGER);
+    }
+    assert!(strangers_converted > 0);
+}
+
+#[test]
+fn allies_win_together_once_their_signs_add_up() {
+    let ruleset = quiet();
+    let mut area = village();
+    for (player, signs) in [(OWNER, 4), (RIVAL, ruleset.alliance_sign_count - 4)] {
+        let mut holder = agent(player, 0, 20);
+        holder.signs = signs;
+        area.add_agent(holder);
+    }
+    let turns = vec![PlayerTurn::new(OWNER), PlayerTurn::new(RIVAL)];
+    let game_over = |results: &TurnResults| {
+        results.events.iter().find_map(|event| match event {
+            TurnEvent::GameOver { winner, allies, .. } => Some((*winner, allies.clone())),
+            _ => None,
+        })
+    };
+    for seed in 0..10 {
+        let results = season(&allied(), turns.clone(), seed, &area);
+        assert_eq!(game_over(&results), Some((OWNER, vec![RIVAL])));
+        let results = season(&Pacts::default(), turns.clone(), seed, &area);
+        assert_eq!(game_over(&results), None);
+    }
+}
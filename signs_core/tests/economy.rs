@@ -0,0 +1,114 @@
+//! Tithes: what players earn from the areas they hold, and what their orders cost.
+
+mod common;
+
+use common::{add_followers, agent, quiet, village, OWNER, RIVAL};
+use signs_core::{
+    economy::{controller, income, Treasury},
+    event::TurnEvent,
+    prelude::*,
+    turns::apply_turns,
+    world::AgentNumbers,
+};
+
+fn agent_id(player: PlayerId) -> AgentId {
+    AgentId { player, agent: 0 }
+}
+
+fn parish(owned: u32, rival: u32, locals: u32) -> WorldArea {
+    let mut area = village();
+    for (affinity, count) in [(Some(OWNER), owned), (Some(RIVAL), rival), (None, locals)] {
+        for power in 0..count {
+            add_followers(&mut area, 1, 20 + power, affinity);
+        }
+    }
+    area
+}
+
+fn treasury(balance: u32) -> Treasury {
+    let mut treasury = Treasury::default();
+    treasury.deposit(OWNER, balance);
+    treasury
+}
+
+#[test]
+fn only_the_strongest_player_collects_tithes() {
+    let ruleset = Ruleset::default();
+    let held = parish(3, 1, 0);
+    assert_eq!(controller(&held), Some(OWNER));
+    assert_eq!(income([&held].into_iter(), OWNER, &ruleset), 3);
+    assert_eq!(income([&held].into_iter(), RIVAL, &ruleset), 0);
+
+    let contested = parish(2, 2, 0);
+    assert_eq!(controller(&contested), None);
+    assert_eq!(income([&contested].into_iter(), OWNER, &ruleset), 0);
+}
+
+#[test]
+fn orders_that_cannot_be_paid_for_are_called_off() {
+    let ruleset = Ruleset::default();
+    let mut turn = PlayerTurn::new(OWNER);
+    turn.set_action(agent_id(OWNER), AgentAction::Corrupt);
+    turn.set_action(AgentId::new(0, 1), AgentAction::Corrupt);
+    assert_eq!(turn.spending(&ruleset), ruleset.corruption_cost * 2);
+
+    let mut treasury = treasury(ruleset.corruption_cost);
+    treasury.charge_actions(&mut turn, &ruleset);
+    assert_eq!(treasury.balance(OWNER), 0);
+    assert_eq!(turn.get_action(agent_id(OWNER)), Some(AgentAction::Corrupt));
+    assert_eq!(turn.get_action(AgentId::new(0, 1)), Some(AgentAction::None));
+}
+
+#[test]
+fn bribes_need_an_agent_and_the_money() {
+    let ruleset = quiet();
+    let mut area = parish(0, 0, 3);
+    area.add_agent(agent(OWNER, 0, 20));
+    let bribe = |balance: u32| {
+        let mut turn = PlayerTurn::new(OWNER);
+        turn.set_bribe((0, 0), true);
+        apply_turns(
+            1,
+            &ruleset,
+            &Pacts::default(),
+            &treasury(balance),
+            &AgentNumbers::default(),
+            vec![turn, PlayerTurn::new(RIVAL)],
+            vec![3, 5],
+            vec![area.clone()],
+        )
+    };
+
+    let broke = bribe(ruleset.bribe_cost - 1);
+    assert!(!broke
+        .events
+        .iter()
+        .any(|event| matches!(event, TurnEvent::LocalsBribed { .. })));
+    assert_eq!(
+        broke.treasury.balance(OWNER),
+        ruleset.bribe_cost - 1 + income(broke.new_world_areas.values(), OWNER, &ruleset)
+    );
+    assert_eq!(
+        broke.new_world_areas[&(0, 0)].get_player_followers(OWNER),
+        0
+    );
+
+    let paid = bribe(ruleset.bribe_cost);
+    assert!(paid.events.iter().any(|event| matches!(
+        event,
+        TurnEvent::LocalsBribed { player: OWNER, swayed, .. } if *swayed == ruleset.bribe_sway
+    )));
+    let bribed = &paid.new_world_areas[&(0, 0)];
+    assert_eq!(bribed.get_player_followers(OWNER), ruleset.bribe_sway);
+    // The cheapest locals take the money first.
+    assert!(bribed
+        .followers
+        .iter()
+        .filter(|f| f.affinity == Some(OWNER))
+        .all(|f| f.power < 22));
+    // Now they hold the area, the new followers pay their tithes straight away.
+    assert_eq!(
+        paid.treasury.balance(OWNER),
+        income(paid.new_world_areas.values(), OWNER, &ruleset)
+    );
+}
@@ -0,0 +1,85 @@
+//! Generated maps deal every seat a start of about the same worth, and the fairness report says
+//! how close they came.
+
+mod common;
+
+use common::{add_followers, VILLAGE};
+use signs_core::{
+    fairness::{FairnessReport, Starts, RIVAL_DISTANCE_CAP, SIGN_HOLDER_VALUE},
+    map::{generate_seeded_map, STARTING_AGENTS},
+    prelude::*,
+};
+
+const SEEDS: u64 = 30;
+
+fn town(name: &str, x: u32, y: u32, locals: usize, sign_holders: usize) -> WorldArea {
+    let mut area = WorldArea::new(name, x, y);
+    add_followers(&mut area, locals, 10, None);
+    for follower in area.followers.iter_mut().take(sign_holders) {
+        follower.sign_holder = true;
+    }
+    area
+}
+
+#[test]
+fn the_report_weighs_population_signs_and_room() {
+    let areas = vec![
+        town(VILLAGE, 0, 0, 10, 1),
+        town("Brindle", 1, 0, 5, 0),
+        town("Corvey", 9, 0, 20, 2),
+    ];
+    let starts: Starts = [(PlayerId(0), vec![(0, 0)]), (PlayerId(1), vec![(9, 0)])]
+        .into_iter()
+        .collect();
+    let report = FairnessReport::of_starts(&areas, &starts);
+    let near_ashford = &report.seats[0];
+    assert_eq!(near_ashford.population, 15);
+    assert_eq!(near_ashford.sign_holders, 1);
+    assert_eq!(near_ashford.rival_distance, RIVAL_DISTANCE_CAP);
+    let near_corvey = &report.seats[1];
+    assert_eq!(
+        near_corvey.value - near_ashford.value,
+        5 + SIGN_HOLDER_VALUE
+    );
+    assert_eq!(report.spread(), 5 + SIGN_HOLDER_VALUE);
+    assert!(report.balance() < 1.);
+}
+
+#[test]
+fn generated_starts_are_close_in_worth() {
+    for players in 2..=5 {
+        for seed in 0..SEEDS {
+            let map = generate_seeded_map(seed, (0..players).map(PlayerId).collect());
+            let report = FairnessReport::of(&map);
+            assert_eq!(report.seats.len(), players as usize);
+            assert!(
+                report.balance() >= 0.85,
+                "seed {} with {} players:\n{}",
+                seed,
+                players,
+                report
+            );
+        }
+    }
+}
+
+#[test]
+fn every_seat_gets_its_own_areas() {
+    for seed in 0..SEEDS {
+        let map = generate_seeded_map(seed, (0..4).map(PlayerId).collect());
+        for area in map.areas.iter() {
+            assert!(area.agents.len() <= 1);
+            if area.kind == AreaKind::Ruins {
+                assert!(area.agents.is_empty());
+            }
+        }
+        for player in 0..4 {
+            let agents: u32 = map
+                .areas
+                .iter()
+                .map(|area| area.get_player_agent_count(PlayerId(player)))
+                .sum();
+            assert_eq!(agents as usize, STARTING_AGENTS);
+        }
+    }
+}
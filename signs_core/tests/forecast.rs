@@ -0,0 +1,91 @@
+//! Forecasts rehearse the real rules, so their odds should match what the rules promise.
+
+mod common;
+
+use common::{add_followers, agent, village, OWNER};
+use signs_core::{
+    forecast::{forecast, FORECAST_ROLLOUTS},
+    prelude::*,
+};
+
+fn shrine(sign_holders: usize, others: usize) -> (WorldArea, AgentId) {
+    let mut area = village();
+    add_followers(&mut area, sign_holders + others, 20, Some(OWNER));
+    for follower in area.followers.iter_mut().take(sign_holders) {
+        follower.sign_holder = true;
+    }
+    let agent = agent(OWNER, 0, 40);
+    let agent_id = agent.id;
+    area.add_agent(agent);
+    (area, agent_id)
+}
+
+#[test]
+fn sacrifices_among_sign_holders_always_find_a_sign() {
+    let (area, agent_id) = shrine(3, 0);
+    let odds = forecast(
+        &area,
+        agent_id,
+        &AgentAction::Sacrifice,
+        None,
+        &[],
+        1,
+        FORECAST_ROLLOUTS,
+    )
+    .unwrap();
+    assert_eq!(odds.sign_chance, 1.);
+    assert_eq!(odds.followers_gained, -1.);
+}
+
+#[test]
+fn sacrifices_without_sign_holders_never_do() {
+    let (area, agent_id) = shrine(0, 3);
+    let odds = forecast(
+        &area,
+        agent_id,
+        &AgentAction::Sacrifice,
+        None,
+        &[],
+        1,
+        FORECAST_ROLLOUTS,
+    )
+    .unwrap();
+    assert_eq!(odds.sign_chance, 0.);
+}
+
+#[test]
+fn forecasts_are_repeatable_and_leave_the_area_alone() {
+    let (area, agent_id) = shrine(1, 4);
+    let before = area.clone();
+    let first = forecast(&area, agent_id, &AgentAction::Corrupt, None, &[], 9, 50);
+    let second = forecast(&area, agent_id, &AgentAction::Corrupt, None, &[], 9, 50);
+    assert_eq!(first, second);
+    assert_eq!(area, before);
+    assert!(forecast(
+        &area,
+        agent_id,
+        &AgentAction::Move(1, 1, String::new()),
+        None,
+        &[],
+        9,
+        50
+    )
+    .is_none());
+}
+
+#[test]
+fn a_singled_out_sign_holder_is_always_the_one_sacrificed() {
+    let (area, agent_id) = shrine(1, 4);
+    let sign_holder = area.followers.iter().find(|f| f.sign_holder).unwrap().id;
+    let odds = forecast(
+        &area,
+        agent_id,
+        &AgentAction::Sacrifice,
+        Some(sign_holder),
+        &[],
+        1,
+        FORECAST_ROLLOUTS,
+    )
+    .unwrap();
+    assert_eq!(odds.sign_chance, 1.);
+}
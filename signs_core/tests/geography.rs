@@ -0,0 +1,213 @@
+//! Coasts, rivers and roads on generated maps, and what they do to travellers.
+
+mod common;
+
+use common::{agent, village, OWNER, RIVAL, STRANGER};
+use signs_core::{
+    economy::Treasury,
+    geography::{fill_geography, is_road, is_water, lay_roads},
+    map::{generate_seeded_map, MapDesc, BRIDGE, RIVER, ROAD, VILLAGE, WATER},
+    prelude::*,
+    turns::apply_turns,
+    world::AgentNumbers,
+};
+
+fn players() -> Vec<PlayerId> {
+    vec![OWNER, RIVAL, STRANGER]
+}
+
+fn maps() -> impl Iterator<Item = MapDesc> {
+    (0..30).map(|seed| generate_seeded_map(seed, players()))
+}
+
+#[test]
+fn generated_maps_are_islands_in_the_sea() {
+    for map in maps() {
+        assert_eq!(map.tiles.len() as u32, map.width * map.height);
+        for x in 0..map.width {
+            assert_eq!(map.get_tile(x, 0), WATER);
+            assert_eq!(map.get_tile(x, map.height - 1), WATER);
+        }
+        for y in 0..map.height {
+            assert_eq!(map.get_tile(0, y), WATER);
+            assert_eq!(map.get_tile(map.width - 1, y), WATER);
+        }
+        let water = map.tiles.iter().filter(|tile| **tile == WATER).count();
+        assert!(water < map.tiles.len() / 2);
+    }
+}
+
+#[test]
+fn settlements_stand_on_dry_land() {
+    for map in maps() {
+        for area in map.areas.iter() {
+            let (x, y) = area.world_position;
+            assert!(!is_water(map.get_tile(x, y)));
+        }
+    }
+}
+
+#[test]
+fn rivers_run_to_the_sea() {
+    let mut rivers = 0;
+    for map in maps() {
+        let width = map.width as usize;
+        for (idx, tile) in map.tiles.iter().enumerate() {
+            if *tile != RIVER && *tile != BRIDGE {
+                continue;
+            }
+            rivers += 1;
+            let (x, y) = ((idx % width) as u32, (idx / width) as u32);
+            let banks = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            // Every stretch of river flows on into more water.
+            assert!(banks
+                .iter()
+                .filter(|(x, y)| *x < map.width && *y < map.height)
+                .any(|(x, y)| is_water(map.get_tile(*x, *y))));
+        }
+    }
+    assert!(rivers > 0);
+}
+
+#[test]
+fn roads_join_nearby_settlements() {
+    let (mut roads, mut bridges, mut networks) = (0, 0, 0);
+    for map in maps() {
+        roads += map.tiles.iter().filter(|tile| **tile == ROAD).count();
+        bridges += map.tiles.iter().filter(|tile| **tile == BRIDGE).count();
+        let on_roads = map
+            .areas
+            .iter()
+            .filter_map(|area| area.road)
+            .collect::<HashSet<u32>>();
+        networks += on_roads.len();
+        for road in on_roads {
+            assert!(
+                map.areas
+                    .iter()
+                    .filter(|area| area.road == Some(road))
+                    .count()
+                    >= 2
+            );
+        }
+    }
+    assert!(roads > 0);
+    assert!(bridges > 0);
+    assert!(networks > 0);
+}
+
+/// Three villages in a row: two joined by a road, and the third across a river.
+fn riverside() -> Vec<WorldArea> {
+    let tiles = vec![
+        VILLAGE, ROAD, ROAD, VILLAGE, RIVER, VILLAGE, //
+    ];
+    let mut areas = vec![
+        village(),
+        WorldArea::new("Brookend", 3, 0),
+        WorldArea::new("Crossway", 5, 0),
+    ];
+    fill_geography(6, 1, &tiles, &mut areas);
+    areas
+}
+
+#[test]
+fn rivers_divide_regions_and_roads_join_settlements() {
+    let areas = riverside();
+    assert_eq!(areas[0].region, areas[1].region);
+    assert_ne!(areas[1].region, areas[2].region);
+    assert!(areas[0].road.is_some());
+    assert_eq!(areas[0].road, areas[1].road);
+    assert_eq!(areas[2].road, None);
+}
+
+#[test]
+fn roads_spare_travellers_and_crossings_tire_them() {
+    let areas = riverside();
+    let ruleset = Ruleset {
+        travel_exhaustion: 30,
+        ..Default::default()
+    };
+    assert_eq!(
+        ruleset.travel_cost(&areas[0], &areas[1]),
+        30 - ruleset.road_bonus
+    );
+    assert_eq!(
+        ruleset.travel_cost(&areas[1], &areas[2]),
+        30 + ruleset.crossing_exhaustion
+    );
+
+    // A bridge carries the road, and its travellers, over the river.
+    let tiles = vec![VILLAGE, BRIDGE, VILLAGE];
+    let mut bridged = vec![village(), WorldArea::new("Brookend", 2, 0)];
+    fill_geography(3, 1, &tiles, &mut bridged);
+    assert_ne!(bridged[0].region, bridged[1].region);
+    assert!(is_road(BRIDGE));
+    assert_eq!(ruleset.travel_cost(&bridged[0], &bridged[1]), 10);
+}
+
+#[test]
+fn travellers_are_still_weary_the_season_after() {
+    let mut areas = riverside();
+    areas[1].add_agent(agent(OWNER, 0, 10));
+    let mut turn = PlayerTurn::new(OWNER);
+    turn.actions.insert(
+        AgentId::new(0, 0),
+        AgentAction::Move(5, 0, "Crossway".to_string()),
+    );
+    let ruleset = Ruleset::default();
+    let crossway = apply_turns(
+        1,
+        &ruleset,
+        &Pacts::default(),
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        vec![turn],
+        vec![3],
+        areas,
+    )
+    .get_new_world_area((5, 0))
+    .unwrap();
+    let agent = &crossway.agents[0];
+    assert_eq!(
+        agent.stamina,
+        100 + agent.power - ruleset.crossing_exhaustion
+    );
+}
+
+#[test]
+fn maps_saved_before_geography_load_as_one_region_off_the_road() {
+    let saved = ron::to_string(&village()).unwrap();
+    let before = saved.replace(",region:0,road:None", "");
+    assert_ne!(before, saved);
+    let area: WorldArea = ron::from_str(&before).unwrap();
+    assert_eq!((area.region, area.road), (0, None));
+}
+
+#[test]
+fn neighbouring_villages_share_a_road_without_one_between() {
+    let mut tiles = vec![VILLAGE, VILLAGE, WATER, VILLAGE];
+    lay_roads(4, 1, &mut tiles);
+    assert!(!tiles.iter().any(|tile| is_road(*tile)));
+    let mut areas = vec![
+        village(),
+        WorldArea::new("Brookend", 1, 0),
+        WorldArea::new("Crossway", 3, 0),
+    ];
+    fill_geography(4, 1, &tiles, &mut areas);
+    assert!(areas[0].road.is_some());
+    assert_eq!(areas[0].road, areas[1].road);
+    assert_eq!(areas[2].road, None);
+    let ruleset = Ruleset {
+        travel_exhaustion: 30,
+        ..Default::default()
+    };
+    assert_eq!(
+        ruleset.travel_cost(&areas[0], &areas[1]),
+        30 - ruleset.road_bonus
+    );
+}
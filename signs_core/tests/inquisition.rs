@@ -0,0 +1,149 @@
+//! The Inquisition: where it is sent for, where its inquisitors go, and who they are.
+
+mod common;
+
+use common::{add_followers, agent, quiet, village, OWNER};
+use signs_core::{
+    economy::Treasury,
+    event::TurnEvent,
+    prelude::*,
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+const SUSPICIOUS: u32 = 50;
+
+fn blackmoor() -> WorldArea {
+    let mut area = WorldArea::new("Blackmoor", 1, 0);
+    area.suspicion = SUSPICIOUS;
+    area.nearest_neighbors = vec![(0, 0)];
+    area
+}
+
+fn season(areas: Vec<WorldArea>, numbers: &AgentNumbers) -> TurnResults {
+    apply_turns(
+        1,
+        &quiet(),
+        &Pacts::default(),
+        &Treasury::default(),
+        numbers,
+        vec![PlayerTurn::new(OWNER)],
+        vec![3],
+        areas,
+    )
+}
+
+fn arrivals(results: &TurnResults) -> Vec<String> {
+    results
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TurnEvent::InquisitionArrived { location_name, .. } => Some(location_name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn inquisitors(area: &WorldArea) -> Vec<AgentId> {
+    area.player_agents(INQUISITION)
+        .map(|agent| agent.id)
+        .collect()
+}
+
+#[test]
+fn inquisitors_sent_on_together_arrive_once() {
+    let mut ashford = village();
+    ashford.nearest_neighbors = vec![(1, 0)];
+    ashford.add_agent(agent(INQUISITION, 0, 20));
+    ashford.add_agent(agent(INQUISITION, 1, 20));
+
+    let results = season(vec![ashford, blackmoor()], &AgentNumbers::default());
+    assert_eq!(arrivals(&results), ["Blackmoor"]);
+    assert!(inquisitors(&results.new_world_areas[&(0, 0)]).is_empty());
+    assert_eq!(
+        inquisitors(&results.new_world_areas[&(1, 0)]),
+        [
+            AgentId::new(INQUISITION.0, 0),
+            AgentId::new(INQUISITION.0, 1)
+        ]
+    );
+}
+
+#[test]
+fn inquisitors_who_went_home_are_never_sent_for_again() {
+    // A calm area with nowhere to go sends its inquisitor home.
+    let mut ashford = village();
+    ashford.add_agent(agent(INQUISITION, 4, 20));
+
+    let first = season(vec![ashford, blackmoor()], &AgentNumbers::default());
+    assert!(inquisitors(&first.new_world_areas[&(0, 0)]).is_empty());
+    assert_eq!(
+        inquisitors(&first.new_world_areas[&(1, 0)]),
+        [AgentId::new(INQUISITION.0, 5)]
+    );
+
+    let mut areas: Vec<WorldArea> = first.new_world_areas.values().cloned().collect();
+    // The new inquisitor has gone home too, and Blackmoor is restless again.
+    for area in areas.iter_mut() {
+        area.agents.clear();
+        if area.world_position == (1, 0) {
+            area.suspicion = SUSPICIOUS;
+        }
+    }
+    let second = season(areas, &first.agent_numbers);
+    assert_eq!(
+        inquisitors(&second.new_world_areas[&(1, 0)]),
+        [AgentId::new(INQUISITION.0, 6)]
+    );
+}
+
+#[test]
+fn inquisitors_purge_and_hunt_alike_for_the_same_seeds() {
+    let ruleset = quiet();
+    let mut area = blackmoor();
+    area.nearest_neighbors.clear();
+    add_followers(&mut area, 3, 10, Some(OWNER));
+    for follower in area.followers.iter_mut() {
+        follower.corrupted = true;
+    }
+    add_followers(&mut area, 4, 20, Some(OWNER));
+    area.add_agent(agent(INQUISITION, 0, 30));
+    area.add_agent(agent(OWNER, 0, 5));
+    let season = |seed: u64| {
+        apply_turns(
+            1,
+            &ruleset,
+            &Pacts::default(),
+            &Treasury::default(),
+            &AgentNumbers::default(),
+            vec![PlayerTurn::new(OWNER)],
+            vec![seed],
+            vec![area.clone()],
+        )
+    };
+    for seed in 0..20 {
+        let results = season(seed);
+        assert_eq!(results.new_world_areas, season(seed).new_world_areas);
+        assert_eq!(results.events, season(seed).events);
+
+        let blackmoor = &results.new_world_areas[&(1, 0)];
+        // Only so many of the corrupted are purged in a season.
+        let corrupted = blackmoor
+            .followers
+            .iter()
+            .filter(|follower| follower.corrupted)
+            .count() as u32;
+        assert_eq!(corrupted, 3 - ruleset.inquisitor_purges);
+        assert!(results.events.iter().any(|event| matches!(
+            event,
+            TurnEvent::FollowersPurged { purged, .. } if *purged == ruleset.inquisitor_purges
+        )));
+        // The faithful lose their nerve while the Inquisition watches.
+        assert!(blackmoor
+            .followers
+            .iter()
+            .filter(|follower| !follower.corrupted && follower.affinity == Some(OWNER))
+            .all(|follower| follower.power < 20));
+        assert_eq!(inquisitors(blackmoor), [AgentId::new(INQUISITION.0, 0)]);
+    }
+}
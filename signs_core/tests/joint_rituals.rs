@@ -0,0 +1,144 @@
+//! Joint rituals: several of a player's agents in one area acting as one.
+
+mod common;
+
+use common::{agent, owned_village, quiet, OWNER};
+use signs_core::{
+    economy::{income, Treasury},
+    prelude::*,
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+/// Holds the ritual with enough tithes put by for every agent to pay their part.
+fn ritual(area: &WorldArea, action: AgentAction, seed: u64) -> TurnResults {
+    let cost = action.cost(&quiet()) * area.get_player_agent_count(OWNER);
+    paid_ritual(area, action, seed, cost)
+}
+
+fn paid_ritual(area: &WorldArea, action: AgentAction, seed: u64, balance: u32) -> TurnResults {
+    let mut turn = PlayerTurn::new(OWNER);
+    let agent_ids: Vec<AgentId> = area.player_agents(OWNER).map(|agent| agent.id).collect();
+    turn.set_group_action(&agent_ids, action);
+    let mut treasury = Treasury::default();
+    treasury.deposit(OWNER, balance);
+    apply_turns(
+        1,
+        &quiet(),
+        &Pacts::default(),
+        &treasury,
+        &AgentNumbers::default(),
+        vec![turn],
+        vec![seed],
+        vec![area.clone()],
+    )
+}
+
+fn signs(results: &TurnResults) -> u32 {
+    results.new_world_areas[&(0, 0)]
+        .agents
+        .iter()
+        .map(|agent| agent.signs)
+        .sum()
+}
+
+/// Whether the area's one sign holder, newcomers aside, still holds their sign.
+fn still_holds_a_sign(results: &TurnResults, area: &WorldArea) -> bool {
+    let sign_holder = area.followers.iter().find(|f| f.sign_holder).unwrap().id;
+    results.new_world_areas[&(0, 0)]
+        .followers
+        .iter()
+        .any(|follower| follower.id == sign_holder && follower.sign_holder)
+}
+
+#[test]
+fn a_grand_sacrifice_never_misses_a_sign_holder() {
+    let mut area = owned_village(12);
+    area.followers[7].sign_holder = true;
+    area.followers[7].power = 40;
+    area.add_agent(agent(OWNER, 0, 20));
+    area.add_agent(agent(OWNER, 1, 20));
+    for seed in 0..20 {
+        let results = ritual(&area, AgentAction::GrandSacrifice, seed);
+        assert_eq!(
+            results.new_world_areas,
+            ritual(&area, AgentAction::GrandSacrifice, seed).new_world_areas
+        );
+        assert_eq!(signs(&results), 1);
+        assert!(!still_holds_a_sign(&results, &area));
+    }
+}
+
+#[test]
+fn a_lone_agent_cannot_hold_a_grand_sacrifice() {
+    let mut area = owned_village(12);
+    area.followers[7].sign_holder = true;
+    area.followers[7].power = 40;
+    area.add_agent(agent(OWNER, 0, 20));
+    for seed in 0..20 {
+        let results = ritual(&area, AgentAction::GrandSacrifice, seed);
+        assert_eq!(signs(&results), 0);
+        assert!(still_holds_a_sign(&results, &area));
+    }
+}
+
+#[test]
+fn dark_communion_needs_a_corrupted_agent_to_lead_it() {
+    let mut area = owned_village(12);
+    area.add_agent(agent(OWNER, 0, 20));
+    area.add_agent(agent(OWNER, 1, 20));
+    let corrupted = |results: &TurnResults| {
+        results.new_world_areas[&(0, 0)]
+            .agents
+            .iter()
+            .filter(|agent| agent.corrupted)
+            .count()
+    };
+    let mut led = area.clone();
+    led.agents[0].corrupt();
+    let mut communed = 0;
+    for seed in 0..20 {
+        assert_eq!(
+            corrupted(&ritual(&area, AgentAction::DarkCommunion, seed)),
+            0
+        );
+
+        let results = ritual(&led, AgentAction::DarkCommunion, seed);
+        assert_eq!(
+            results.new_world_areas,
+            ritual(&led, AgentAction::DarkCommunion, seed).new_world_areas
+        );
+        // Nothing but the ritual itself is spent: no corrupted follower is needed.
+        assert_eq!(
+            results.new_world_areas[&(0, 0)]
+                .followers
+                .iter()
+                .filter(|f| f.corrupted)
+                .count(),
+            0
+        );
+        communed += corrupted(&results) - 1;
+    }
+    assert!(communed > 0);
+}
+
+#[test]
+fn every_agent_in_a_dark_communion_pays_the_corruption_cost() {
+    let mut area = owned_village(12);
+    area.add_agent(agent(OWNER, 0, 20));
+    area.add_agent(agent(OWNER, 1, 20));
+    area.agents[0].corrupt();
+    let cost = quiet().corruption_cost;
+    assert_eq!(AgentAction::DarkCommunion.cost(&quiet()), cost);
+    for seed in 0..20 {
+        let results = paid_ritual(&area, AgentAction::DarkCommunion, seed, cost * 2);
+        let tithes = income(results.new_world_areas.values(), OWNER, &quiet());
+        assert_eq!(results.treasury.balance(OWNER), tithes);
+
+        // With one agent's share, the other cannot commune alone, and the share comes back.
+        let results = paid_ritual(&area, AgentAction::DarkCommunion, seed, cost);
+        let tithes = income(results.new_world_areas.values(), OWNER, &quiet());
+        assert_eq!(results.treasury.balance(OWNER), cost + tithes);
+        assert!(!results.new_world_areas[&(0, 0)].agents[1].corrupted);
+    }
+}
@@ -0,0 +1,83 @@
+//! Followers only stay loyal to a player who looks after them.
+
+mod common;
+
+use common::{agent, owned_village, OWNER, RIVAL, STRANGER};
+use signs_core::{
+    event::{LossReason, TurnEvent},
+    report::{project, TurnReportEvent},
+    world::FULL_LOYALTY,
+};
+
+#[test]
+fn followers_with_an_agent_stay_loyal() {
+    let mut area = owned_village(3);
+    area.add_agent(agent(OWNER, 0, 20));
+    area.followers[0].loyalty = 1;
+    for _ in 0..FULL_LOYALTY * 2 {
+        assert!(area.tend_loyalty(1).is_empty());
+    }
+    assert!(area
+        .followers
+        .iter()
+        .all(|f| f.affinity == Some(OWNER) && f.loyalty == FULL_LOYALTY));
+}
+
+#[test]
+fn neglected_followers_defect_to_the_strongest_rival() {
+    let mut area = owned_village(3);
+    area.add_agent(agent(RIVAL, 0, 5));
+    area.add_agent(agent(STRANGER, 0, 4));
+    for _ in 1..FULL_LOYALTY {
+        assert!(area.tend_loyalty(1).is_empty());
+    }
+    assert_eq!(area.tend_loyalty(1), [(OWNER, Some(RIVAL), 3)]);
+    assert_eq!(area.get_player_power(OWNER), 0);
+    assert_eq!(area.get_player_power(RIVAL), 35);
+}
+
+#[test]
+fn neglected_followers_with_no_rival_follow_no_one() {
+    let mut area = owned_village(2);
+    assert_eq!(area.tend_loyalty(FULL_LOYALTY), [(OWNER, None, 2)]);
+    assert!(area.followers.iter().all(|f| f.affinity.is_none()));
+}
+
+#[test]
+fn corrupted_followers_never_defect() {
+    let mut area = owned_village(2);
+    area.followers[0].corrupted = true;
+    assert_eq!(area.tend_loyalty(FULL_LOYALTY), [(OWNER, None, 1)]);
+    assert_eq!(area.followers[0].affinity, Some(OWNER));
+}
+
+#[test]
+fn brutality_drives_followers_off_and_only_their_player_hears() {
+    let mut area = owned_village(2);
+    area.add_agent(agent(OWNER, 0, 20));
+    area.followers[1].loyalty = 2;
+    assert_eq!(area.weaken_loyalty(OWNER, 2), Some((None, 1)));
+    assert_eq!(area.followers[1].affinity, None);
+    assert_eq!(area.followers[0].affinity, Some(OWNER));
+    assert_eq!(area.get_player_power(OWNER), 30);
+
+    let events = [TurnEvent::FollowersDefected {
+        location: (0, 0),
+        location_name: area.name.clone(),
+        player: OWNER,
+        defected_to: None,
+        count: 1,
+        reason: LossReason::Brutality,
+    }];
+    let reports = project(&events, OWNER);
+    assert!(matches!(
+        reports.as_slice(),
+        [TurnReportEvent::FollowersLost {
+            reason: LossReason::Brutality,
+            count: 1,
+            ..
+        }]
+    ));
+    assert_eq!(reports[0].get_title(), "Followers turn away");
+    assert!(project(&events, RIVAL).is_empty());
+}
@@ -0,0 +1,180 @@
+//! Names spun from the themed grammars: never empty, never repeated on a map or a roster.
+
+mod common;
+
+use common::{add_followers, village, OWNER};
+use signs_core::{
+    economy::Treasury,
+    event::TurnEvent,
+    map::generate_seeded_map,
+    names::{default_theme, theme, themes, NameGrammar, AGENT},
+    prelude::*,
+    scenario::Scenario,
+    turns::apply_turns,
+    world::AgentNumbers,
+};
+
+const CROSSROADS: &str = include_str!("../../assets/scenarios/crossroads.ron");
+
+const TINY: &str = r#"(
+    id: "tiny",
+    name: "Tiny",
+    rules: {
+        "village": ["Ford"],
+        "city": ["Burg"],
+        "temple": ["Shrine"],
+        "ruins": ["Ruins of {city}"],
+        "stronghold": ["Keep"],
+        "agent": ["Ax"],
+        "inquisitor": ["Inquisitor {agent}"],
+    },
+)"#;
+
+#[test]
+fn every_theme_that_ships_loads() {
+    let ids: Vec<&str> = themes().iter().map(|theme| theme.id.as_str()).collect();
+    assert_eq!(ids, ["kingdoms", "fenland"]);
+    assert_eq!(default_theme(), "kingdoms");
+    for theme in themes() {
+        theme.validate().unwrap();
+    }
+    assert_eq!(theme("no such theme"), &themes()[0]);
+}
+
+#[test]
+fn generated_maps_never_repeat_a_name() {
+    for seed in 0..30 {
+        let map = generate_seeded_map(seed, (0..5).map(PlayerId).collect());
+        let mut area_names = HashSet::new();
+        let mut agent_names = HashSet::new();
+        for area in map.areas.iter() {
+            assert!(area_names.insert(area.name.clone()), "{}", area.name);
+            for agent in area.agents.iter() {
+                assert!(agent_names.insert(agent.name.clone()), "{}", agent.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn names_have_no_gaps() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for theme in themes() {
+        for rule in theme.rules().keys() {
+            for _ in 0..50 {
+                let name = theme.expand(&mut rng, rule);
+                assert!(!name.is_empty());
+                assert_eq!(name.trim(), name);
+                assert!(!name.contains("  "), "{}", name);
+                assert!(!name.contains('{') && !name.contains('}'), "{}", name);
+            }
+        }
+    }
+}
+
+#[test]
+fn taken_names_are_numbered() {
+    let grammar = NameGrammar::from_ron(TINY).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut taken = HashSet::new();
+    let names: Vec<String> = (0..4)
+        .map(|_| grammar.unique(&mut rng, AGENT, &mut taken))
+        .collect();
+    assert_eq!(names, ["Ax", "Ax II", "Ax III", "Ax IV"]);
+}
+
+#[test]
+fn malformed_grammars_are_refused() {
+    assert!(NameGrammar::from_ron(&TINY.replace(r#""stronghold": ["Keep"],"#, "")).is_err());
+    assert!(NameGrammar::from_ron(&TINY.replace("Ruins of {city}", "Ruins of {town}")).is_err());
+    assert!(NameGrammar::from_ron(&TINY.replace("Ruins of {city}", "Ruins of {city")).is_err());
+    assert!(NameGrammar::from_ron(&TINY.replace(r#"["Burg"]"#, r#"["Burg", "{city}"]"#)).is_err());
+    assert!(NameGrammar::from_ron(&TINY.replace(r#"["Ford"]"#, r#"[""]"#)).is_err());
+}
+
+#[test]
+fn grammars_are_validated_wherever_they_are_read() {
+    let open = TINY.replace("Ruins of {city}", "Ruins of {city");
+    assert!(ron::from_str::<Vec<NameGrammar>>(&format!("[{}]", TINY)).is_ok());
+    assert!(ron::from_str::<Vec<NameGrammar>>(&format!("[{}]", open)).is_err());
+}
+
+#[test]
+fn scenarios_name_from_their_own_theme() {
+    let scenario = Scenario::from_ron(CROSSROADS).unwrap();
+    assert_eq!(scenario.names.as_deref(), Some("fenland"));
+    assert_eq!(scenario.ruleset(&Ruleset::default()).names, "fenland");
+    let map = scenario.map_desc(vec![PlayerId(0), PlayerId(1)]).unwrap();
+    let fenland = theme("fenland");
+    for agent in map.areas.iter().flat_map(|area| area.agents.iter()) {
+        // Fenfolk go by a given name and a family name.
+        let (given, family) = agent.name.split_once(' ').unwrap();
+        assert!(fenland.rules()["given"].iter().any(|name| name == given));
+        assert!(fenland.rules()["family"].iter().any(|name| name == family));
+    }
+
+    let unknown = CROSSROADS.replace(r#"Some("fenland")"#, r#"Some("atlantis")"#);
+    assert!(Scenario::from_ron(&unknown).is_err());
+    let twins = CROSSROADS.replace("\"Eastford\"", "\"Westford\"");
+    assert!(Scenario::from_ron(&twins).is_err());
+}
+
+#[test]
+fn agents_are_named_from_the_rules_theme() {
+    let fenland = Ruleset {
+        names: "fenland".to_string(),
+        ..Ruleset::default()
+    };
+    // Fenfolk go by a given name and a family name.
+    let is_fenfolk = |name: &str| {
+        name.split_once(' ').is_some_and(|(given, _)| {
+            theme("fenland").rules()["given"]
+                .iter()
+                .any(|name| name == given)
+        })
+    };
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0..20 {
+        let name = generate_agent_name(&mut rng, &fenland);
+        assert!(is_fenfolk(&name), "{}", name);
+    }
+    let map = generate_map(vec![PlayerId(0), PlayerId(1)], &fenland);
+    for agent in map.areas.iter().flat_map(|area| area.agents.iter()) {
+        assert!(is_fenfolk(&agent.name), "{}", agent.name);
+    }
+}
+
+#[test]
+fn recruits_never_share_a_name_with_an_agent() {
+    let mut area = village();
+    add_followers(&mut area, 1, 20, Some(OWNER));
+    // The recruiter already goes by the name the follower was born with.
+    let namesake = area.followers[0].name(theme(&Ruleset::default().names));
+    area.add_agent(Agent::new(namesake.clone(), AgentId::new(0, 0), (0, 0), 20));
+
+    let mut turn = PlayerTurn::new(OWNER);
+    turn.set_action(AgentId::new(0, 0), AgentAction::Recruit);
+    let mut treasury = Treasury::default();
+    treasury.deposit(OWNER, 50);
+    let results = apply_turns(
+        1,
+        &Ruleset::default(),
+        &Pacts::default(),
+        &treasury,
+        &AgentNumbers::default(),
+        vec![turn],
+        vec![3],
+        vec![area],
+    );
+    let recruit = results
+        .events
+        .iter()
+        .find_map(|event| match event {
+            TurnEvent::FollowerPromoted { agent_name, .. } => Some(agent_name.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(recruit, format!("{} II", namesake));
+    let ashford = &results.new_world_areas[&(0, 0)];
+    assert!(ashford.agents.iter().any(|agent| agent.name == recruit));
+}
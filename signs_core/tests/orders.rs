@@ -0,0 +1,183 @@
+//! Planning orders: taking changes back within a season, and leaving some standing across seasons.
+
+mod common;
+
+use common::{add_followers, agent, village, OWNER};
+use signs_core::{
+    agent::CORRUPT_POWER,
+    player::{OrderHistory, StandingOrder, StandingOrders},
+    prelude::*,
+};
+
+fn agents() -> [AgentId; 3] {
+    [AgentId::new(0, 0), AgentId::new(0, 1), AgentId::new(0, 2)]
+}
+
+fn planned_turn() -> PlayerTurn {
+    let mut turn = PlayerTurn::new(OWNER);
+    for agent_id in agents() {
+        turn.initialize_agent(agent_id);
+    }
+    turn
+}
+
+#[test]
+fn a_joint_ritual_is_undone_in_one_step() {
+    let mut turn = planned_turn();
+    let mut history = OrderHistory::default();
+    let [first, second, third] = agents();
+    history.record(&mut turn, |turn| {
+        turn.set_action(third, AgentAction::Prostelytize)
+    });
+    history.record(&mut turn, |turn| {
+        turn.set_group_action(&[first, second], AgentAction::GrandSacrifice)
+    });
+    assert!(history.undo(&mut turn));
+    assert_eq!(turn.get_action(first), Some(AgentAction::None));
+    assert_eq!(turn.get_action(second), Some(AgentAction::None));
+    assert_eq!(turn.get_action(third), Some(AgentAction::Prostelytize));
+    assert!(history.redo(&mut turn));
+    assert_eq!(turn.get_action(second), Some(AgentAction::GrandSacrifice));
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn a_new_change_forgets_what_was_undone() {
+    let mut turn = planned_turn();
+    let mut history = OrderHistory::default();
+    let [first, _, _] = agents();
+    history.record(&mut turn, |turn| {
+        turn.set_action(first, AgentAction::Brutalize)
+    });
+    history.undo(&mut turn);
+    history.record(&mut turn, |turn| {
+        turn.set_action(first, AgentAction::Corrupt)
+    });
+    assert!(!history.redo(&mut turn));
+    assert_eq!(turn.get_action(first), Some(AgentAction::Corrupt));
+}
+
+#[test]
+fn clearing_keeps_every_agent_listed_and_can_be_undone() {
+    let mut turn = planned_turn();
+    let mut history = OrderHistory::default();
+    let [first, second, _] = agents();
+    history.record(&mut turn, |turn| {
+        turn.set_action(first, AgentAction::Sacrifice)
+    });
+    history.record(&mut turn, |turn| {
+        turn.set_action(second, AgentAction::Sacrifice)
+    });
+    history.record(&mut turn, |turn| turn.clear_actions());
+    assert_eq!(turn.get_unassigned_agents(), 3);
+    // Clearing again changes nothing, so there is nothing new to undo.
+    history.record(&mut turn, |turn| turn.clear_actions());
+    assert!(history.undo(&mut turn));
+    assert_eq!(turn.get_unassigned_agents(), 1);
+}
+
+#[test]
+fn targets_and_bribes_are_taken_back_and_cleared_with_the_orders() {
+    let mut turn = planned_turn();
+    let mut history = OrderHistory::default();
+    let [first, _, _] = agents();
+    let follower = FollowerId {
+        home: (0, 0),
+        number: 1,
+    };
+    history.record(&mut turn, |turn| {
+        turn.set_action(first, AgentAction::Sacrifice)
+    });
+    history.record(&mut turn, |turn| turn.set_target(first, Some(follower)));
+    history.record(&mut turn, |turn| turn.set_bribe((0, 0), true));
+
+    history.record(&mut turn, |turn| turn.clear_actions());
+    assert_eq!(turn.get_target(first), None);
+    assert!(!turn.is_bribing((0, 0)));
+
+    assert!(history.undo(&mut turn));
+    assert_eq!(turn.get_action(first), Some(AgentAction::Sacrifice));
+    assert_eq!(turn.get_target(first), Some(follower));
+    assert!(turn.is_bribing((0, 0)));
+
+    assert!(history.undo(&mut turn));
+    assert!(!turn.is_bribing((0, 0)));
+    assert!(history.undo(&mut turn));
+    assert_eq!(turn.get_target(first), None);
+    assert!(history.redo(&mut turn));
+    assert_eq!(turn.get_target(first), Some(follower));
+}
+
+fn village_with(agent_power: u32, followers: &[(Option<PlayerId>, u32)]) -> WorldArea {
+    let mut area = village();
+    area.add_agent(agent(OWNER, 0, agent_power));
+    for (affinity, power) in followers {
+        add_followers(&mut area, 1, *power, *affinity);
+    }
+    area
+}
+
+#[test]
+fn standing_orders_are_given_each_season() {
+    let [first, _, _] = agents();
+    let mut standing = StandingOrders::default();
+    standing.set(
+        first,
+        Some(StandingOrder::repeat(AgentAction::Prostelytize)),
+    );
+    let area = village_with(10, &[(None, 1)]);
+    for _ in 0..2 {
+        let mut turn = PlayerTurn::new(OWNER);
+        standing.issue(&mut turn, [&area], &[], &Ruleset::default());
+        assert_eq!(turn.get_action(first), Some(AgentAction::Prostelytize));
+    }
+    assert!(standing.lapsed(first).is_none());
+}
+
+#[test]
+fn standing_orders_switch_once_the_condition_is_met() {
+    let [first, _, _] = agents();
+    let mut standing = StandingOrders::default();
+    standing.set(
+        first,
+        Some(StandingOrder::until(
+            AgentAction::Prostelytize,
+            CORRUPT_POWER,
+            AgentAction::Corrupt,
+        )),
+    );
+    let weak = village_with(10, &[(None, 1), (Some(OWNER), 5)]);
+    let mut turn = PlayerTurn::new(OWNER);
+    standing.issue(&mut turn, [&weak], &[], &Ruleset::default());
+    assert_eq!(turn.get_action(first), Some(AgentAction::Prostelytize));
+
+    let strong = village_with(10, &[(None, 1), (Some(OWNER), CORRUPT_POWER)]);
+    let mut turn = PlayerTurn::new(OWNER);
+    standing.issue(&mut turn, [&strong], &[], &Ruleset::default());
+    assert_eq!(turn.get_action(first), Some(AgentAction::Corrupt));
+    assert_eq!(
+        standing.get(first),
+        Some(&StandingOrder::repeat(AgentAction::Corrupt))
+    );
+}
+
+#[test]
+fn standing_orders_lapse_when_they_become_invalid() {
+    let [first, _, _] = agents();
+    let mut standing = StandingOrders::default();
+    standing.set(
+        first,
+        Some(StandingOrder::repeat(AgentAction::Prostelytize)),
+    );
+    // Nobody left here who could be swayed.
+    let area = village_with(10, &[(Some(OWNER), 20)]);
+    let mut turn = PlayerTurn::new(OWNER);
+    standing.issue(&mut turn, [&area], &[], &Ruleset::default());
+    assert_eq!(turn.get_action(first), None);
+    assert!(standing.get(first).is_none());
+    let lapsed = standing.lapsed(first).unwrap();
+    assert_eq!(lapsed.order.action, AgentAction::Prostelytize);
+    // The lapse is only news for the season it happened in.
+    standing.issue(&mut turn, [&area], &[], &Ruleset::default());
+    assert!(standing.lapsed(first).is_none());
+}
@@ -0,0 +1,163 @@
+//! Births between seasons: seeded so every client agrees, and held to the ruleset's limits.
+
+mod common;
+
+use common::{add_followers, owned_village, quiet, OWNER, STRANGER};
+use signs_core::{
+    calendar::Phase,
+    economy::Treasury,
+    event::TurnEvent,
+    map::generate_seeded_map,
+    prelude::*,
+    report::{project, TurnReportEvent},
+    turns::apply_turns,
+    world::{AgentNumbers, TEMPLE_SIGN_HOLDERS},
+};
+
+fn grown(seed: u64, ruleset: &Ruleset) -> Vec<(WorldArea, (u32, u32))> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    generate_seeded_map(seed, vec![OWNER, PlayerId(1)])
+        .areas
+        .into_iter()
+        .map(|mut area| {
+            let grown = area.grow_population(&mut rng, ruleset);
+            (area, grown)
+        })
+        .collect()
+}
+
+#[test]
+fn the_same_seed_brings_the_same_births() {
+    let ruleset = Ruleset::default();
+    for seed in 0..20 {
+        let before = generate_seeded_map(seed, vec![OWNER, PlayerId(1)]).areas;
+        let after = grown(seed, &ruleset);
+        assert_eq!(after, grown(seed, &ruleset));
+        for (area, (area_after, (births, sign_holders))) in before.iter().zip(after.iter()) {
+            let sign_holder_limit = if area.kind == AreaKind::Temple {
+                ruleset.sign_holder_limit + TEMPLE_SIGN_HOLDERS
+            } else {
+                ruleset.sign_holder_limit
+            };
+            assert!(*births <= ruleset.births(area.kind), "{}", area.name);
+            assert_eq!(
+                area_after.followers.len(),
+                area.followers.len() + *births as usize
+            );
+            assert!(
+                area_after.followers.len() as u32
+                    <= ruleset
+                        .population_limit(area.kind)
+                        .max(area.followers.len() as u32)
+            );
+            // Newcomers arrive without any loyalties.
+            assert!(area_after.followers[area.followers.len()..]
+                .iter()
+                .all(|follower| follower.affinity.is_none()));
+            assert_eq!(
+                area_after.sign_holder_count(),
+                area.sign_holder_count() + sign_holders
+            );
+            if *sign_holders > 0 {
+                assert!(area_after.sign_holder_count() <= sign_holder_limit);
+            }
+        }
+    }
+}
+
+#[test]
+fn a_full_village_has_no_room_for_newcomers() {
+    let ruleset = Ruleset {
+        village_births: 10,
+        sign_holder_birth_chance: 1.,
+        ..Ruleset::default()
+    };
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut full = owned_village(ruleset.village_population_limit as usize);
+        assert_eq!(full.grow_population(&mut rng, &ruleset), (0, 0));
+
+        let mut nearly = owned_village(ruleset.village_population_limit as usize - 1);
+        let (births, sign_holders) = nearly.grow_population(&mut rng, &ruleset);
+        assert!(births <= 1);
+        assert_eq!(sign_holders, births);
+        assert!(nearly.followers.len() as u32 <= ruleset.village_population_limit);
+    }
+}
+
+#[test]
+fn seasons_resolved_from_the_same_seeds_grow_alike() {
+    let season = |seeds: Vec<u64>| {
+        let mut area = owned_village(0);
+        add_followers(&mut area, 5, 10, None);
+        apply_turns(
+            1,
+            &quiet(),
+            &Pacts::default(),
+            &Treasury::default(),
+            &AgentNumbers::default(),
+            vec![PlayerTurn::new(OWNER)],
+            seeds,
+            vec![area],
+        )
+        .new_world_areas
+    };
+    for seed in 0..20 {
+        let ashford = &season(vec![seed])[&(0, 0)];
+        assert_eq!(ashford, &season(vec![seed])[&(0, 0)]);
+        // The season's phase may bring more births than the ruleset alone.
+        let births = Phase::of(1).ruleset(&quiet()).village_births;
+        assert!(ashford.followers.len() <= 5 + births as usize);
+    }
+}
+
+#[test]
+fn births_are_reported_to_those_with_standing_there() {
+    let ruleset = Ruleset {
+        village_births: 10,
+        sign_holder_birth_chance: 1.,
+        ..quiet()
+    };
+    let results = apply_turns(
+        1,
+        &ruleset,
+        &Pacts::default(),
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        vec![PlayerTurn::new(OWNER), PlayerTurn::new(STRANGER)],
+        vec![11, 12],
+        vec![owned_village(5)],
+    );
+    let ashford = &results.new_world_areas[&(0, 0)];
+    let (births, sign_holders) = results
+        .events
+        .iter()
+        .find_map(|event| match event {
+            TurnEvent::FollowersBorn {
+                births,
+                sign_holders,
+                ..
+            } => Some((*births, *sign_holders)),
+            _ => None,
+        })
+        .expect("the village had room to grow");
+    assert_eq!(ashford.followers.len(), 5 + births as usize);
+    assert_eq!(ashford.sign_holder_count(), sign_holders);
+    assert_eq!(sign_holders, 1);
+
+    let heard = |player| {
+        project(&results.events, player)
+            .into_iter()
+            .filter_map(|report| match report {
+                TurnReportEvent::Births {
+                    births,
+                    sign_holders,
+                    ..
+                } => Some((births, sign_holders)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(heard(OWNER), [(births, sign_holders)]);
+    assert!(heard(STRANGER).is_empty());
+}
@@ -0,0 +1,171 @@
+//! Agents may raise strong followers into new agents, for a price and up to a limit.
+
+mod common;
+
+use common::{add_followers, agent, quiet, village, OWNER, RIVAL};
+use signs_core::{
+    economy::{income, Treasury},
+    event::TurnEvent,
+    prelude::*,
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+fn chapel(agents: &[u32]) -> WorldArea {
+    let mut area = village();
+    add_followers(&mut area, 3, 20, Some(OWNER));
+    for id in agents {
+        area.add_agent(agent(OWNER, *id, 20));
+    }
+    area
+}
+
+fn recruit(ruleset: &Ruleset, agents: &[u32], recruiters: &[u32], balance: u32) -> TurnResults {
+    recruit_in(
+        ruleset,
+        chapel(agents),
+        &AgentNumbers::default(),
+        recruiters,
+        balance,
+    )
+}
+
+fn recruit_in(
+    ruleset: &Ruleset,
+    area: WorldArea,
+    numbers: &AgentNumbers,
+    recruiters: &[u32],
+    balance: u32,
+) -> TurnResults {
+    let mut turn = PlayerTurn::new(OWNER);
+    for agent in recruiters {
+        turn.set_action(AgentId::new(0, *agent), AgentAction::Recruit);
+    }
+    let mut treasury = Treasury::default();
+    treasury.deposit(OWNER, balance);
+    apply_turns(
+        1,
+        ruleset,
+        &Pacts::default(),
+        &treasury,
+        numbers,
+        vec![turn, PlayerTurn::new(RIVAL)],
+        vec![3, 5],
+        vec![area],
+    )
+}
+
+fn recruited(results: &TurnResults) -> Vec<AgentId> {
+    results
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TurnEvent::FollowerPromoted { agent_id, .. } => Some(*agent_id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// What the season's end paid in, on top of whatever was left unspent.
+fn tithes(results: &TurnResults) -> u32 {
+    income(results.new_world_areas.values(), OWNER, &Ruleset::default())
+}
+
+#[test]
+fn recruits_get_ids_past_the_highest_agent() {
+    let ruleset = quiet();
+    let results = recruit(&ruleset, &[0, 5], &[0, 5], ruleset.recruit_cost * 2);
+    assert_eq!(
+        recruited(&results),
+        [AgentId::new(0, 6), AgentId::new(0, 7)]
+    );
+    let chapel = &results.new_world_areas[&(0, 0)];
+    assert_eq!(chapel.agents.len(), 4);
+    assert_eq!(chapel.get_player_followers(OWNER), 1);
+    assert_eq!(results.treasury.balance(OWNER), tithes(&results));
+}
+
+#[test]
+fn recruiting_stops_at_the_agent_limit() {
+    let ruleset = Ruleset {
+        agent_limit: 3,
+        ..quiet()
+    };
+    let results = recruit(&ruleset, &[0, 1], &[0, 1], ruleset.recruit_cost * 2);
+    assert_eq!(recruited(&results), [AgentId::new(0, 2)]);
+    assert_eq!(results.new_world_areas[&(0, 0)].agents.len(), 3);
+    assert_eq!(
+        results.new_world_areas[&(0, 0)].get_player_followers(OWNER),
+        2
+    );
+}
+
+#[test]
+fn recruiting_needs_the_money() {
+    let ruleset = quiet();
+    let results = recruit(&ruleset, &[0], &[0], ruleset.recruit_cost - 1);
+    assert!(recruited(&results).is_empty());
+    assert_eq!(
+        results.new_world_areas[&(0, 0)].get_player_followers(OWNER),
+        3
+    );
+    assert_eq!(
+        results.treasury.balance(OWNER),
+        ruleset.recruit_cost - 1 + tithes(&results)
+    );
+}
+
+#[test]
+fn ids_of_lost_agents_are_never_handed_out_again() {
+    let ruleset = quiet();
+    let first = recruit(&ruleset, &[0, 5], &[0, 5], ruleset.recruit_cost * 2);
+    let mut chapel = first.new_world_areas[&(0, 0)].clone();
+    // The newest agents are taken off the board before the next season.
+    for agent in [5, 6, 7] {
+        chapel.remove_agent(AgentId::new(0, agent));
+    }
+    add_followers(&mut chapel, 1, 20, Some(OWNER));
+    let second = recruit_in(
+        &ruleset,
+        chapel,
+        &first.agent_numbers,
+        &[0],
+        ruleset.recruit_cost,
+    );
+    assert_eq!(recruited(&second), [AgentId::new(0, 8)]);
+}
+
+#[test]
+fn recruiting_at_the_agent_limit_costs_nothing() {
+    let ruleset = Ruleset {
+        agent_limit: 2,
+        ..quiet()
+    };
+    let balance = ruleset.recruit_cost * 2;
+    let results = recruit(&ruleset, &[0, 1], &[0, 1], balance);
+    assert!(recruited(&results).is_empty());
+    assert_eq!(
+        results.new_world_areas[&(0, 0)].get_player_followers(OWNER),
+        3
+    );
+    assert_eq!(results.treasury.balance(OWNER), balance + tithes(&results));
+}
+
+#[test]
+fn recruiting_without_a_follower_to_raise_costs_nothing() {
+    let ruleset = quiet();
+    let mut empty = village();
+    empty.add_agent(agent(OWNER, 0, 20));
+    let results = recruit_in(
+        &ruleset,
+        empty,
+        &AgentNumbers::default(),
+        &[0],
+        ruleset.recruit_cost,
+    );
+    assert!(recruited(&results).is_empty());
+    assert_eq!(
+        results.treasury.balance(OWNER),
+        ruleset.recruit_cost + tithes(&results)
+    );
+}
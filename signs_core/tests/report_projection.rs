@@ -0,0 +1,113 @@
+//! The report rules on their own: which parts of a season each player gets to hear about.
+
+mod common;
+
+use common::{AGENT, OWNER, RIVAL, STRANGER, VILLAGE};
+use signs_core::{
+    diplomacy::PactChange,
+    event::TurnEvent,
+    prelude::*,
+    report::{project, TurnReportEvent},
+};
+
+fn agent() -> AgentId {
+    AgentId {
+        player: OWNER,
+        agent: 0,
+    }
+}
+
+fn titles(events: &[TurnEvent], player: PlayerId) -> Vec<String> {
+    project(events, player)
+        .iter()
+        .map(|report| report.get_title())
+        .collect()
+}
+
+#[test]
+fn moves_are_only_spotted_by_the_powerful() {
+    let events = [TurnEvent::AgentMoved {
+        location: (1, 2),
+        location_name: VILLAGE.to_string(),
+        agent_id: agent(),
+        agent_name: AGENT.to_string(),
+        witnesses: vec![(OWNER, 20), (RIVAL, 11), (STRANGER, 10)],
+    }];
+    assert_eq!(titles(&events, OWNER), ["Mordecai arrived"]);
+    assert_eq!(titles(&events, RIVAL), ["Agent Spotted!"]);
+    assert!(titles(&events, STRANGER).is_empty());
+}
+
+#[test]
+fn pact_offers_are_private_but_betrayals_are_not() {
+    let events = [
+        TurnEvent::PactChanged {
+            player: OWNER,
+            other: RIVAL,
+            change: PactChange::Proposed,
+        },
+        TurnEvent::PactChanged {
+            player: RIVAL,
+            other: OWNER,
+            change: PactChange::Broken,
+        },
+    ];
+    assert_eq!(titles(&events, OWNER), ["Betrayal!"]);
+    assert_eq!(titles(&events, RIVAL), ["A pact is offered", "Betrayal!"]);
+    assert_eq!(titles(&events, STRANGER), ["Betrayal!"]);
+}
+
+#[test]
+fn others_only_find_the_bodies_and_signs_of_a_sacrifice() {
+    let events = [
+        TurnEvent::AgentActed {
+            location: (1, 2),
+            location_name: VILLAGE.to_string(),
+            agents: vec![agent()],
+            agent_name: AGENT.to_string(),
+            action: AgentAction::Sacrifice,
+            success_amount: 1,
+            fail_amount: 2,
+        },
+        TurnEvent::SignFound {
+            location: (1, 2),
+            location_name: VILLAGE.to_string(),
+            agent_id: agent(),
+        },
+    ];
+    let reports = project(&events, OWNER);
+    assert!(matches!(
+        reports[..],
+        [
+            TurnReportEvent::AgentAction { .. },
+            TurnReportEvent::SignSeen { mine: true, .. }
+        ]
+    ));
+    let reports = project(&events, STRANGER);
+    assert!(matches!(
+        reports[..],
+        [
+            TurnReportEvent::Sacrificed {
+                follower: false,
+                ..
+            },
+            TurnReportEvent::SignSeen { mine: false, .. }
+        ]
+    ));
+}
+
+#[test]
+fn brutality_is_only_heard_where_a_player_has_power() {
+    let events = [TurnEvent::LocalsBrutalized {
+        location: (1, 2),
+        location_name: VILLAGE.to_string(),
+        agent_id: agent(),
+        agent_name: AGENT.to_string(),
+        swayed: 1,
+        fled: 3,
+        witnesses: vec![(OWNER, 30), (RIVAL, 4)],
+    }];
+    assert_eq!(titles(&events, OWNER), ["Brutality"]);
+    assert_eq!(titles(&events, RIVAL), ["Violence erupts!"]);
+    assert!(titles(&events, STRANGER).is_empty());
+}
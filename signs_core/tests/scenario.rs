@@ -0,0 +1,180 @@
+//! Hand-authored scenarios: loading them from RON, laying them out and playing to their endings.
+
+use signs_core::{
+    economy::Treasury,
+    event::TurnEvent,
+    map::{area_tile, generate_seeded_map},
+    prelude::*,
+    ruleset::WinCondition,
+    scenario::Scenario,
+    turns::apply_turns,
+    world::AgentNumbers,
+};
+
+const CROSSROADS: &str = include_str!("../../assets/scenarios/crossroads.ron");
+
+const SHRINE: &str = r#"(
+    id: "shrine",
+    name: "The Shrine",
+    tiles: [[3, 0, 5]],
+    areas: [
+        (name: "Ashford", position: (0, 0), population: [(count: 4, power: 10)], sign_holders: 2),
+        (name: "Shrine", position: (2, 0), population: [(count: 2, power: 30), (count: 3, power: 5)], sign_holders: 1),
+    ],
+    seats: [
+        [(name: Some("Mordecai"), position: (2, 0), power: 20)],
+        [(position: (0, 0), power: 20)],
+    ],
+    win_conditions: [HoldArea(position: (2, 0), season: 4)],
+)"#;
+
+fn players() -> Vec<PlayerId> {
+    vec![PlayerId(1), PlayerId(0)]
+}
+
+fn play(scenario: &Scenario, season: i32) -> Vec<TurnEvent> {
+    let map = scenario.map_desc(players()).unwrap();
+    apply_turns(
+        season,
+        &scenario.ruleset(&Ruleset::default()),
+        &Pacts::default(),
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        vec![PlayerTurn::new(PlayerId(0)), PlayerTurn::new(PlayerId(1))],
+        vec![3, 5],
+        map.areas,
+    )
+    .events
+}
+
+fn winner(events: &[TurnEvent]) -> Option<PlayerId> {
+    events.iter().find_map(|event| match event {
+        TurnEvent::GameOver { winner, .. } => Some(*winner),
+        _ => None,
+    })
+}
+
+#[test]
+fn built_in_scenarios_load() {
+    let scenario = Scenario::from_ron(CROSSROADS).unwrap();
+    let map = scenario.map_desc(players()).unwrap();
+    assert_eq!(map.tiles.len() as u32, map.width * map.height);
+    for area in map.areas.iter() {
+        let (x, y) = area.world_position;
+        assert_eq!(map.get_tile(x, y), area_tile(area.kind));
+        assert!(!area.nearest_neighbors.is_empty());
+    }
+    assert_eq!(scenario.ruleset(&Ruleset::default()).win_sign_count(), 4);
+}
+
+#[test]
+fn scenarios_round_trip_through_ron() {
+    let scenario = Scenario::from_ron(SHRINE).unwrap();
+    assert_eq!(
+        Scenario::from_ron(&scenario.to_ron().unwrap()).unwrap(),
+        scenario
+    );
+}
+
+#[test]
+fn seats_go_to_players_in_order() {
+    let map = Scenario::from_ron(SHRINE)
+        .unwrap()
+        .map_desc(players())
+        .unwrap();
+    let shrine = &map.areas[1];
+    assert_eq!(shrine.kind, AreaKind::Temple);
+    assert_eq!(shrine.agents[0].name, "Mordecai");
+    assert_eq!(shrine.agents[0].id, AgentId::new(0, 0));
+    assert_eq!(map.areas[0].agents[0].id, AgentId::new(1, 0));
+
+    let sign_holders: Vec<u32> = shrine
+        .followers
+        .iter()
+        .filter(|f| f.sign_holder)
+        .map(|f| f.power)
+        .collect();
+    assert_eq!(sign_holders, [30]);
+    assert_eq!(
+        map.areas[0]
+            .followers
+            .iter()
+            .filter(|f| f.sign_holder)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn scenarios_are_for_a_fixed_number_of_players() {
+    let scenario = Scenario::from_ron(SHRINE).unwrap();
+    assert!(scenario.map_desc(vec![PlayerId(0)]).is_err());
+    assert!(scenario
+        .map_desc(vec![PlayerId(0), PlayerId(1), PlayerId(2)])
+        .is_err());
+}
+
+#[test]
+fn malformed_scenarios_are_refused() {
+    let missing_area = SHRINE.replace("[[3, 0, 5]]", "[[3, 3, 5]]");
+    assert!(Scenario::from_ron(&missing_area).is_err());
+    let off_the_map = SHRINE.replace("position: (0, 0), power", "position: (1, 0), power");
+    assert!(Scenario::from_ron(&off_the_map).is_err());
+    let ragged = SHRINE.replace("[[3, 0, 5]]", "[[3, 0, 5], [0]]");
+    assert!(Scenario::from_ron(&ragged).is_err());
+}
+
+#[test]
+fn holding_the_area_wins_once_the_season_comes() {
+    let scenario = Scenario::from_ron(SHRINE).unwrap();
+    assert_eq!(winner(&play(&scenario, 3)), None);
+    assert_eq!(winner(&play(&scenario, 4)), Some(PlayerId(0)));
+}
+
+#[test]
+fn the_most_signs_win_once_the_season_comes() {
+    let mut scenario = Scenario::from_ron(SHRINE).unwrap();
+    scenario.win_conditions = vec![WinCondition::MostSigns { season: 2 }];
+    assert_eq!(winner(&play(&scenario, 2)), None);
+
+    let mut map = scenario.map_desc(players()).unwrap();
+    map.areas[0].agents[0].signs = 1;
+    let events = apply_turns(
+        2,
+        &scenario.ruleset(&Ruleset::default()),
+        &Pacts::default(),
+        &Treasury::default(),
+        &AgentNumbers::default(),
+        vec![PlayerTurn::new(PlayerId(0)), PlayerTurn::new(PlayerId(1))],
+        vec![3, 5],
+        map.areas,
+    )
+    .events;
+    assert_eq!(winner(&events), Some(PlayerId(1)));
+}
+
+#[test]
+fn maps_written_up_as_scenarios_lay_out_the_same() {
+    let map = generate_seeded_map(7, vec![PlayerId(0), PlayerId(1)]);
+    let scenario = Scenario::from_map_desc("edited", "Edited", &map);
+    let scenario = Scenario::from_ron(&scenario.to_ron().unwrap()).unwrap();
+    let laid_out = scenario.map_desc(vec![PlayerId(0), PlayerId(1)]).unwrap();
+    assert_eq!(laid_out.tiles, map.tiles);
+    assert_eq!((laid_out.width, laid_out.height), (map.width, map.height));
+    for area in map.areas.iter() {
+        let (x, y) = area.world_position;
+        let copy = laid_out.get_area(x, y).unwrap();
+        assert_eq!(copy.name, area.name);
+        assert_eq!(copy.kind, area.kind);
+        assert_eq!(copy.followers.len(), area.followers.len());
+        let signs = |area: &WorldArea| area.followers.iter().filter(|f| f.sign_holder).count();
+        assert_eq!(signs(copy), signs(area));
+        let agents = |area: &WorldArea| {
+            area.agents
+                .iter()
+                .map(|agent| (agent.id, agent.name.clone(), agent.power))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(agents(copy), agents(area));
+    }
+}
@@ -0,0 +1,363 @@
+//! Generative checks on `apply_turns`: random worlds, random valid orders, and the promises
+//! season resolution has to keep no matter what players throw at it.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rand::seq::SliceRandom;
+use signs_core::{
+    economy::Treasury,
+    map::generate_seeded_map,
+    prelude::*,
+    report::{project, TurnReportEvent},
+    turns::{apply_turns, TurnResults},
+    world::AgentNumbers,
+};
+
+const CASES: u64 = 150;
+
+const EVENTS: [WorldEvent; 4] = [
+    WorldEvent::Plague,
+    WorldEvent::Festival,
+    WorldEvent::Eclipse,
+    WorldEvent::Storm,
+];
+
+struct Case {
+    season: i32,
+    players: Vec<PlayerId>,
+    ruleset: Ruleset,
+    pacts: Pacts,
+    treasury: Treasury,
+    turns: Vec<PlayerTurn>,
+    seeds: Vec<u64>,
+    areas: Vec<WorldArea>,
+}
+
+impl Case {
+    fn generate(case_seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(case_seed);
+        let players: Vec<PlayerId> = (0..rng.gen_range(2..=5)).map(PlayerId).collect();
+        let mut areas = generate_seeded_map(rng.gen(), players.clone()).areas;
+        // Fresh maps are calm, so stir them up to reach the later-game rules too.
+        for area in areas.iter_mut() {
+            area.suspicion = rng.gen_range(0..40);
+            for follower in area.followers.iter_mut() {
+                if rng.gen_bool(0.4) {
+                    follower.affinity = choose(&mut rng, &players);
+                    follower.corrupted = rng.gen_bool(0.2);
+                }
+            }
+            for agent in area.agents.iter_mut() {
+                if rng.gen_bool(0.2) {
+                    agent.corrupt();
+                }
+                agent.signs = rng.gen_range(0..=2);
+            }
+            if rng.gen_bool(0.2) {
+                area.strike(choose(&mut rng, &EVENTS).unwrap());
+            }
+        }
+        let mut pacts = Pacts::default();
+        for a in players.iter() {
+            for b in players.iter().filter(|b| a < *b) {
+                if rng.gen_bool(0.2) {
+                    pacts.alliances.push((*a, *b));
+                } else if rng.gen_bool(0.2) {
+                    pacts.proposals.push((*a, *b));
+                }
+            }
+        }
+        let turns = players
+            .iter()
+            .map(|player| random_turn(&mut rng, *player, &players, &pacts, &areas))
+            .collect();
+        let seeds = players.iter().map(|_| rng.gen()).collect();
+        let mut treasury = Treasury::default();
+        for player in players.iter() {
+            treasury.deposit(*player, rng.gen_range(0..10));
+        }
+        Self {
+            season: rng.gen_range(1..30),
+            players,
+            ruleset: Ruleset::default(),
+            pacts,
+            treasury,
+            turns,
+            seeds,
+            areas,
+        }
+    }
+
+    fn resolve(&self) -> TurnResults {
+        apply_turns(
+            self.season,
+            &self.ruleset,
+            &self.pacts,
+            &self.treasury,
+            &AgentNumbers::default(),
+            self.turns.clone(),
+            self.seeds.clone(),
+            self.areas.clone(),
+        )
+    }
+
+    fn agents(&self) -> Vec<Agent> {
+        self.areas
+            .iter()
+            .flat_map(|area| area.agents.iter().cloned())
+            .collect()
+    }
+}
+
+/// Orders a player could actually give from the UI: only actions without invalid reasons.
+fn random_turn(
+    rng: &mut StdRng,
+    player: PlayerId,
+    players: &[PlayerId],
+    pacts: &Pacts,
+    areas: &[WorldArea],
+) -> PlayerTurn {
+    let mut turn = PlayerTurn::new(player);
+    let allies = pacts.allies(player);
+    for area in areas {
+        let agents: Vec<&Agent> = area.player_agents(player).collect();
+        if agents.is_empty() {
+            continue;
+        }
+        if rng.gen_bool(0.2) {
+            turn.set_bribe(area.world_position, true);
+        }
+        let group: Vec<AgentId> = agents.iter().map(|agent| agent.id).collect();
+        let joint = choose(
+            rng,
+            &[AgentAction::GrandSacrifice, AgentAction::DarkCommunion],
+        )
+        .unwrap();
+        if rng.gen_bool(0.3)
+            && joint
+                .allied_invalid_reasons(agents[0], area, &allies)
+                .is_none()
+        {
+            turn.set_group_action(&group, joint);
+            continue;
+        }
+        for agent in agents {
+            let mut options = vec![
+                AgentAction::None,
+                AgentAction::Prostelytize,
+                AgentAction::Brutalize,
+                AgentAction::Corrupt,
+                AgentAction::CorruptAgent,
+                AgentAction::Sacrifice,
+                AgentAction::Recruit,
+                AgentAction::Search,
+            ];
+            options.extend(
+                area.nearest_neighbors
+                    .iter()
+                    .filter(|position| **position != area.world_position)
+                    .take(3)
+                    .map(|(x, y)| {
+                        let target = areas.iter().find(|a| a.world_position == (*x, *y));
+                        AgentAction::Move(*x, *y, target.unwrap().name.clone())
+                    }),
+            );
+            options.retain(|action| {
+                action
+                    .allied_invalid_reasons(agent, area, &allies)
+                    .is_none()
+            });
+            turn.set_action(agent.id, choose(rng, &options).unwrap_or(AgentAction::None));
+            // Targets may be anyone here, eligible or not.
+            if rng.gen_bool(0.3) {
+                let target = choose(rng, &area.followers).map(|follower| follower.id);
+                turn.set_target(agent.id, target);
+            }
+        }
+    }
+    for other in players.iter().filter(|other| **other != player) {
+        if rng.gen_bool(0.2) {
+            turn.set_diplomacy(*other, Some(pacts.available(player, *other)));
+        }
+    }
+    turn
+}
+
+/// Everything a client keeps from a season, in a fixed order so it can be compared as bytes.
+fn canonical_bytes(results: &TurnResults) -> Vec<u8> {
+    let areas: BTreeMap<(u32, u32), &WorldArea> = results
+        .new_world_areas
+        .iter()
+        .map(|(position, area)| (*position, area))
+        .collect();
+    postcard::to_allocvec(&(areas, &results.events, &results.pacts, &results.treasury)).unwrap()
+}
+
+fn report_bytes(results: &TurnResults, player: PlayerId) -> Vec<u8> {
+    postcard::to_allocvec(&project(&results.events, player)).unwrap()
+}
+
+fn result_agents(results: &TurnResults) -> Vec<Agent> {
+    results
+        .new_world_areas
+        .values()
+        .flat_map(|area| area.agents.iter().cloned())
+        .collect()
+}
+
+#[test]
+fn agents_are_never_duplicated_or_lost() {
+    for case_seed in 0..CASES {
+        let case = Case::generate(case_seed);
+        let results = case.resolve();
+        let mut ids = HashSet::new();
+        for agent in result_agents(&results) {
+            assert!(
+                ids.insert(agent.id),
+                "case {}: {:?} appears twice",
+                case_seed,
+                agent.id
+            );
+        }
+        for player in case.players.iter() {
+            // Only the Inquisition may take an agent off the board, and the owner always hears.
+            let lost = case
+                .agents()
+                .iter()
+                .filter(|agent| agent.id.player == *player && !ids.contains(&agent.id))
+                .count();
+            let captured = project(&results.events, *player)
+                .iter()
+                .filter(|event| matches!(event, TurnReportEvent::AgentCaptured { .. }))
+                .count();
+            assert_eq!(
+                lost, captured,
+                "case {}: {:?} lost agents without a capture",
+                case_seed, player
+            );
+        }
+    }
+}
+
+#[test]
+fn followers_keep_distinct_identities() {
+    for case_seed in 0..CASES {
+        let case = Case::generate(case_seed);
+        let results = case.resolve();
+        let mut ids = HashSet::new();
+        for area in results.new_world_areas.values() {
+            for follower in area.followers.iter() {
+                assert!(
+                    follower.id.is_known(),
+                    "case {}: unnamed follower",
+                    case_seed
+                );
+                assert!(
+                    ids.insert(follower.id),
+                    "case {}: {:?} appears twice",
+                    case_seed,
+                    follower.id
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn power_never_underflows() {
+    for case_seed in 0..CASES {
+        let case = Case::generate(case_seed);
+        let results = case.resolve();
+        for area in results.new_world_areas.values() {
+            for follower in area.followers.iter() {
+                assert!(
+                    follower.power < u32::MAX / 2,
+                    "case {}: follower power wrapped in {}",
+                    case_seed,
+                    area.name
+                );
+            }
+            for agent in area.agents.iter() {
+                assert!(
+                    agent.power < u32::MAX / 2 && agent.stamina < u32::MAX / 2,
+                    "case {}: {} wrapped around",
+                    case_seed,
+                    agent.name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn signs_are_never_lost() {
+    for case_seed in 0..CASES {
+        let case = Case::generate(case_seed);
+        let results = case.resolve();
+        let after: BTreeMap<AgentId, u32> = result_agents(&results)
+            .iter()
+            .map(|agent| (agent.id, agent.signs))
+            .collect();
+        let mut carried_off = 0;
+        for agent in case.agents() {
+            match after.get(&agent.id) {
+                Some(signs) => assert!(
+                    *signs >= agent.signs,
+                    "case {}: {} lost a sign",
+                    case_seed,
+                    agent.name
+                ),
+                // Captured agents take their signs with them.
+                None => carried_off += agent.signs,
+            }
+        }
+        let before: u32 = case.agents().iter().map(|agent| agent.signs).sum();
+        let total: u32 = after.values().sum();
+        assert!(
+            total + carried_off >= before,
+            "case {}: total signs fell from {} to {}",
+            case_seed,
+            before,
+            total
+        );
+    }
+}
+
+#[test]
+fn turn_order_does_not_matter() {
+    for case_seed in 0..CASES {
+        let mut case = Case::generate(case_seed);
+        let expected = canonical_bytes(&case.resolve());
+        let mut rng = StdRng::seed_from_u64(case_seed);
+        case.turns.shuffle(&mut rng);
+        case.seeds.shuffle(&mut rng);
+        assert!(
+            canonical_bytes(&case.resolve()) == expected,
+            "case {}: shuffling turns and seeds changed the outcome",
+            case_seed
+        );
+    }
+}
+
+#[test]
+fn identical_inputs_give_identical_output() {
+    for case_seed in 0..CASES {
+        let case = Case::generate(case_seed);
+        let first = case.resolve();
+        let second = case.resolve();
+        assert!(
+            canonical_bytes(&first) == canonical_bytes(&second),
+            "case {}: resolving twice gave different bytes",
+            case_seed
+        );
+        // Every client resolves the same events, only the report is theirs alone.
+        for player in case.players.iter() {
+            assert!(
+                report_bytes(&first, *player) == report_bytes(&second, *player),
+                "case {}: {:?} heard a different season",
+                case_seed,
+                player
+            );
+        }
+    }
+}
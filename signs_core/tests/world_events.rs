@@ -0,0 +1,60 @@
+//! The world events deck: drawn from the season's seed, and only where each event can strike.
+
+use signs_core::{
+    map::generate_seeded_map,
+    prelude::*,
+    world::{draw_world_events, AgentNumbers, WorldState},
+};
+
+fn world(seed: u64) -> WorldState {
+    let areas = generate_seeded_map(seed, vec![PlayerId(0), PlayerId(1)]).areas;
+    WorldState::new(areas, &AgentNumbers::default())
+}
+
+fn draw(seed: u64, ruleset: &Ruleset, world: &mut WorldState) -> Vec<(u32, u32, WorldEvent)> {
+    draw_world_events(&mut StdRng::seed_from_u64(seed), ruleset, world)
+}
+
+#[test]
+fn the_same_seed_deals_the_same_events() {
+    let ruleset = Ruleset {
+        events_per_season: 6,
+        ..Ruleset::default()
+    };
+    for seed in 0..30 {
+        let mut first = world(seed);
+        let mut second = world(seed);
+        let drawn = draw(seed, &ruleset, &mut first);
+        assert_eq!(drawn, draw(seed, &ruleset, &mut second));
+        assert!(first.areas().eq(second.areas()));
+        assert!(drawn.len() as u32 <= ruleset.events_per_season);
+        for (x, y, event) in drawn.iter() {
+            let area = first.area((*x, *y));
+            assert!(event.can_strike(area), "{:?} in {}", event, area.name);
+            assert_eq!(
+                area.events.iter().filter(|struck| *struck == event).count(),
+                1
+            );
+        }
+    }
+}
+
+#[test]
+fn last_seasons_events_are_cleared_before_the_draw() {
+    let ruleset = Ruleset::default();
+    for seed in 0..30 {
+        let mut world = world(seed);
+        draw(seed, &ruleset, &mut world);
+        let mut drawn = draw(seed + 1, &ruleset, &mut world);
+        let mut standing: Vec<(u32, u32, WorldEvent)> = world
+            .areas()
+            .flat_map(|area| {
+                let (x, y) = area.world_position;
+                area.events.iter().map(move |event| (x, y, *event))
+            })
+            .collect();
+        standing.sort_by_key(|(x, y, event)| (*x, *y, event.name()));
+        drawn.sort_by_key(|(x, y, event)| (*x, *y, event.name()));
+        assert_eq!(standing, drawn);
+    }
+}
@@ -0,0 +1,45 @@
+//! The season's world index: agents are found where they stand, however an area changes.
+
+mod common;
+
+use common::{agent, village, OWNER};
+use signs_core::{
+    prelude::*,
+    world::{AgentNumbers, WorldState},
+};
+
+fn world() -> WorldState {
+    let mut ashford = village();
+    ashford.add_agent(agent(OWNER, 0, 10));
+    ashford.add_agent(agent(OWNER, 1, 10));
+    WorldState::new(
+        vec![ashford, WorldArea::new("Blackmoor", 1, 0)],
+        &AgentNumbers::default(),
+    )
+}
+
+#[test]
+fn agents_taken_off_the_board_are_not_found() {
+    let mut world = world();
+    world.update((0, 0), |area| {
+        area.remove_agent(AgentId::new(0, 1));
+    });
+    assert_eq!(world.locate(AgentId::new(0, 1)), None);
+    assert_eq!(world.locate(AgentId::new(0, 0)), Some((0, 0)));
+    assert_eq!(world.player_agent_count(OWNER), 1);
+}
+
+#[test]
+fn agents_replaced_in_place_are_found() {
+    let mut world = world();
+    world.update((0, 0), |area| {
+        area.remove_agent(AgentId::new(0, 1));
+        area.add_agent(agent(OWNER, 2, 10));
+    });
+    assert_eq!(world.locate(AgentId::new(0, 1)), None);
+    assert_eq!(world.locate(AgentId::new(0, 2)), Some((0, 0)));
+
+    world.move_agent(AgentId::new(0, 2), (1, 0));
+    assert_eq!(world.locate(AgentId::new(0, 2)), Some((1, 0)));
+    assert_eq!(world.agent_count((1, 0), OWNER), 1);
+}
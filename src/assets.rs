@@ -24,6 +24,15 @@ pub struct MyAssets {
             "tile.glb#Scene1",
             "tile.glb#Scene4",
             "tile.glb#Scene3",
+            // Until they have art of their own, temples and ruins stand in as villages,
+            // strongholds as cities, and waterways and roads as open country.
+            "tile.glb#Scene4",
+            "tile.glb#Scene4",
+            "tile.glb#Scene3",
+            "tile.glb#Scene2",
+            "tile.glb#Scene2",
+            "tile.glb#Scene2",
+            "tile.glb#Scene2",
         ),
         collection(typed)
     )]
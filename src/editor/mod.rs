@@ -0,0 +1,414 @@
+use signs_core::{
+    geography::fill_geography,
+    map::{fill_neighbors, generate_area, tile_kind, BRIDGE, RIVER, ROAD, TILE_COUNT, WATER},
+    names::{default_theme, theme},
+    scenario::Scenario,
+};
+
+use crate::{game::world::tiles_3d::TileClicked, prelude::*};
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorState>()
+            .add_system(add_editor_ui.in_schedule(OnEnter(GameState::Editor)))
+            .add_system(remove_editor.in_schedule(OnExit(GameState::Editor)))
+            .add_system(paint_tiles.run_if(in_state(GameState::Editor)))
+            .add_system(handle_editor_keys.run_if(in_state(GameState::Editor)))
+            .add_system(save_map.run_if(in_state(GameState::Editor)))
+            .add_system(update_editor_ui.run_if(in_state(GameState::Editor)));
+    }
+}
+
+/// The keys that pick each of the first tiles as the brush, in tile order. , and . step through
+/// them all.
+const BRUSH_KEYS: [KeyCode; 10] = [
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+/// How much stronger or weaker [ and ] make the locals to be added.
+const POWER_STEP: u32 = 5;
+
+/// What the keyboard is naming, if anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Naming {
+    Map,
+    Area(Entity),
+}
+
+#[derive(Resource, Debug)]
+pub struct EditorState {
+    pub name: String,
+    /// The tile a right click paints.
+    pub brush: u32,
+    /// The power of each local added to an area.
+    pub power: u32,
+    pub naming: Option<Naming>,
+    /// How the last save went.
+    pub status: String,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            brush: 3,
+            power: 10,
+            naming: None,
+            status: String::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct EditorElement;
+
+fn tile_name(tile: u32) -> String {
+    match tile {
+        WATER => "Sea".to_string(),
+        RIVER => "River".to_string(),
+        ROAD => "Road".to_string(),
+        BRIDGE => "Bridge".to_string(),
+        tile => tile_kind(tile)
+            .map(|kind| kind.name().to_string())
+            .unwrap_or_else(|| format!("Land {}", tile + 1)),
+    }
+}
+
+/// The scenario id for a map name, such as "the-crossroads" for "The Crossroads".
+fn scenario_id(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+fn add_editor_ui(mut commands: Commands, assets: Res<MyAssets>) {
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(ONE_UNIT),
+                    left: Val::Px(ONE_UNIT),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: assets.font.clone(),
+                    font_size: FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ),
+            background_color: Color::rgba(0., 0., 0., 0.6).into(),
+            ..Default::default()
+        },
+        EditorElement,
+    ));
+}
+
+fn remove_editor(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<EditorElement>, With<MapTile>, With<DirectionalLight>)>>,
+    mut tile_input: ResMut<TileInputState>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    tile_input.clear();
+    tile_input.typing = false;
+    commands.insert_resource(EditorState::default());
+}
+
+fn paint_tiles(
+    mut commands: Commands,
+    mut clicks: EventReader<TileClicked>,
+    editor: Res<EditorState>,
+    assets: Res<MyAssets>,
+    mut tile_input: ResMut<TileInputState>,
+    mut tiles: Query<(&mut MapTile, Option<&mut MapArea>)>,
+) {
+    // New areas are named apart from those already on the map.
+    let mut taken: std::collections::HashSet<String> = tiles
+        .iter()
+        .filter_map(|(_, area)| area.map(|area| area.name.clone()))
+        .collect();
+    for click in clicks.iter() {
+        if click.button != PointerButton::Secondary {
+            continue;
+        }
+        // Right clicks paint here, rather than send agents travelling.
+        tile_input.traveling = None;
+        if let Ok((mut tile, area)) = tiles.get_mut(click.entity) {
+            if tile.sprite_id == editor.brush {
+                continue;
+            }
+            tile.sprite_id = editor.brush;
+            commands
+                .entity(click.entity)
+                .insert(assets.tiles[editor.brush as usize].clone());
+            match (tile_kind(editor.brush), area) {
+                (Some(kind), Some(mut area)) => {
+                    area.kind = kind;
+                    area.relic = kind == AreaKind::Ruins;
+                }
+                (Some(_), None) => {
+                    let mut rng = StdRng::from_entropy();
+                    if let Some(area) = generate_area(
+                        &mut rng,
+                        theme(default_theme()),
+                        &mut taken,
+                        tile.x as u32,
+                        tile.y as u32,
+                        editor.brush,
+                    ) {
+                        commands.entity(click.entity).insert(MapArea(area));
+                    }
+                }
+                (None, Some(_)) => {
+                    commands.entity(click.entity).remove::<MapArea>();
+                    if tile_input.is_selected(click.entity) {
+                        tile_input.selected = None;
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+fn handle_editor_keys(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut events: EventReader<ReceivedCharacter>,
+    mut editor: ResMut<EditorState>,
+    mut tile_input: ResMut<TileInputState>,
+    mut areas: Query<&mut MapArea>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Some(naming) = editor.naming {
+        let mut name = match naming {
+            Naming::Map => editor.name.clone(),
+            Naming::Area(entity) => match areas.get(entity) {
+                Ok(area) => area.name.clone(),
+                Err(_) => String::new(),
+            },
+        };
+        if keyboard_input.just_pressed(KeyCode::Return)
+            || keyboard_input.just_pressed(KeyCode::Escape)
+        {
+            editor.naming = None;
+            tile_input.typing = false;
+            return;
+        } else if keyboard_input.just_pressed(KeyCode::Back) {
+            name.pop();
+        } else {
+            for event in events.iter() {
+                if !event.char.is_control() {
+                    name.push(event.char);
+                }
+            }
+        }
+        match naming {
+            Naming::Map => editor.name = name,
+            Naming::Area(entity) => {
+                if let Ok(mut area) = areas.get_mut(entity) {
+                    area.name = name;
+                }
+            }
+        }
+        return;
+    }
+
+    // Outside a name, keys are commands, and shouldn't turn up in the next name typed.
+    events.clear();
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+        return;
+    }
+    for (tile, key) in BRUSH_KEYS.iter().enumerate() {
+        if keyboard_input.just_pressed(*key) {
+            editor.brush = tile as u32;
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Comma) {
+        editor.brush = (editor.brush + TILE_COUNT - 1) % TILE_COUNT;
+    } else if keyboard_input.just_pressed(KeyCode::Period) {
+        editor.brush = (editor.brush + 1) % TILE_COUNT;
+    }
+    if keyboard_input.just_pressed(KeyCode::LBracket) {
+        editor.power = editor.power.saturating_sub(POWER_STEP).max(1);
+    } else if keyboard_input.just_pressed(KeyCode::RBracket) {
+        editor.power += POWER_STEP;
+    }
+    if keyboard_input.just_pressed(KeyCode::N) {
+        editor.naming = Some(Naming::Map);
+        tile_input.typing = true;
+        return;
+    }
+
+    if let Some((entity, _, _)) = tile_input.selected {
+        if let Ok(mut area) = areas.get_mut(entity) {
+            edit_area(
+                &keyboard_input,
+                &mut editor,
+                &mut tile_input,
+                entity,
+                &mut area,
+            );
+        }
+    }
+}
+
+/// Renames, peoples and anoints the selected area.
+fn edit_area(
+    keyboard_input: &Input<KeyCode>,
+    editor: &mut EditorState,
+    tile_input: &mut TileInputState,
+    entity: Entity,
+    area: &mut WorldArea,
+) {
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        editor.naming = Some(Naming::Area(entity));
+        tile_input.typing = true;
+    } else if keyboard_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        area.add_follower(Follower::new(editor.power));
+    } else if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        // The weakest go first, and sign holders last of all.
+        if let Some(weakest) = area
+            .followers
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, follower)| (follower.sign_holder, follower.power))
+            .map(|(idx, _)| idx)
+        {
+            area.followers.remove(weakest);
+        }
+    } else if keyboard_input.just_pressed(KeyCode::H) {
+        if let Some(strongest) = area
+            .followers
+            .iter_mut()
+            .filter(|follower| !follower.sign_holder)
+            .max_by_key(|follower| follower.power)
+        {
+            strongest.sign_holder = true;
+        }
+    } else if keyboard_input.just_pressed(KeyCode::J) {
+        if let Some(weakest) = area
+            .followers
+            .iter_mut()
+            .filter(|follower| follower.sign_holder)
+            .min_by_key(|follower| follower.power)
+        {
+            weakest.sign_holder = false;
+        }
+    }
+}
+
+fn save_map(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut editor: ResMut<EditorState>,
+    tile_query: Query<(&MapTile, Option<&MapArea>)>,
+) {
+    if editor.naming.is_some() || !keyboard_input.just_pressed(KeyCode::F5) {
+        return;
+    }
+    let mut map = describe_map(&tile_query);
+    fill_neighbors(&mut map.areas);
+    fill_geography(map.width, map.height, &map.tiles, &mut map.areas);
+    let id = scenario_id(&editor.name);
+    let scenario = Scenario::from_map_desc(&id, &editor.name, &map);
+    editor.status = match scenario
+        .validate()
+        .and_then(|_| write_map(&id, &map, &scenario))
+    {
+        Ok(true) => format!("Saved {}.", editor.name),
+        Ok(false) => String::new(),
+        Err(err) => format!("Could not save: {}", err),
+    };
+}
+
+/// Writes the map out as a scenario, or as a bare map for a `.json` file. Returns whether
+/// anything was written.
+#[cfg(feature = "native-dialog")]
+fn write_map(id: &str, map: &MapDesc, scenario: &Scenario) -> Result<bool, String> {
+    let path = native_dialog::FileDialog::new()
+        .set_filename(&format!("{}.ron", id))
+        .add_filter("Scenario", &["ron"])
+        .add_filter("Map", &["json"])
+        .show_save_single_file()
+        .map_err(|err| err.to_string())?;
+    match path {
+        Some(path) => {
+            let contents = if path.extension().map_or(false, |ext| ext == "json") {
+                serde_json::to_string(map).map_err(|err| err.to_string())?
+            } else {
+                scenario.to_ron()?
+            };
+            std::fs::write(path, contents).map_err(|err| err.to_string())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_map(id: &str, _map: &MapDesc, scenario: &Scenario) -> Result<bool, String> {
+    save_game_js(format!("{}.ron", id), scenario.to_ron()?);
+    Ok(true)
+}
+
+fn update_editor_ui(
+    editor: Res<EditorState>,
+    tile_input: Res<TileInputState>,
+    areas: Query<&MapArea>,
+    mut text: Query<&mut Text, With<EditorElement>>,
+) {
+    let cursor = |naming: Naming| {
+        if editor.naming == Some(naming) {
+            "_"
+        } else {
+            ""
+        }
+    };
+    let selected = tile_input
+        .selected
+        .and_then(|(entity, _, _)| areas.get(entity).ok().map(|area| (entity, area)));
+    let area_help = match selected {
+        Some((entity, area)) => format!(
+            "{}{} ({})\n{} locals, {} sign holders, {} agents\n\
+             Enter renames it, + and - add or remove a local,\nH and J give or take a sign.",
+            area.name,
+            cursor(Naming::Area(entity)),
+            area.kind.name(),
+            area.followers.len(),
+            area.sign_holder_count(),
+            area.agents.len(),
+        ),
+        None => "Left click a settlement to edit it.".to_string(),
+    };
+    for mut text in text.iter_mut() {
+        text.sections[0].value = format!(
+            "{}{}\nBrush: {} (0-9, comma and period to choose, right click to paint)\n\
+             New locals: power {} ([ and ] to change)\n\n{}\n\n\
+             N renames the map, F5 saves it, Escape leaves.\n{}",
+            editor.name,
+            cursor(Naming::Map),
+            tile_name(editor.brush),
+            editor.power,
+            area_help,
+            editor.status,
+        );
+    }
+}
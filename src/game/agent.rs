@@ -1,182 +1,41 @@
 use crate::prelude::*;
 
-use super::{player, ui::ActiveInactiveImages, WIN_SIGN_COUNT};
+use super::{treasury::unaffordable_reason, ui::ActiveInactiveImages};
+use signs_core::ruleset::WinCondition;
+use signs_core::{
+    agent::HIDE_BUTTON,
+    forecast::{forecast, FORECAST_ROLLOUTS},
+    world::AgentNumbers,
+};
 
-#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum AgentAction {
-    None,
-    Move(u32, u32, #[serde(skip)] String),
-    Prostelytize,
-    Brutalize,
-    Corrupt,
-    CorruptAgent,
-    Sacrifice,
-}
-
-pub const CORRUPT_POWER: u32 = 30;
-pub const HIDE_BUTTON: &'static str = "HIDE";
-
-impl AgentAction {
-    pub fn describe(&self) -> String {
-        match self {
-            AgentAction::None => "View next agent in area, if any.".to_string(),
-            AgentAction::Move(_, _, name) => format!("Travel\n\nMove to {}.\nWhen you leave, a powerful follower in this area\nmay become an agent.", name),
-            AgentAction::Prostelytize => {
-                "Prostelytize\n\nSpread the word of darkness to the locals to gain new followers.".to_string()
-            }
-            AgentAction::Brutalize => {
-                "Brutalize\n\nBrutalize the locals, scaring away the weak\nand attracting the strong."
-                    .to_string()
-            }
-            AgentAction::Corrupt => {
-                "Corrupt\n\nAttempt to corrupt a follower,\ngaining access to greater power.".to_string()
-            }
-            AgentAction::CorruptAgent => {
-                "Corrupt Agent\n\nAttempt to corrupt this agent.\nYou will lose a corrupted follower.".to_string()
-            }
-            AgentAction::Sacrifice => {
-                "Sacrifice\n\nSacrifice a local, hoping to unlock\na Sign of Corruption.".to_string()
-            }
-        }
-    }
-
-    pub fn invalid_reasons(&self, agent: &Agent, area: &WorldArea) -> Option<String> {
-        match self {
-            AgentAction::None => None,
-            AgentAction::Move(x, y, name) => {
-                if name.len() <= 3 {
-                    Some("Must select a location to travel to.".to_string())
-                } else if *x == area.world_position.0 && *y == area.world_position.1 {
-                    Some("Must select a different location to travel to.".to_string())
-                } else {
-                    None
-                }
-            }
-            AgentAction::Prostelytize => {
-                if area.can_prostelytize(agent) {
-                    None
-                } else {
-                    Some("You find no chances in the remaining minds here.".to_string())
-                }
-            }
-            AgentAction::Brutalize => {
-                if area.get_non_player_agent_power(agent.id.player)
-                    > area.get_player_power(agent.id.player)
-                {
-                    Some("A hertical agent would overpower your followers.".to_string())
-                } else if area.get_player_power(agent.id.player) > area.get_value() / 5 {
-                    None
-                } else {
-                    Some("The locals are not afraid of your followers.".to_string())
-                }
-            }
-            AgentAction::Corrupt => {
-                if area.get_player_power(agent.id.player) <= CORRUPT_POWER {
-                    Some(format!(
-                        "You need {} power to corrupt a follower.",
-                        CORRUPT_POWER
-                    ))
-                } else if area.corrupted_followers(agent.id.player) > 0 {
-                    Some(HIDE_BUTTON.to_string())
-                } else {
-                    None
-                }
-            }
-            AgentAction::CorruptAgent => {
-                if agent.corrupted {
-                    Some(format!("{} is already corrupted.", agent.name))
-                } else if area.corrupted_followers(agent.id.player) == 0 {
-                    Some(HIDE_BUTTON.to_string())
-                } else if area.get_possible_sign_holder_count(agent.id) > 0 {
-                    Some(format!(
-                        "You cannot corrupt agents while there is still a chance to find a sign holder.\nBegin the sacrifices."
-                    ))
-                } else {
-                    None
-                }
-            }
-            AgentAction::Sacrifice => {
-                if area.corrupted_count(agent.id.player) == 0 {
-                    Some("You have no corrupted followers to enact sacrifices.".to_string())
-                } else if area.get_player_power(agent.id.player) <= area.get_value() / 3 {
-                    Some(format!("The locals would stop your public sacrifice."))
-                } else if area.get_possible_sign_holder_count(agent.id) == 0 {
-                    Some(format!(
-                        "There are no possible sign holders here.\nRecruit more or search elsewhere."
-                    ))
-                } else {
-                    None
-                }
-            }
-        }
-    }
-
-    pub fn sting(&self) -> &'static str {
-        match self {
-            AgentAction::Brutalize => "Brutalize.wav",
-            AgentAction::Prostelytize => "Prostelytize.wav",
-            AgentAction::Sacrifice => "Sacrifice.wav",
-            AgentAction::Corrupt => "Corrupt.wav",
-            AgentAction::Move(_, _, _) => "Move.wav",
-            _ => "",
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct Agent {
-    pub name: String,
-    pub id: AgentId,
-    // pub world_position: (u32, u32),
-    pub power: u32,
-    pub corrupted: bool,
-    pub stamina: u32,
-    pub signs: u32,
-}
-
-impl Agent {
-    pub fn new(name: String, id: AgentId, world_position: (u32, u32), power: u32) -> Self {
-        Self {
-            name,
-            id,
-            // world_position,
-            power,
-            corrupted: false,
-            stamina: 100,
-            signs: 0,
-        }
-    }
-
-    pub fn exhaust(&mut self, amount: u32) {
-        self.stamina = self.stamina.saturating_sub(amount);
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct AgentId {
-    pub player: PlayerId,
-    pub agent: u32,
-}
-
-impl AgentId {
-    pub fn new(player: u32, agent: u32) -> Self {
-        Self {
-            player: PlayerId(player),
-            agent,
-        }
-    }
-}
+/// Marks an action button in the agent panel with the action it assigns.
+#[derive(Component, Deref, DerefMut, Debug, Clone, PartialEq)]
+pub struct ActionButton(pub AgentAction);
 
 #[derive(Resource, Debug, Clone, Default)]
 pub struct AgentLocations {
     pub locations: HashMap<AgentId, (u32, u32)>,
 }
 
+/// Which agent the agent panel shows, and one it has been asked to show once its area is
+/// selected.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AgentFocus {
+    pub shown: Option<AgentId>,
+    pub requested: Option<AgentId>,
+}
+
+/// The numbers new agents take, kept across seasons so no id is ever handed out twice.
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default, PartialEq)]
+pub struct GameAgentNumbers(pub AgentNumbers);
+
 pub struct AgentPlugin;
 
 impl Plugin for AgentPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AgentLocations>()
+            .init_resource::<GameAgentNumbers>()
+            .init_resource::<AgentFocus>()
             .add_system(render_agent_ui.run_if(in_state(GameState::Playing)))
             .add_system(update_agent_locations.run_if(in_state(GameState::Playing)))
             .add_system(prepare_my_turn.run_if(in_state(GameState::Playing)))
@@ -193,14 +52,26 @@ struct AgentUiState {
     y: TileLoc,
     agent_idx: u32,
     tooltip_control: bool,
+    forecast: Option<CachedForecast>,
+}
+
+/// The last forecast shown, kept while the same plan stays under the cursor.
+struct CachedForecast {
+    agent_id: AgentId,
+    action: AgentAction,
+    target: Option<FollowerId>,
+    area: WorldArea,
+    text: String,
 }
 
 fn render_agent_ui(
     mut local: Local<AgentUiState>,
     mut tooltip: ResMut<Tooltip>,
-    player_id: Res<PlayerId>,
-    mut player_turn: ResMut<PlayerTurn>,
-    map_query: Query<(&MapTile, &WorldArea)>,
+    player_id: Res<LocalPlayer>,
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+    mut agent_focus: ResMut<AgentFocus>,
+    map_query: Query<(&MapTile, &MapArea)>,
     mut ui_query: Query<
         (
             Entity,
@@ -209,28 +80,75 @@ fn render_agent_ui(
             Option<&RelativeCursorPosition>,
             &mut Visibility,
         ),
-        Or<(With<Name>, With<AgentAction>)>,
+        Or<(With<Name>, With<ActionButton>)>,
     >,
-    action_query: Query<&AgentAction>,
+    action_query: Query<&ActionButton>,
     mut image_query: Query<(Entity, &ActiveInactiveImages, &mut UiImage)>,
     mut interaction_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
     assets: Res<MyAssets>,
     audio: Res<Audio>,
     tile_input: Res<TileInputState>,
+    pacts: Res<GamePacts>,
+    (treasury, ruleset, season): (Res<GameTreasury>, Res<GameRuleset>, Res<Season>),
 ) {
+    let ruleset = ruleset.for_season(**season);
+    let allies = pacts.allies(**player_id);
+    let agent_count: u32 = map_query
+        .iter()
+        .map(|(_, area)| area.get_player_agent_count(**player_id))
+        .sum();
+    // Actions the rules forbid, or that the player cannot pay for.
+    let invalid_reason =
+        |action: &AgentAction, agent: &Agent, area: &WorldArea, player_turn: &PlayerTurn| {
+            action
+                .allied_invalid_reasons(agent, area, &allies)
+                .or_else(|| action.weary_reason(agent, &ruleset))
+                .or_else(|| {
+                    (*action == AgentAction::Recruit && agent_count >= ruleset.agent_limit).then(
+                        || format!("You already lead {} agents, the most you may.", agent_count),
+                    )
+                })
+                .or_else(|| {
+                    // A joint ritual is given to every agent here at once.
+                    let agent_ids: Vec<AgentId> = if action.is_joint() {
+                        area.player_agents(agent.id.player).map(|a| a.id).collect()
+                    } else {
+                        vec![agent.id]
+                    };
+                    unaffordable_reason(action, &agent_ids, player_turn, &treasury, &ruleset)
+                })
+        };
     let mut tooltip_value = None;
     if let Some((tile, world_area)) = tile_input
         .selected
         .and_then(|(entity, _, _)| map_query.get(entity).ok())
     {
-        if local.x != tile.x || local.y != tile.y {
+        let moved = local.x != tile.x || local.y != tile.y;
+        if moved {
             local.x = tile.x;
             local.y = tile.y;
             local.agent_idx = world_area
                 .get_unassigned_player_agent(&player_turn)
                 .unwrap_or(0);
         }
-        if let Some(active_agent) = world_area.get_nth_player_agent(*player_id, local.agent_idx) {
+        if let Some(agent_id) = agent_focus.requested {
+            if let Some(idx) = world_area
+                .player_agents(**player_id)
+                .position(|agent| agent.id == agent_id)
+            {
+                local.agent_idx = idx as u32;
+                agent_focus.requested = None;
+            } else if moved {
+                agent_focus.requested = None;
+            }
+        }
+        let shown = world_area
+            .get_nth_player_agent(**player_id, local.agent_idx)
+            .map(|agent| agent.id);
+        if agent_focus.shown != shown {
+            agent_focus.shown = shown;
+        }
+        if let Some(active_agent) = world_area.get_nth_player_agent(**player_id, local.agent_idx) {
             for (entity, name, mut text, rcp, mut visibility) in ui_query.iter_mut() {
                 if let Some(name) = name {
                     if name.eq_ignore_ascii_case("agent_name") {
@@ -243,7 +161,7 @@ fn render_agent_ui(
                         *visibility = Visibility::Visible;
                     }
                 } else if let Ok(action) = action_query.get(entity) {
-                    let invalid = action.invalid_reasons(active_agent, world_area);
+                    let invalid = invalid_reason(action, active_agent, world_area, &player_turn);
                     if let Some(invalid_reason) = &invalid {
                         if invalid_reason.eq(HIDE_BUTTON) {
                             *visibility = Visibility::Hidden;
@@ -257,7 +175,14 @@ fn render_agent_ui(
                         if let Some(invalid_reason) = invalid {
                             tooltip_value = Some(invalid_reason);
                         } else {
-                            tooltip_value = Some(action.describe());
+                            tooltip_value = Some(forecast_tooltip(
+                                &mut local.forecast,
+                                active_agent.id,
+                                action,
+                                player_turn.get_target(active_agent.id),
+                                world_area,
+                                &allies,
+                            ));
                         }
                     }
                 }
@@ -266,10 +191,23 @@ fn render_agent_ui(
                 match interaction {
                     Interaction::Clicked => {
                         if let Ok(action) = action_query.get(entity) {
-                            if *action == AgentAction::None {
+                            if **action == AgentAction::None {
                                 local.agent_idx += 1;
-                            } else if action.invalid_reasons(active_agent, world_area).is_none() {
-                                player_turn.set_action(active_agent.id, action.clone());
+                            } else if invalid_reason(action, active_agent, world_area, &player_turn)
+                                .is_none()
+                            {
+                                let group: Vec<AgentId> = world_area
+                                    .player_agents(**player_id)
+                                    .map(|agent| agent.id)
+                                    .collect();
+                                history.record(&mut player_turn, |turn| {
+                                    if action.is_joint() {
+                                        turn.set_group_action(&group, action.0.clone());
+                                    } else {
+                                        turn.leave_group_action(active_agent.id, &group);
+                                        turn.set_action(active_agent.id, action.0.clone());
+                                    }
+                                });
                                 if let Some(sound) = assets.action_stings.get(action.sting()) {
                                     audio.play(sound.clone());
                                 }
@@ -281,9 +219,11 @@ fn render_agent_ui(
             }
             for (entity, active_inactive, mut image) in image_query.iter_mut() {
                 if let Some(action) = action_query.get(entity).ok() {
-                    if player_turn.get_action(active_agent.id) == Some(action.clone()) {
+                    if player_turn.get_action(active_agent.id) == Some(action.0.clone()) {
                         image.texture = active_inactive.active.clone();
-                    } else if action.invalid_reasons(active_agent, world_area).is_some() {
+                    } else if invalid_reason(action, active_agent, world_area, &player_turn)
+                        .is_some()
+                    {
                         image.texture = active_inactive.deactivated.clone();
                     } else {
                         image.texture = active_inactive.inactive.clone();
@@ -303,6 +243,9 @@ fn render_agent_ui(
             }
         }
     } else {
+        if agent_focus.shown.is_some() {
+            agent_focus.shown = None;
+        }
         for (entity, name, _, _, mut visibility) in ui_query.iter_mut() {
             if name
                 .map(|name| name.eq_ignore_ascii_case("Area Agent"))
@@ -323,15 +266,61 @@ fn render_agent_ui(
     }
 }
 
+/// Describes the action along with its odds, rehearsing it only when the plan has changed.
+fn forecast_tooltip(
+    cache: &mut Option<CachedForecast>,
+    agent_id: AgentId,
+    action: &AgentAction,
+    target: Option<FollowerId>,
+    area: &WorldArea,
+    allies: &[PlayerId],
+) -> String {
+    let fresh = cache
+        .as_ref()
+        .map(|cached| {
+            cached.agent_id == agent_id
+                && cached.action == *action
+                && cached.target == target
+                && cached.area == *area
+        })
+        .unwrap_or_default();
+    if !fresh {
+        let seed = agent_id.agent as u64;
+        let text = match forecast(
+            area,
+            agent_id,
+            action,
+            target,
+            allies,
+            seed,
+            FORECAST_ROLLOUTS,
+        ) {
+            Some(odds) => format!("{}\n\n{}", action.describe(), odds.describe()),
+            None => action.describe(),
+        };
+        *cache = Some(CachedForecast {
+            agent_id,
+            action: action.clone(),
+            target,
+            area: area.clone(),
+            text,
+        });
+    }
+    cache
+        .as_ref()
+        .map(|cached| cached.text.clone())
+        .unwrap_or_default()
+}
+
 fn update_agent_locations(
-    player: Res<PlayerId>,
+    player: Res<LocalPlayer>,
     mut agent_locations: ResMut<AgentLocations>,
-    areas: Query<&WorldArea>,
+    areas: Query<&MapArea>,
 ) {
     let mut locations = HashMap::new();
     for area in areas.iter() {
         for agent in area.agents.iter() {
-            if agent.id.player == *player {
+            if agent.id.player == **player {
                 locations.insert(agent.id, area.world_position);
             }
         }
@@ -340,8 +329,8 @@ fn update_agent_locations(
 }
 
 fn prepare_my_turn(
-    player: Res<PlayerId>,
-    mut player_turn: ResMut<PlayerTurn>,
+    player: Res<LocalPlayer>,
+    mut player_turn: ResMut<CurrentTurn>,
     mut agent_locations: ResMut<AgentLocations>,
 ) {
     for (agent_id, _) in agent_locations.locations.iter_mut() {
@@ -350,9 +339,9 @@ fn prepare_my_turn(
 }
 
 fn update_agent_label(
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
     mut text_query: Query<(&Name, &mut Text)>,
-    areas: Query<&WorldArea>,
+    areas: Query<&MapArea>,
 ) {
     let unassigned_agents = player_turn.get_unassigned_agents();
 
@@ -378,11 +367,11 @@ fn update_agent_label(
 
 fn agent_tooltip(
     mut tooltip_control: Local<bool>,
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
     player_agents: Res<AgentLocations>,
     mut tooltip: ResMut<Tooltip>,
     agent_label: Query<(&RelativeCursorPosition, &Name)>,
-    mut tile_query: Query<(Entity, &mut MapTile, &WorldArea)>,
+    mut tile_query: Query<(Entity, &mut MapTile, &MapArea)>,
     input: Res<Input<MouseButton>>,
     mut tile_input: ResMut<TileInputState>,
 ) {
@@ -418,10 +407,10 @@ fn agent_tooltip(
 fn corrupted_tooltip(
     mut corrupted_idx: Local<usize>,
     mut tooltip_control: Local<bool>,
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
     mut tooltip: ResMut<Tooltip>,
     agent_label: Query<(&RelativeCursorPosition, &Name)>,
-    mut tile_query: Query<(Entity, &mut MapTile, &WorldArea)>,
+    mut tile_query: Query<(Entity, &mut MapTile, &MapArea)>,
     input: Res<Input<MouseButton>>,
     mut tile_input: ResMut<TileInputState>,
 ) {
@@ -461,10 +450,11 @@ fn corrupted_tooltip(
 
 fn signs_tooltip(
     mut tooltip_control: Local<bool>,
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
+    ruleset: Res<GameRuleset>,
     mut tooltip: ResMut<Tooltip>,
     agent_label: Query<(&RelativeCursorPosition, &Name)>,
-    tile_query: Query<(&MapTile, &WorldArea)>,
+    tile_query: Query<(&MapTile, &MapArea)>,
 ) {
     for (cursor, name) in agent_label.iter() {
         if name.eq_ignore_ascii_case("Signs") {
@@ -474,14 +464,36 @@ fn signs_tooltip(
                     .iter()
                     .map(|(_, area)| area.sign_count(player_turn.player_id))
                     .sum::<u32>();
-                if signs_count > 0 {
-                    tooltip.value = Some(format!(
+                let mut value = if signs_count > 0 {
+                    format!(
                         "Your agents control {} signs. You need {} to win.",
-                        signs_count, WIN_SIGN_COUNT,
-                    ));
+                        signs_count,
+                        ruleset.win_sign_count(),
+                    )
                 } else {
-                    tooltip.value = Some("You have no signs.\nCorrupted followers can enact sacrifices to discover signs.".to_string());
+                    "You have no signs.\nCorrupted followers can enact sacrifices to discover signs.".to_string()
+                };
+                for condition in ruleset.win_conditions.iter() {
+                    match condition {
+                        WinCondition::HoldArea { position, season } => {
+                            if let Some((_, area)) = tile_query
+                                .iter()
+                                .find(|(_, area)| area.world_position == *position)
+                            {
+                                value.push_str(&format!(
+                                    "\nWhoever holds {} at the end of season {} wins.",
+                                    area.name, season
+                                ));
+                            }
+                        }
+                        WinCondition::MostSigns { season } => value.push_str(&format!(
+                            "\nWhoever holds the most signs at the end of season {} wins.",
+                            season
+                        )),
+                        WinCondition::Signs(_) => {}
+                    }
                 }
+                tooltip.value = Some(value);
             } else if *tooltip_control {
                 tooltip.value = None;
                 *tooltip_control = false;
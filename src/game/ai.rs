@@ -24,21 +24,32 @@ fn get_ai_rng(game_players: &GamePlayers, player: PlayerId, seeds: &AiSeeds) ->
     StdRng::seed_from_u64(seed)
 }
 
-fn get_action(rng: &mut StdRng, agent: &Agent, area: &WorldArea) -> AgentAction {
+fn get_action(
+    rng: &mut StdRng,
+    agent: &Agent,
+    area: &WorldArea,
+    funds: &mut u32,
+    ruleset: &Ruleset,
+) -> AgentAction {
     if AgentAction::Sacrifice
         .invalid_reasons(agent, area)
         .is_none()
     {
         AgentAction::Sacrifice
-    } else if AgentAction::Corrupt.invalid_reasons(agent, area).is_none() {
+    } else if AgentAction::Corrupt.invalid_reasons(agent, area).is_none()
+        && *funds >= AgentAction::Corrupt.cost(ruleset)
+    {
+        *funds -= AgentAction::Corrupt.cost(ruleset);
         AgentAction::Corrupt
     } else if AgentAction::Brutalize
         .invalid_reasons(agent, area)
+        .or_else(|| AgentAction::Brutalize.weary_reason(agent, ruleset))
         .is_none()
     {
         AgentAction::Brutalize
     } else if AgentAction::Prostelytize
         .invalid_reasons(agent, area)
+        .or_else(|| AgentAction::Prostelytize.weary_reason(agent, ruleset))
         .is_none()
     {
         AgentAction::Prostelytize
@@ -62,11 +73,18 @@ pub fn generate_seeds(player_names: Vec<String>, ai_players: u32) -> AiSeeds {
     AiSeeds { seeds }
 }
 
-fn take_turn(player: PlayerId, rng: &mut StdRng, world_areas: &Query<&WorldArea>) -> PlayerTurn {
+fn take_turn(
+    player: PlayerId,
+    rng: &mut StdRng,
+    world_areas: &Query<&MapArea>,
+    treasury: &GameTreasury,
+    ruleset: &Ruleset,
+) -> PlayerTurn {
     let mut turn = PlayerTurn::new(player);
+    let mut funds = treasury.balance(player);
     for area in world_areas.iter() {
         for agent in area.player_agents(player) {
-            let action = get_action(rng, agent, area);
+            let action = get_action(rng, agent, area, &mut funds, ruleset);
             turn.set_action(agent.id, action);
         }
     }
@@ -79,7 +97,9 @@ fn ai_turn(
     game_players: Res<GamePlayers>,
     mut ai_seeds: ResMut<AiSeeds>,
     mut evoking: ResMut<EvokingState>,
-    world_areas: Query<&WorldArea>,
+    treasury: Res<GameTreasury>,
+    ruleset: Res<GameRuleset>,
+    world_areas: Query<&MapArea>,
 ) {
     if let Some((season, seed, turn)) = match evoking.as_mut() {
         EvokingState::Evoking { season, evoked, .. } => {
@@ -89,7 +109,13 @@ fn ai_turn(
                 for player in game_players.get_ids() {
                     if game_players.is_ai(player) && !evoked.contains_key(&player) {
                         let mut rng = get_ai_rng(&game_players, player, &ai_seeds);
-                        let ai_turn = take_turn(player, &mut rng, &world_areas);
+                        let ai_turn = take_turn(
+                            player,
+                            &mut rng,
+                            &world_areas,
+                            &treasury,
+                            &ruleset.for_season(*season),
+                        );
                         // let mut debug = ai_turn.actions.iter().collect::<Vec<_>>();
                         // debug.sort_by(|a, b| a.0.cmp(&b.0));
                         // println!("AI {:?} turn: {:?}", player, debug);
@@ -0,0 +1,54 @@
+use crate::prelude::*;
+use signs_core::calendar::Phase;
+
+pub struct CalendarPlugin;
+
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_calendar_ui.run_if(in_state(GameState::Playing)))
+            .add_system(calendar_tooltip.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// The phase as the HUD has room for it, such as "Winter, full moon".
+fn short_name(phase: &Phase) -> String {
+    format!("{}, {}", phase.year_season.name(), phase.moon.name())
+}
+
+fn update_calendar_ui(season: Res<Season>, mut text_query: Query<(&Name, &mut Text)>) {
+    let phase = Phase::of(**season);
+    let next = Phase::after(**season);
+    for (name, mut text) in text_query.iter_mut() {
+        if name.eq_ignore_ascii_case("Calendar-value") {
+            text.sections[0].value = format!("{}\nNext: {}", short_name(&phase), short_name(&next));
+        } else if name.eq_ignore_ascii_case("evoking_body") {
+            text.sections[0].value = format!("You have evoked the darkness\nunder {}.\n", phase);
+        }
+    }
+}
+
+fn calendar_tooltip(
+    mut tooltip_control: Local<bool>,
+    season: Res<Season>,
+    mut tooltip: ResMut<Tooltip>,
+    label_query: Query<(&RelativeCursorPosition, &Name)>,
+) {
+    for (cursor, name) in label_query.iter() {
+        if name.eq_ignore_ascii_case("Calendar") {
+            if cursor.mouse_over() {
+                *tooltip_control = true;
+                let phase = Phase::of(**season);
+                let next = Phase::after(**season);
+                let [year_effect, moon_effect] = phase.effects();
+                let [next_year_effect, next_moon_effect] = next.effects();
+                tooltip.value = Some(format!(
+                    "This season falls under {}.\n{}\n{}\n\nNext comes {}.\n{}\n{}",
+                    phase, year_effect, moon_effect, next, next_year_effect, next_moon_effect
+                ));
+            } else if *tooltip_control {
+                tooltip.value = None;
+                *tooltip_control = false;
+            }
+        }
+    }
+}
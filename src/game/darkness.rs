@@ -1,16 +1,19 @@
 use crate::prelude::*;
 
 use super::{
+    hotseat::Hotseat,
     player::GamePlayers,
     turn_ui::{TurnReport, EVOKE_COLOR, TRANSPARENT_EVOKE_COLOR},
-    turns::{apply_turns, Season},
+    turns::Season,
 };
+use signs_core::{report::project, turns::apply_turns};
 
 pub struct DarknessPlugin;
 
 impl Plugin for DarknessPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EvokingState>()
+            .init_resource::<GameRuleset>()
             .add_system(add_turn_end_button.in_schedule(OnEnter(GameState::Playing)))
             .add_system(add_evoking_ui.in_schedule(OnEnter(GameState::Playing)))
             .add_system(evoke_darkness_on_click.run_if(in_state(GameState::Playing)))
@@ -176,6 +179,31 @@ impl EvokingState {
     pub fn is_evoking(&self) -> bool {
         matches!(self, Self::Evoking { .. })
     }
+
+    /// Whether the player's turn is in for this season.
+    pub fn has_evoked(&self, player: PlayerId) -> bool {
+        match self {
+            Self::None { .. } => false,
+            Self::Evoking { evoked, .. } => evoked.contains_key(&player),
+            Self::Ready { .. } => true,
+        }
+    }
+
+    /// Every turn in so far this season, in seat order.
+    pub fn evokations(&self) -> Vec<Evokation> {
+        match self {
+            Self::Evoking { evoked, .. } => {
+                let mut evokations = evoked.values().cloned().collect::<Vec<_>>();
+                evokations.sort_by_key(|evokation| evokation.player_turn.player_id);
+                evokations
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn push_evokation(&mut self, evokation: Evokation) -> bool {
+        self.push(evokation.season, evokation.seed, evokation.player_turn)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -194,24 +222,67 @@ impl Evokation {
         }
     }
 
+    /// Diplomacy, then targets, then bribes ride after the evokation itself, so runes from
+    /// before any of them still read.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = postcard::to_allocvec(self).unwrap();
+        let player_turn = &self.player_turn;
+        let has_bribes = !player_turn.bribes.is_empty();
+        let has_targets = !player_turn.targets.is_empty() || has_bribes;
+        if !player_turn.diplomacy.is_empty() || has_targets {
+            bytes.extend(postcard::to_allocvec(&player_turn.diplomacy).unwrap());
+        }
+        if has_targets {
+            bytes.extend(postcard::to_allocvec(&player_turn.targets).unwrap());
+        }
+        if has_bribes {
+            bytes.extend(postcard::to_allocvec(&player_turn.bribes).unwrap());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Evokation, String> {
+        let (mut evokation, rest) =
+            postcard::take_from_bytes::<Evokation>(bytes).map_err(|e| e.to_string())?;
+        if let Ok((diplomacy, rest)) = postcard::take_from_bytes(rest) {
+            evokation.player_turn.diplomacy = diplomacy;
+            if let Ok((targets, rest)) = postcard::take_from_bytes(rest) {
+                evokation.player_turn.targets = targets;
+                evokation.player_turn.bribes = postcard::from_bytes(rest).unwrap_or_default();
+            }
+        }
+        Ok(evokation)
+    }
+
+    pub fn from_runes(runes: &str, futhark: bool) -> Option<Evokation> {
+        Self::from_bytes(&parse_runes(runes, futhark)).ok()
+    }
+
     pub fn retrieve_evokation() -> Result<Evokation, String> {
-        retrieve_from_runes::<Evokation>()
+        retrieve_bytes_from_runes().and_then(|bytes| Self::from_bytes(&bytes))
     }
 
     pub fn store_evokation(&self, futhark: bool) -> Option<String> {
-        store_in_runes(self, futhark)
+        store_bytes_in_runes(&self.to_bytes(), futhark)
     }
 
     pub fn to_runes(&self, futhark: bool) -> String {
-        create_runes(self, futhark)
+        generate_runes(&self.to_bytes(), futhark)
     }
 }
 
 fn evoke_darkness_on_click(
     mut evoking_state: ResMut<EvokingState>,
     season: Res<Season>,
-    player_turn: Res<PlayerTurn>,
+    mut player_turn: ResMut<CurrentTurn>,
     game_players: Res<GamePlayers>,
+    (mut hotseat, mut player, mut history, mut turn_report, mut standing_orders): (
+        ResMut<Hotseat>,
+        ResMut<LocalPlayer>,
+        ResMut<TurnHistory>,
+        ResMut<TurnReport>,
+        ResMut<GameStandingOrders>,
+    ),
     mut interaction_query: Query<
         &Interaction,
         (
@@ -227,21 +298,37 @@ fn evoke_darkness_on_click(
     mut tile_input: ResMut<TileInputState>,
 ) {
     if let Some(interaction) = interaction_query.iter_mut().next() {
-        if *interaction == Interaction::Clicked
-            && matches!(*evoking_state, EvokingState::None { .. })
-        {
-            evoking_state.begin(**season, player_turn.clone(), game_players.as_ref());
-            evoking_state
-                .get_evokation(&player_turn.player_id)
-                .unwrap()
-                .store_evokation(true);
-            for mut visibility in evoking_ui.iter_mut() {
-                *visibility = Visibility::Visible;
+        if *interaction == Interaction::Clicked && !evoking_state.has_evoked(**player) {
+            if evoking_state.is_evoking() {
+                // Another seat on this device has evoked already.
+                evoking_state.push(**season, rand::thread_rng().gen(), player_turn.0.clone());
+            } else {
+                evoking_state.begin(**season, player_turn.0.clone(), game_players.as_ref());
             }
             tile_input.clear();
             audio.play(my_assets.evoke_darkness.clone());
-            #[cfg(target_arch = "wasm32")]
-            show_clipboard("calc(100% - 7em)", "50%");
+            if !hotseat.is_hotseat() {
+                evoking_state
+                    .get_evokation(&player_turn.player_id)
+                    .unwrap()
+                    .store_evokation(true);
+                #[cfg(target_arch = "wasm32")]
+                show_clipboard("calc(100% - 7em)", "50%");
+            }
+            if let Some(next) = hotseat.next_seat(&evoking_state) {
+                history.clear();
+                hotseat.hand_over(
+                    next,
+                    &mut player,
+                    &mut player_turn,
+                    &mut turn_report,
+                    &mut standing_orders,
+                );
+            } else {
+                for mut visibility in evoking_ui.iter_mut() {
+                    *visibility = Visibility::Visible;
+                }
+            }
         }
     }
 }
@@ -251,13 +338,16 @@ fn watch_evokations(
     time: Res<Time>,
     mut evoking_state: ResMut<EvokingState>,
     game_players: Res<GamePlayers>,
-    player_id: Res<PlayerId>,
+    player_id: Res<LocalPlayer>,
+    hotseat: Res<Hotseat>,
     keyboard: Res<Input<KeyCode>>,
     my_assets: Res<MyAssets>,
     audio: Res<Audio>,
     mut player_list: Query<&mut Text, With<EvokingPlayerList>>,
 ) {
-    if *cooldown < 0. {
+    if hotseat.is_hotseat() {
+        // Every human is at this device, so there are no runes to pass around.
+    } else if *cooldown < 0. {
         *cooldown = 3.;
         match Evokation::retrieve_evokation() {
             Ok(evokation) => {
@@ -275,7 +365,7 @@ fn watch_evokations(
     } else {
         *cooldown -= time.delta_seconds();
     }
-    if keyboard.just_pressed(KeyCode::C) {
+    if keyboard.just_pressed(KeyCode::C) && !hotseat.is_hotseat() {
         if let Some(evokation) = evoking_state.get_evokation(&player_id) {
             evokation.store_evokation(true);
             audio.play(my_assets.evoke_darkness.clone());
@@ -315,8 +405,12 @@ fn watch_evokations(
             player_list.sections.insert(
                 0,
                 TextSection {
-                    value: "Player evokation status (use clipboard to load other players)\n\n"
-                        .to_string(),
+                    value: if hotseat.is_hotseat() {
+                        "Player evokation status\n\n".to_string()
+                    } else {
+                        "Player evokation status (use clipboard to load other players)\n\n"
+                            .to_string()
+                    },
                     style: TextStyle {
                         font: my_assets.font.clone(),
                         font_size: 20.0,
@@ -342,14 +436,19 @@ fn watch_evokations(
 }
 
 fn end_evokation(
-    player_id: Res<PlayerId>,
+    (mut player_id, mut hotseat): (ResMut<LocalPlayer>, ResMut<Hotseat>),
     game_players: Res<GamePlayers>,
+    ruleset: Res<GameRuleset>,
+    mut pacts: ResMut<GamePacts>,
+    (mut treasury, mut agent_numbers): (ResMut<GameTreasury>, ResMut<GameAgentNumbers>),
     mut season: ResMut<Season>,
     mut commands: Commands,
-    mut player_turn: ResMut<PlayerTurn>,
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+    mut standing_orders: ResMut<GameStandingOrders>,
     mut turn_report: ResMut<TurnReport>,
     mut evoking_state: ResMut<EvokingState>,
-    query: Query<&WorldArea>,
+    query: Query<&MapArea>,
     tile_query: Query<(Entity, &MapTile)>,
     mut evoking_ui: Query<&mut Visibility, With<EvokingUi>>,
 ) {
@@ -358,12 +457,15 @@ fn end_evokation(
     if let EvokingState::Ready { turns, seeds } = evoking_state.as_ref() {
         let world_areas = query
             .iter()
-            .map(|world_area| world_area.clone())
+            .map(|world_area| world_area.0.clone())
             .collect::<Vec<WorldArea>>();
         // println!("{:?} {:?} {:?} {:?}", turns, seeds, world_areas, player_id);
         let results = apply_turns(
             **season,
-            *player_id,
+            &ruleset,
+            &pacts,
+            &treasury,
+            &agent_numbers,
             turns.clone(),
             seeds.clone(),
             world_areas,
@@ -372,15 +474,55 @@ fn end_evokation(
             if let Some(new_world_area) =
                 results.get_new_world_area((map_tile.x as u32, map_tile.y as u32))
             {
-                commands.entity(entity).insert(new_world_area);
+                commands.entity(entity).insert(MapArea(new_world_area));
             } else if query.contains(entity) {
-                commands.entity(entity).remove::<WorldArea>();
+                commands.entity(entity).remove::<MapArea>();
             }
         }
-        turn_report.append_reports(results.report);
+        **pacts = results.pacts;
+        **treasury = results.treasury;
+        **agent_numbers = results.agent_numbers;
+        turn_report.append_reports(project(&results.events, **player_id));
         player_turn.reset();
+        history.clear();
+        // Standing orders are given for the season about to begin.
+        let next_ruleset = ruleset.for_season(**season + 1);
+        standing_orders.issue(
+            &mut player_turn,
+            results.new_world_areas.values(),
+            &pacts.allies(**player_id),
+            &next_ruleset,
+        );
+        // Seats waiting for the device hear of the season and get their standing orders too.
+        let waiting = hotseat
+            .seats
+            .iter()
+            .copied()
+            .filter(|seat| *seat != **player_id)
+            .collect::<Vec<_>>();
+        for player in waiting {
+            let seat = hotseat.seat_mut(player);
+            seat.report.append_reports(project(&results.events, player));
+            seat.turn.reset();
+            seat.standing_orders.issue(
+                &mut seat.turn,
+                results.new_world_areas.values(),
+                &pacts.allies(player),
+                &next_ruleset,
+            );
+        }
         **season = **season + 1;
         *evoking_state = EvokingState::None { last_evokation };
+        if hotseat.is_hotseat() {
+            let first = hotseat.seats[0];
+            hotseat.hand_over(
+                first,
+                &mut player_id,
+                &mut player_turn,
+                &mut turn_report,
+                &mut standing_orders,
+            );
+        }
         for mut visibility in evoking_ui.iter_mut() {
             *visibility = Visibility::Hidden;
 
@@ -475,7 +617,7 @@ fn add_evoking_ui(
                             },
                             text: Text::from_sections(
                                 vec![TextSection {
-                                    value: "You have evoked the darkness\non the third full moon of the season.\n".to_string(),
+                                    value: "You have evoked the darkness.\n".to_string(),
                                     style: TextStyle {
                                         font: assets.font.clone(),
                                         font_size: FONT_SIZE,
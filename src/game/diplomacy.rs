@@ -0,0 +1,156 @@
+use crate::prelude::*;
+
+use super::turn_ui::EVOKE_COLOR;
+
+pub struct DiplomacyPlugin;
+
+impl Plugin for DiplomacyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamePacts>()
+            .add_system(add_diplomacy_ui.in_schedule(OnEnter(GameState::Playing)))
+            .add_system(plan_diplomacy_on_click.run_if(in_state(GameState::Playing)))
+            .add_system(update_diplomacy_ui.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default, PartialEq)]
+pub struct GamePacts(pub Pacts);
+
+#[derive(Component)]
+struct DiplomacyButton(PlayerId);
+
+fn add_diplomacy_ui(
+    mut commands: Commands,
+    assets: Res<MyAssets>,
+    game_players: Res<GamePlayers>,
+    player_id: Res<LocalPlayer>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(ONE_UNIT),
+                        right: Val::Px(ONE_UNIT),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    gap: Size::all(Val::Px(ONE_UNIT)),
+                    ..default()
+                },
+                background_color: Color::ALICE_BLUE.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Name::new("diplomacy_ui"),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    ..default()
+                },
+                text: Text::from_section(
+                    "Pacts",
+                    TextStyle {
+                        font: assets.fancy_font.clone(),
+                        font_size: FONT_SIZE,
+                        color: Color::BLACK,
+                    },
+                ),
+                background_color: Color::WHITE.into(),
+                ..default()
+            });
+            for player in game_players
+                .get_ids()
+                .into_iter()
+                .filter(|player| *player != **player_id)
+            {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                border: UiRect::all(Val::Px(ONE_UNIT)),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                        DiplomacyButton(player),
+                        RelativeCursorPosition::default(),
+                        SimpleTooltip::new(
+                            "Allies never steal each other's followers,\nbrutalize together, and may share a victory.\nPacts change when the darkness is evoked.",
+                        ),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            text: Text::from_section(
+                                game_players.get_name(player).cloned().unwrap_or_default(),
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: FONT_SIZE,
+                                    color: Color::BLACK,
+                                },
+                            ),
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
+
+fn plan_diplomacy_on_click(
+    pacts: Res<GamePacts>,
+    player_id: Res<LocalPlayer>,
+    mut player_turn: ResMut<CurrentTurn>,
+    interaction_query: Query<(&Interaction, &DiplomacyButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            if player_turn.get_diplomacy(button.0).is_some() {
+                player_turn.set_diplomacy(button.0, None);
+            } else {
+                player_turn.set_diplomacy(button.0, Some(pacts.available(**player_id, button.0)));
+            }
+        }
+    }
+}
+
+fn update_diplomacy_ui(
+    pacts: Res<GamePacts>,
+    player_id: Res<LocalPlayer>,
+    player_turn: Res<CurrentTurn>,
+    game_players: Res<GamePlayers>,
+    mut button_query: Query<(&DiplomacyButton, &Children, &mut BackgroundColor)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children, mut background) in button_query.iter_mut() {
+        let name = game_players.get_name(button.0).cloned().unwrap_or_default();
+        let status = if pacts.are_allied(**player_id, button.0) {
+            "Allied"
+        } else if pacts.has_proposal(button.0, **player_id) {
+            "Offers a pact"
+        } else if pacts.has_proposal(**player_id, button.0) {
+            "Pact offered"
+        } else {
+            "No pact"
+        };
+        let planned = player_turn.get_diplomacy(button.0);
+        *background = if planned.is_some() {
+            EVOKE_COLOR.into()
+        } else {
+            Color::WHITE.into()
+        };
+        let value = match planned {
+            Some(diplomacy) => format!("{} ({})\n> {}", name, status, diplomacy.describe()),
+            None => format!("{} ({})", name, status),
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+}
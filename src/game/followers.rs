@@ -0,0 +1,266 @@
+use crate::prelude::*;
+use signs_core::{names::theme, world::FULL_LOYALTY};
+
+use super::{agent::AgentFocus, turn_ui::EVOKE_COLOR};
+
+/// How many followers the area panel lists at once.
+const FOLLOWERS_PER_PAGE: usize = 8;
+
+pub struct FollowersPlugin;
+
+impl Plugin for FollowersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FollowerBrowser>()
+            .add_system(browse_followers_on_click.run_if(in_state(GameState::Playing)))
+            .add_system(update_follower_list.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// The page of followers shown for the selected area.
+#[derive(Resource, Debug, Clone, Default)]
+struct FollowerBrowser {
+    area: Option<(u32, u32)>,
+    page: usize,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum FollowerPage {
+    Previous,
+    Next,
+}
+
+/// One line of the follower list, and who it currently shows.
+#[derive(Component, Debug, Clone, Copy)]
+struct FollowerSlot {
+    index: usize,
+    follower: Option<FollowerId>,
+}
+
+/// The follower list in the area panel: a page of followers with buttons to turn it.
+pub fn spawn_follower_list(parent: &mut ChildBuilder, font: Handle<Font>) {
+    let text_style = TextStyle {
+        font,
+        font_size: FONT_SIZE,
+        color: Color::BLACK,
+    };
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    flex_direction: FlexDirection::Column,
+                    gap: Size::all(Val::Px(ONE_UNIT)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            Name::new("area_follower_list"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        gap: Size::all(Val::Px(ONE_UNIT)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (page, label) in [(FollowerPage::Previous, "<"), (FollowerPage::Next, ">")]
+                    {
+                        if page == FollowerPage::Next {
+                            parent.spawn((
+                                TextBundle {
+                                    style: Style {
+                                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                                        ..default()
+                                    },
+                                    text: Text::from_section("", text_style.clone()),
+                                    background_color: Color::WHITE.into(),
+                                    ..default()
+                                },
+                                Name::new("follower_page"),
+                            ));
+                        }
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                                        ..default()
+                                    },
+                                    background_color: Color::WHITE.into(),
+                                    ..default()
+                                },
+                                page,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(label, text_style.clone()));
+                            });
+                    }
+                });
+            for index in 0..FOLLOWERS_PER_PAGE {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                border: UiRect::all(Val::Px(ONE_UNIT)),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                        FollowerSlot {
+                            index,
+                            follower: None,
+                        },
+                        RelativeCursorPosition::default(),
+                        SimpleTooltip::new(
+                            "Click to single this follower out for the agent shown.\nThey will be the one promoted, sacrificed or corrupted, if they can be.\nClick again to leave it to chance.",
+                        ),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("", text_style.clone()));
+                    });
+            }
+        });
+}
+
+fn browse_followers_on_click(
+    mut browser: ResMut<FollowerBrowser>,
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+    agent_focus: Res<AgentFocus>,
+    page_query: Query<(&Interaction, &FollowerPage), Changed<Interaction>>,
+    slot_query: Query<(&Interaction, &FollowerSlot), Changed<Interaction>>,
+) {
+    for (interaction, page) in page_query.iter() {
+        if *interaction == Interaction::Clicked {
+            browser.page = match page {
+                FollowerPage::Previous => browser.page.saturating_sub(1),
+                FollowerPage::Next => browser.page + 1,
+            };
+        }
+    }
+    for (interaction, slot) in slot_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        if let (Some(agent_id), Some(follower)) = (agent_focus.shown, slot.follower) {
+            let target = if player_turn.get_target(agent_id) == Some(follower) {
+                None
+            } else {
+                Some(follower)
+            };
+            history.record(&mut player_turn, |turn| turn.set_target(agent_id, target));
+        }
+    }
+}
+
+fn update_follower_list(
+    mut browser: ResMut<FollowerBrowser>,
+    player_id: Res<LocalPlayer>,
+    player_turn: Res<CurrentTurn>,
+    game_players: Res<GamePlayers>,
+    agent_focus: Res<AgentFocus>,
+    tile_input: Res<TileInputState>,
+    ruleset: Res<GameRuleset>,
+    map_query: Query<&MapArea>,
+    mut slot_query: Query<(
+        &mut FollowerSlot,
+        &Children,
+        &mut BackgroundColor,
+        &mut Visibility,
+    )>,
+    mut text_query: Query<(Option<&Name>, &mut Text)>,
+) {
+    let area = match tile_input
+        .selected
+        .and_then(|(entity, _, _)| map_query.get(entity).ok())
+    {
+        Some(area) => area,
+        None => return,
+    };
+    if browser.area != Some(area.world_position) {
+        browser.area = Some(area.world_position);
+        browser.page = 0;
+    }
+    // The player's own followers first, strongest first.
+    let mut followers: Vec<&Follower> = area.followers.iter().collect();
+    followers.sort_by_key(|follower| {
+        (
+            follower.affinity != Some(**player_id),
+            std::cmp::Reverse(follower.power),
+            follower.id,
+        )
+    });
+    let pages = (followers.len() + FOLLOWERS_PER_PAGE - 1) / FOLLOWERS_PER_PAGE;
+    if browser.page >= pages.max(1) {
+        browser.page = pages.max(1) - 1;
+    }
+    let page: Vec<&Follower> = followers
+        .iter()
+        .skip(browser.page * FOLLOWERS_PER_PAGE)
+        .take(FOLLOWERS_PER_PAGE)
+        .cloned()
+        .collect();
+    let target = agent_focus
+        .shown
+        .and_then(|agent_id| player_turn.get_target(agent_id));
+
+    for (name, mut text) in text_query.iter_mut() {
+        if name
+            .map(|name| name.eq_ignore_ascii_case("follower_page"))
+            .unwrap_or_default()
+        {
+            text.sections[0].value = format!("Page {} of {}", browser.page + 1, pages.max(1));
+        }
+    }
+    for (mut slot, children, mut background, mut visibility) in slot_query.iter_mut() {
+        let follower = match page.get(slot.index) {
+            Some(follower) => follower,
+            None => {
+                slot.follower = None;
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+        };
+        slot.follower = Some(follower.id);
+        *visibility = Visibility::Inherited;
+        *background = if target == Some(follower.id) {
+            EVOKE_COLOR.into()
+        } else {
+            Color::WHITE.into()
+        };
+        let affinity = match follower.affinity {
+            Some(player) => game_players
+                .get_name(player)
+                .cloned()
+                .unwrap_or_else(|| "Unknown".to_string()),
+            None => "Unaligned".to_string(),
+        };
+        let standing = if follower.corrupted {
+            ", corrupted".to_string()
+        } else if follower.affinity.is_some() {
+            format!(", loyalty {}/{}", follower.loyalty, FULL_LOYALTY)
+        } else {
+            String::new()
+        };
+        let value = format!(
+            "{} #{} ({}), power {}{}",
+            follower.name(theme(&ruleset.names)),
+            follower.id.number,
+            affinity,
+            follower.power,
+            standing
+        );
+        for child in children.iter() {
+            if let Ok((_, mut text)) = text_query.get_mut(*child) {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::*;
+
+use super::turn_ui::{TurnReport, EVOKE_COLOR};
+use signs_core::player::StandingOrders;
+
+pub struct HotseatPlugin;
+
+impl Plugin for HotseatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Hotseat>()
+            .add_system(add_pass_device_ui.in_schedule(OnEnter(GameState::Playing)))
+            .add_system(pass_device.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// What a seat leaves behind while someone else has the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Seat {
+    pub turn: PlayerTurn,
+    pub report: TurnReport,
+    pub standing_orders: StandingOrders,
+}
+
+impl Seat {
+    fn new(player: PlayerId) -> Self {
+        Self {
+            turn: PlayerTurn::new(player),
+            report: TurnReport::default(),
+            standing_orders: StandingOrders::default(),
+        }
+    }
+}
+
+/// The human seats sharing this client. With more than one, each takes their turn in order and
+/// the evokation is gathered here instead of through runes.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Hotseat {
+    pub seats: Vec<PlayerId>,
+    /// The seats waiting for the device, with their orders and reports.
+    pub waiting: BTreeMap<PlayerId, Seat>,
+    /// Whether the device is being handed over, so nothing of the last seat's may show.
+    pub passing: bool,
+}
+
+impl Hotseat {
+    pub fn new(seats: Vec<PlayerId>) -> Self {
+        Self {
+            passing: seats.len() > 1,
+            seats,
+            waiting: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_hotseat(&self) -> bool {
+        self.seats.len() > 1
+    }
+
+    /// The first seat still to evoke this season, if any is left.
+    pub fn next_seat(&self, evoking_state: &EvokingState) -> Option<PlayerId> {
+        self.seats
+            .iter()
+            .copied()
+            .find(|seat| !evoking_state.has_evoked(*seat))
+    }
+
+    /// The seat's orders and report, whether it holds the device or waits for it.
+    pub fn seat_mut(&mut self, player: PlayerId) -> &mut Seat {
+        self.waiting
+            .entry(player)
+            .or_insert_with(|| Seat::new(player))
+    }
+
+    /// Puts the local seat's orders and report aside and takes up those of the next seat.
+    pub fn hand_over(
+        &mut self,
+        next: PlayerId,
+        player: &mut LocalPlayer,
+        player_turn: &mut CurrentTurn,
+        turn_report: &mut TurnReport,
+        standing_orders: &mut GameStandingOrders,
+    ) {
+        if next == **player {
+            self.passing = true;
+            return;
+        }
+        let mut seat = self
+            .waiting
+            .remove(&next)
+            .unwrap_or_else(|| Seat::new(next));
+        std::mem::swap(&mut seat.turn, &mut player_turn.0);
+        std::mem::swap(&mut seat.report, turn_report);
+        std::mem::swap(&mut seat.standing_orders, &mut standing_orders.0);
+        self.waiting.insert(**player, seat);
+        // The report panel still shows the last seat's page.
+        turn_report.rendered_event_id = None;
+        player.0 = next;
+        self.passing = true;
+    }
+}
+
+#[derive(Component)]
+struct PassDeviceUi;
+
+#[derive(Component)]
+struct PassDeviceButton;
+
+fn pass_device(
+    mut hotseat: ResMut<Hotseat>,
+    player: Res<LocalPlayer>,
+    game_players: Res<GamePlayers>,
+    interactions: Query<&Interaction, (Changed<Interaction>, With<PassDeviceButton>)>,
+    mut pass_ui: Query<&mut Visibility, With<PassDeviceUi>>,
+    mut text_query: Query<(&Name, &mut Text)>,
+) {
+    if interactions
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        hotseat.passing = false;
+    }
+    for mut visibility in pass_ui.iter_mut() {
+        *visibility = if hotseat.passing {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if hotseat.passing {
+        let name = game_players.get_name(**player).cloned().unwrap_or_default();
+        for (text_name, mut text) in text_query.iter_mut() {
+            if text_name.eq_ignore_ascii_case("pass_device_title") {
+                text.sections[0].value = format!("Pass the device to {}", name);
+            } else if text_name.eq_ignore_ascii_case("pass_device_button") {
+                text.sections[0].value = format!("I am {}", name);
+            }
+        }
+    }
+}
+
+fn add_pass_device_ui(mut commands: Commands, assets: Res<MyAssets>, hotseat: Res<Hotseat>) {
+    // Covers the whole screen, map and all, until the next seat is at the device.
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(0.),
+                        right: Val::Px(0.),
+                        top: Val::Px(0.),
+                        bottom: Val::Px(0.),
+                    },
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(ONE_UNIT * 4.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                z_index: ZIndex::Global(10),
+                visibility: if hotseat.passing {
+                    Visibility::Visible
+                } else {
+                    Visibility::Hidden
+                },
+                ..default()
+            },
+            PassDeviceUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Pass the device",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: FONT_SIZE * 2.,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    ..default()
+                },
+                Name::new("pass_device_title"),
+            ));
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            border: UiRect::all(Val::Px(ONE_UNIT)),
+                            ..default()
+                        },
+                        background_color: Color::WHITE.into(),
+                        ..default()
+                    },
+                    PassDeviceButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text::from_section(
+                                "I am here",
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: 32.,
+                                    color: Color::BLACK,
+                                },
+                            ),
+                            background_color: EVOKE_COLOR.into(),
+                            ..default()
+                        },
+                        Name::new("pass_device_button"),
+                    ));
+                });
+        });
+}
@@ -2,10 +2,18 @@ use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 
 pub mod agent;
 pub mod ai;
+pub mod calendar;
 pub mod darkness;
+pub mod diplomacy;
+pub mod followers;
+pub mod hotseat;
+pub mod orders;
 pub mod persistence;
 pub mod player;
+pub mod ruleset;
+pub mod scenario;
 pub mod tooltip;
+pub mod treasury;
 pub mod turn_ui;
 pub mod turns;
 pub mod ui;
@@ -21,6 +29,12 @@ impl PluginGroup for GamePlugins {
         group = group.add(world::AreaPlugin);
         group = group.add(world::tiles_3d::TilesPlugin);
         group = group.add(darkness::DarknessPlugin);
+        group = group.add(hotseat::HotseatPlugin);
+        group = group.add(diplomacy::DiplomacyPlugin);
+        group = group.add(orders::OrdersPlugin);
+        group = group.add(followers::FollowersPlugin);
+        group = group.add(treasury::TreasuryPlugin);
+        group = group.add(calendar::CalendarPlugin);
         group = group.add(ui::UiPlugin);
         group = group.add(tooltip::TooltipPlugin);
         group = group.add(turn_ui::TurnUiPlugin);
@@ -29,5 +43,3 @@ impl PluginGroup for GamePlugins {
         group
     }
 }
-
-pub const WIN_SIGN_COUNT: u32 = 5;
@@ -0,0 +1,419 @@
+use crate::prelude::*;
+use signs_core::names::theme;
+
+use super::{
+    agent::{AgentFocus, AgentLocations},
+    turn_ui::EVOKE_COLOR,
+};
+use signs_core::{
+    agent::CORRUPT_POWER,
+    player::{StandingOrder, StandingOrders},
+};
+
+pub struct OrdersPlugin;
+
+impl Plugin for OrdersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TurnHistory>()
+            .init_resource::<GameStandingOrders>()
+            .add_system(add_orders_ui.in_schedule(OnEnter(GameState::Playing)))
+            .add_system(orders_on_click.run_if(in_state(GameState::Playing)))
+            .add_system(orders_shortcuts.run_if(in_state(GameState::Playing)))
+            .add_system(update_orders_ui.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// The local player's standing orders, given again at the start of every season.
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameStandingOrders(pub StandingOrders);
+
+/// A line in the orders panel, clicked to focus its agent.
+#[derive(Component, Deref, Debug, Clone, Copy, PartialEq, Eq)]
+struct OrderRow(AgentId);
+
+/// Cycles how long an agent's order stands.
+#[derive(Component, Deref, Debug, Clone, Copy, PartialEq, Eq)]
+struct StandingToggle(AgentId);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum OrdersButton {
+    Undo,
+    Redo,
+    ClearAll,
+}
+
+impl OrdersButton {
+    fn label(&self) -> &'static str {
+        match self {
+            OrdersButton::Undo => "Undo",
+            OrdersButton::Redo => "Redo",
+            OrdersButton::ClearAll => "Clear all",
+        }
+    }
+
+    fn tooltip(&self) -> &'static str {
+        match self {
+            OrdersButton::Undo => "Take back the last change to your orders. (Ctrl+Z)",
+            OrdersButton::Redo => "Make the last undone change again. (Ctrl+Y)",
+            OrdersButton::ClearAll => "Leave every agent unassigned, with no followers singled out or bribes paid.",
+        }
+    }
+}
+
+/// What one row of the panel shows, kept to tell when the rows need rebuilding.
+#[derive(Debug, Clone, PartialEq)]
+struct OrderLine {
+    agent_id: AgentId,
+    text: String,
+    standing: &'static str,
+    color: Color,
+}
+
+/// The next step in the cycle of once, every season, and until strong enough to corrupt.
+fn next_standing_order(
+    order: Option<&StandingOrder>,
+    action: Option<AgentAction>,
+) -> Option<StandingOrder> {
+    match (order, action) {
+        (None, Some(action)) if action != AgentAction::None => Some(StandingOrder::repeat(action)),
+        (Some(order), _)
+            if order.until.is_none()
+                && !matches!(
+                    order.action,
+                    AgentAction::Corrupt | AgentAction::CorruptAgent
+                ) =>
+        {
+            Some(StandingOrder::until(
+                order.action.clone(),
+                CORRUPT_POWER,
+                AgentAction::Corrupt,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn add_orders_ui(mut commands: Commands, assets: Res<MyAssets>, mut history: ResMut<TurnHistory>) {
+    history.clear();
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Percent(30.),
+                        right: Val::Px(ONE_UNIT),
+                        ..default()
+                    },
+                    max_size: Size::height(Val::Percent(50.)),
+                    flex_direction: FlexDirection::Column,
+                    gap: Size::all(Val::Px(ONE_UNIT)),
+                    overflow: Overflow::Hidden,
+                    ..default()
+                },
+                background_color: Color::ALICE_BLUE.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Name::new("orders_ui"),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    ..default()
+                },
+                text: Text::from_section(
+                    "Orders",
+                    TextStyle {
+                        font: assets.fancy_font.clone(),
+                        font_size: FONT_SIZE,
+                        color: Color::BLACK,
+                    },
+                ),
+                background_color: Color::WHITE.into(),
+                ..default()
+            });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        gap: Size::all(Val::Px(ONE_UNIT)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for button in [
+                        OrdersButton::Undo,
+                        OrdersButton::Redo,
+                        OrdersButton::ClearAll,
+                    ] {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                                        ..default()
+                                    },
+                                    background_color: Color::WHITE.into(),
+                                    ..default()
+                                },
+                                button,
+                                RelativeCursorPosition::default(),
+                                SimpleTooltip::new(button.tooltip()),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    button.label(),
+                                    TextStyle {
+                                        font: assets.font.clone(),
+                                        font_size: FONT_SIZE,
+                                        color: Color::BLACK,
+                                    },
+                                ));
+                            });
+                    }
+                });
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        gap: Size::all(Val::Px(ONE_UNIT)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Name::new("orders_list"),
+            ));
+        });
+}
+
+fn orders_on_click(
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+    mut agent_focus: ResMut<AgentFocus>,
+    mut standing_orders: ResMut<GameStandingOrders>,
+    agent_locations: Res<AgentLocations>,
+    tile_query: Query<(Entity, &MapTile)>,
+    mut tile_input: ResMut<TileInputState>,
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&OrderRow>,
+            Option<&StandingToggle>,
+            Option<&OrdersButton>,
+        ),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, row, toggle, button) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        if let Some(row) = row {
+            if let Some((x, y)) = agent_locations.locations.get(&**row) {
+                if let Some((entity, _)) = tile_query
+                    .iter()
+                    .find(|(_, tile)| tile.x == *x as TileLoc && tile.y == *y as TileLoc)
+                {
+                    tile_input.selected = Some((entity, *x as TileLoc, *y as TileLoc));
+                    agent_focus.requested = Some(**row);
+                }
+            }
+        }
+        if let Some(toggle) = toggle {
+            let order = next_standing_order(
+                standing_orders.get(**toggle),
+                player_turn.get_action(**toggle),
+            );
+            standing_orders.set(**toggle, order);
+        }
+        match button {
+            Some(OrdersButton::Undo) => {
+                history.undo(&mut player_turn);
+            }
+            Some(OrdersButton::Redo) => {
+                history.redo(&mut player_turn);
+            }
+            Some(OrdersButton::ClearAll) => {
+                history.record(&mut player_turn, |turn| turn.clear_actions());
+            }
+            None => {}
+        }
+    }
+}
+
+fn orders_shortcuts(
+    keyboard: Res<Input<KeyCode>>,
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+) {
+    if !keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+    let shift = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    if keyboard.just_pressed(KeyCode::Y) || (shift && keyboard.just_pressed(KeyCode::Z)) {
+        history.redo(&mut player_turn);
+    } else if keyboard.just_pressed(KeyCode::Z) {
+        history.undo(&mut player_turn);
+    }
+}
+
+fn update_orders_ui(
+    mut commands: Commands,
+    mut shown: Local<(Option<Entity>, Vec<OrderLine>)>,
+    assets: Res<MyAssets>,
+    player_id: Res<LocalPlayer>,
+    player_turn: Res<CurrentTurn>,
+    history: Res<TurnHistory>,
+    standing_orders: Res<GameStandingOrders>,
+    ruleset: Res<GameRuleset>,
+    areas: Query<&MapArea>,
+    list_query: Query<(Entity, &Name)>,
+    mut button_query: Query<(&OrdersButton, &mut BackgroundColor)>,
+) {
+    let names = theme(&ruleset.names);
+    for (button, mut background) in button_query.iter_mut() {
+        let available = match button {
+            OrdersButton::Undo => history.can_undo(),
+            OrdersButton::Redo => history.can_redo(),
+            OrdersButton::ClearAll => player_turn
+                .actions
+                .values()
+                .any(|action| *action != AgentAction::None),
+        };
+        *background = if available {
+            Color::WHITE.into()
+        } else {
+            Color::GRAY.into()
+        };
+    }
+
+    let mut lines: Vec<OrderLine> = areas
+        .iter()
+        .flat_map(|area| {
+            area.player_agents(**player_id).map(|agent| {
+                let action = player_turn
+                    .get_action(agent.id)
+                    .unwrap_or(AgentAction::None);
+                let mut text = format!("{} in {}\n> {}", agent.name, area.name, action.title());
+                if let Some(target) = player_turn
+                    .get_target(agent.id)
+                    .and_then(|target| area.get_follower(target))
+                {
+                    text.push_str(&format!("\nSingling out {}", target.name(names)));
+                }
+                let standing = standing_orders.get(agent.id);
+                if let Some(order) = standing {
+                    text.push_str(&format!("\nStanding: {}", order.describe()));
+                }
+                let lapsed = standing_orders.lapsed(agent.id);
+                if let Some(lapsed) = lapsed {
+                    text.push_str(&format!(
+                        "\nLapsed: {}\n{}",
+                        lapsed.order.describe(),
+                        lapsed.reason
+                    ));
+                }
+                OrderLine {
+                    agent_id: agent.id,
+                    text,
+                    standing: match standing {
+                        Some(StandingOrder { until: Some(_), .. }) => "Until",
+                        Some(_) => "Repeat",
+                        None => "Once",
+                    },
+                    color: if lapsed.is_some() {
+                        Color::SALMON
+                    } else if action != AgentAction::None {
+                        EVOKE_COLOR
+                    } else {
+                        Color::WHITE
+                    },
+                }
+            })
+        })
+        .collect();
+    lines.sort_by_key(|line| line.agent_id);
+    let list = match list_query
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case("orders_list"))
+    {
+        Some((list, _)) => list,
+        None => return,
+    };
+    if shown.0 == Some(list) && shown.1 == lines {
+        return;
+    }
+    commands.entity(list).despawn_descendants();
+    commands.entity(list).with_children(|parent| {
+        for line in lines.iter() {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        gap: Size::all(Val::Px(ONE_UNIT)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                                    flex_grow: 1.,
+                                    ..default()
+                                },
+                                background_color: line.color.into(),
+                                ..default()
+                            },
+                            OrderRow(line.agent_id),
+                            RelativeCursorPosition::default(),
+                            SimpleTooltip::new("Click to focus this agent."),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                line.text.clone(),
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: FONT_SIZE,
+                                    color: Color::BLACK,
+                                },
+                            ));
+                        });
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                                    ..default()
+                                },
+                                background_color: Color::WHITE.into(),
+                                ..default()
+                            },
+                            StandingToggle(line.agent_id),
+                            RelativeCursorPosition::default(),
+                            SimpleTooltip::new(format!(
+                                "How long this order stands:\nOnce, this season only.\nRepeat, every season until it can't be carried out.\nUntil, then Corrupt once your power here passes {}.",
+                                CORRUPT_POWER
+                            )),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                line.standing,
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: FONT_SIZE,
+                                    color: Color::BLACK,
+                                },
+                            ));
+                        });
+                });
+        }
+    });
+    *shown = (Some(list), lines);
+}
@@ -6,6 +6,7 @@ pub use map_desc::*;
 pub use runes::*;
 
 use super::turn_ui::{TurnReport, EVOKE_COLOR, TRANSPARENT_EVOKE_COLOR};
+use signs_core::{economy::Treasury, player::StandingOrders, world::AgentNumbers};
 
 pub struct PersistencePlugin;
 
@@ -116,7 +117,7 @@ fn add_save_button(mut commands: Commands, assets: Res<MyAssets>) {
 }
 
 fn show_hide_save(
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
     save_data: Option<Res<SaveData>>,
     mut button_query: Query<(&Name, &mut Visibility)>,
 ) {
@@ -142,7 +143,8 @@ fn show_hide_save(
 #[cfg(target_arch = "wasm32")]
 fn save_on_click(
     players: Res<GamePlayers>,
-    player: Res<PlayerId>,
+    player: Res<LocalPlayer>,
+    hotseat: Res<Hotseat>,
     save_data: Option<Res<SaveData>>,
     interactions: Query<&Interaction, (Changed<Interaction>, With<SaveGameButton>)>,
 ) {
@@ -150,7 +152,8 @@ fn save_on_click(
         if *interaction == Interaction::Clicked && save_data.is_some() {
             let data = save_data.as_ref().unwrap();
             let json = serde_json::to_string(data.as_ref()).unwrap();
-            save_game_js(format!("{}.json", players.get_save_prefix(*player)), json);
+            let seat = hotseat.seats.first().copied().unwrap_or(**player);
+            save_game_js(format!("{}.json", players.get_save_prefix(seat)), json);
         }
     }
 }
@@ -178,28 +181,7 @@ fn wait_for_loads() {
 fn load_on_event(mut commands: Commands, mut next_state: ResMut<NextState<GameState>>) {
     if let Some(save) = LOAD_STRING.lock().unwrap().take() {
         let save: SaveData = serde_json::from_str(&save).unwrap();
-        commands.insert_resource(save.players.clone());
-        commands.insert_resource(save.player_id.clone());
-        commands.insert_resource(save.ai_seeds.clone());
-        commands.insert_resource(save.map_desc);
-        commands.insert_resource(save.turn_report);
-        if let Some(evokation) = save
-            .last_evokation
-            .and_then(|evokation| read_from_runes::<Evokation>(&evokation, false))
-        {
-            if evokation.season == *save.season {
-                commands.insert_resource(EvokingState::resume(evokation.clone(), &save.players));
-                commands.insert_resource(evokation.player_turn);
-            } else {
-                commands.insert_resource(EvokingState::None {
-                    last_evokation: Some(evokation),
-                });
-                commands.insert_resource(PlayerTurn::new(save.player_id));
-            }
-        } else {
-            commands.insert_resource(PlayerTurn::new(save.player_id));
-        }
-        commands.insert_resource(save.season);
+        save.restore(&mut commands);
         next_state.set(GameState::Playing);
         hide_load();
     }
@@ -214,9 +196,80 @@ pub struct SaveData {
     pub map_desc: MapDesc,
     pub last_evokation: Option<String>,
     pub turn_report: TurnReport,
+    #[serde(default)]
+    pub ruleset: Ruleset,
+    #[serde(default)]
+    pub pacts: Pacts,
+    #[serde(default)]
+    pub standing_orders: StandingOrders,
+    #[serde(default)]
+    pub treasury: Treasury,
+    #[serde(default)]
+    pub agent_numbers: AgentNumbers,
+    /// The seats sharing the device, and what those waiting for it have left behind.
+    #[serde(default)]
+    pub hotseat: Hotseat,
+    /// Every turn in so far this season, so a hotseat game resumes with all of them.
+    #[serde(default)]
+    pub evokations: Vec<String>,
 }
 
-fn describe_map(tile_query: &Query<(&MapTile, Option<&WorldArea>)>) -> MapDesc {
+impl SaveData {
+    /// Sets the game up as it was saved, mid-evokation or not.
+    pub fn restore(self, commands: &mut Commands) {
+        let season = self.season;
+        let mut hotseat = self.hotseat;
+        // Whoever loads the game may not be the seat that saved it.
+        hotseat.passing = hotseat.is_hotseat();
+        let player_turn = hotseat
+            .waiting
+            .remove(&self.player_id)
+            .map(|seat| seat.turn)
+            .unwrap_or_else(|| PlayerTurn::new(self.player_id));
+        let last_evokation = self
+            .last_evokation
+            .and_then(|evokation| Evokation::from_runes(&evokation, false));
+        let mut evokations = last_evokation
+            .iter()
+            .cloned()
+            .chain(
+                self.evokations
+                    .iter()
+                    .filter_map(|evokation| Evokation::from_runes(evokation, false)),
+            )
+            .filter(|evokation| evokation.season == *season)
+            .collect::<Vec<_>>()
+            .into_iter();
+        if let Some(first) = evokations.next() {
+            let mut evoking_state = EvokingState::resume(first, &self.players);
+            for evokation in evokations {
+                evoking_state.push_evokation(evokation);
+            }
+            match last_evokation.filter(|evokation| evokation.season == *season) {
+                Some(evokation) => commands.insert_resource(CurrentTurn(evokation.player_turn)),
+                None => commands.insert_resource(CurrentTurn(player_turn)),
+            }
+            commands.insert_resource(evoking_state);
+        } else {
+            commands.insert_resource(EvokingState::None { last_evokation });
+            commands.insert_resource(CurrentTurn(player_turn));
+        }
+        commands.insert_resource(self.players);
+        commands.insert_resource(LocalPlayer(self.player_id));
+        commands.insert_resource(self.ai_seeds);
+        commands.insert_resource(GameMap(self.map_desc));
+        commands.insert_resource(self.turn_report);
+        commands.insert_resource(GameRuleset(self.ruleset));
+        commands.insert_resource(GamePacts(self.pacts));
+        commands.insert_resource(GameStandingOrders(self.standing_orders));
+        commands.insert_resource(GameTreasury(self.treasury));
+        commands.insert_resource(GameAgentNumbers(self.agent_numbers));
+        commands.insert_resource(hotseat);
+        commands.insert_resource(season);
+    }
+}
+
+pub fn describe_map(tile_query: &Query<(&MapTile, Option<&MapArea>)>) -> MapDesc {
     let width = tile_query.iter().map(|(tile, _)| tile.x).max().unwrap_or(0) as u32 + 1;
     let height = tile_query.iter().map(|(tile, _)| tile.y).max().unwrap_or(0) as u32 + 1;
     let mut tiles = vec![0; (width * height) as usize];
@@ -229,7 +282,7 @@ fn describe_map(tile_query: &Query<(&MapTile, Option<&WorldArea>)>) -> MapDesc {
         }
         tiles[(tile.y as u32 * width + tile.x as u32) as usize] = tile.sprite_id;
         if let Some(area) = area {
-            areas.push(area.clone());
+            areas.push(area.0.clone());
         }
     }
     MapDesc {
@@ -241,34 +294,60 @@ fn describe_map(tile_query: &Query<(&MapTile, Option<&WorldArea>)>) -> MapDesc {
 }
 
 fn save_periodically(
-    mut last_season: Local<(i32, bool)>,
-    player_turn: Res<PlayerTurn>,
+    mut last_season: Local<(i32, usize)>,
+    player_turn: Res<CurrentTurn>,
     season: Res<Season>,
     ai_seeds: Res<AiSeeds>,
     players: Res<GamePlayers>,
-    player: Res<PlayerId>,
+    player: Res<LocalPlayer>,
     evokation: Res<EvokingState>,
     turn_report: Res<TurnReport>,
-    tile_query: Query<(&MapTile, Option<&WorldArea>)>,
+    ruleset: Res<GameRuleset>,
+    pacts: Res<GamePacts>,
+    standing_orders: Res<GameStandingOrders>,
+    (treasury, agent_numbers): (Res<GameTreasury>, Res<GameAgentNumbers>),
+    hotseat: Res<Hotseat>,
+    tile_query: Query<(&MapTile, Option<&MapArea>)>,
     #[cfg(target_arch = "wasm32")] mut commands: Commands,
 ) {
-    if last_season.0 != season.0 || last_season.1 != evokation.is_evoking() {
+    let evokations = evokation.evokations();
+    // Each seat of a hotseat game evoking changes the save, not only the first.
+    if last_season.0 != season.0 || last_season.1 != evokations.len() {
         last_season.0 = season.0;
-        last_season.1 = evokation.is_evoking();
+        last_season.1 = evokations.len();
         let map_desc = describe_map(&tile_query);
+        let mut hotseat = hotseat.clone();
+        if hotseat.is_hotseat() {
+            // The seat at the device keeps its orders too.
+            hotseat.seat_mut(**player).turn = player_turn.0.clone();
+        }
         let save_data = SaveData {
             season: *season,
             ai_seeds: ai_seeds.clone(),
             players: players.clone(),
-            player_id: *player,
+            player_id: **player,
             map_desc,
             last_evokation: evokation.get_evokation(&*player).map(|e| e.to_runes(false)),
             turn_report: turn_report.clone(),
+            ruleset: ruleset.0.clone(),
+            pacts: pacts.0.clone(),
+            standing_orders: standing_orders.0.clone(),
+            treasury: treasury.0.clone(),
+            agent_numbers: agent_numbers.0.clone(),
+            evokations: evokations
+                .iter()
+                .map(|evokation| evokation.to_runes(false))
+                .collect(),
+            hotseat: hotseat.clone(),
         };
         let default = "Unknown".to_string();
+        // One file for the whole table, whichever seat has the device.
         #[cfg(not(target_arch = "wasm32"))]
         save(
-            format!("{}.json", players.get_save_prefix(*player)),
+            format!(
+                "{}.json",
+                players.get_save_prefix(hotseat.seats.first().copied().unwrap_or(**player))
+            ),
             save_data,
         );
         #[cfg(target_arch = "wasm32")]
@@ -73,18 +73,21 @@ pub fn generate_runes(bytes: &[u8], futhark: bool) -> String {
 }
 
 pub fn retrieve_from_runes<T: crate::prelude::DeserializeOwned>() -> Result<T, String> {
-    get_clipboard_text()
-        .map(|text| {
-            let futhark = parse_runes(&text, true);
-            if futhark.len() > 0 {
-                futhark
-            } else {
-                parse_runes(&text, false)
-            }
-        })
+    retrieve_bytes_from_runes()
         .and_then(|bytes| postcard::from_bytes(&bytes).map_err(|e| e.to_string()))
 }
 
+pub fn retrieve_bytes_from_runes() -> Result<Vec<u8>, String> {
+    get_clipboard_text().map(|text| {
+        let futhark = parse_runes(&text, true);
+        if futhark.len() > 0 {
+            futhark
+        } else {
+            parse_runes(&text, false)
+        }
+    })
+}
+
 #[cfg(feature = "arboard")]
 fn get_clipboard_text() -> Result<String, String> {
     arboard::Clipboard::new()
@@ -104,6 +107,11 @@ pub fn store_in_runes<T: crate::prelude::Serialize>(t: T, futhark: bool) -> Opti
     set_clipboard_text(&runes).map(|_| runes)
 }
 
+pub fn store_bytes_in_runes(bytes: &[u8], futhark: bool) -> Option<String> {
+    let runes = generate_runes(bytes, futhark);
+    set_clipboard_text(&runes).map(|_| runes)
+}
+
 #[cfg(feature = "arboard")]
 fn set_clipboard_text(runes: &String) -> Option<()> {
     arboard::Clipboard::new()
@@ -176,4 +184,22 @@ mod runes_tests {
         let evokation: Evokation = postcard::from_bytes(&evokation).unwrap();
         assert_eq!(evokation.seed, 16729024516283669525);
     }
+
+    #[test]
+    fn test_evokation_diplomacy() {
+        use crate::game::darkness::Evokation;
+        use crate::prelude::PlayerId;
+        use signs_core::diplomacy::Diplomacy;
+        let runes = "ᛜᚱᛉᚩᚡᛊᛞᛗᚲᚫᛞᚾᚾᛚᚹᚠᚢᚠᚢᚦᚠᚠᚺᚠᚢᚡᚠᚱᛏᚠᚺᚠᚦᚺᚠᚦᚠᚢᚺᚠ";
+        let evokation = Evokation::from_runes(runes, true).unwrap();
+        assert!(evokation.player_turn.diplomacy.is_empty());
+        let mut player_turn = evokation.player_turn.clone();
+        player_turn.set_diplomacy(PlayerId(1), Some(Diplomacy::Propose(PlayerId(1))));
+        let evokation = Evokation::with_seed(evokation.season, player_turn, 7);
+        let evokation = Evokation::from_runes(&evokation.to_runes(false), false).unwrap();
+        assert_eq!(
+            evokation.player_turn.diplomacy,
+            vec![Diplomacy::Propose(PlayerId(1))]
+        );
+    }
 }
@@ -0,0 +1,12 @@
+use crate::prelude::*;
+use signs_core::calendar::Phase;
+
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default, PartialEq)]
+pub struct GameRuleset(pub Ruleset);
+
+impl GameRuleset {
+    /// The rules as they stand in the given season, once the calendar has had its say.
+    pub fn for_season(&self, season: i32) -> Ruleset {
+        Phase::of(season).ruleset(self)
+    }
+}
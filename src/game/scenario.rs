@@ -0,0 +1,33 @@
+use signs_core::scenario::Scenario;
+
+/// The scenarios that ship with the game, in the order the new-game menu offers them.
+const SCENARIO_SOURCES: &[&str] = &[include_str!("../../assets/scenarios/crossroads.ron")];
+
+lazy_static! {
+    /// A scenario that fails to load is left out of the menu rather than taking the game down.
+    pub static ref SCENARIOS: Vec<Scenario> = SCENARIO_SOURCES
+        .iter()
+        .filter_map(|source| match Scenario::from_ron(source) {
+            Ok(scenario) => Some(scenario),
+            Err(err) => {
+                println!("Could not load scenario: {}", err);
+                None
+            }
+        })
+        .collect();
+}
+
+pub fn find_scenario(id: &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|scenario| scenario.id == id)
+}
+
+/// The scenario after the given one, with a generated map (`None`) before the first.
+pub fn next_scenario(id: Option<&str>) -> Option<&'static Scenario> {
+    match id {
+        None => SCENARIOS.first(),
+        Some(id) => SCENARIOS
+            .iter()
+            .skip_while(|scenario| scenario.id != id)
+            .nth(1),
+    }
+}
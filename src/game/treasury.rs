@@ -0,0 +1,236 @@
+use crate::prelude::*;
+use signs_core::economy::{tithes, Treasury};
+
+use super::turn_ui::EVOKE_COLOR;
+
+/// How many paying areas the treasury tooltip lists before summing up the rest.
+const TITHES_LISTED: usize = 5;
+
+pub struct TreasuryPlugin;
+
+impl Plugin for TreasuryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameTreasury>()
+            .add_system(bribe_on_click.run_if(in_state(GameState::Playing)))
+            .add_system(update_treasury_ui.run_if(in_state(GameState::Playing)))
+            .add_system(treasury_tooltip.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default, PartialEq)]
+pub struct GameTreasury(pub Treasury);
+
+#[derive(Component)]
+struct BribeButton;
+
+/// Why the agents cannot all be given the action for lack of tithes, if they cannot.
+pub fn unaffordable_reason(
+    action: &AgentAction,
+    agent_ids: &[AgentId],
+    player_turn: &PlayerTurn,
+    treasury: &Treasury,
+    ruleset: &Ruleset,
+) -> Option<String> {
+    let changing: Vec<AgentId> = agent_ids
+        .iter()
+        .filter(|agent_id| player_turn.get_action(**agent_id).as_ref() != Some(action))
+        .cloned()
+        .collect();
+    let cost = action.cost(ruleset) * changing.len() as u32;
+    if cost == 0 {
+        return None;
+    }
+    let committed = player_turn.spending(ruleset)
+        - changing
+            .iter()
+            .filter_map(|agent_id| player_turn.get_action(*agent_id))
+            .map(|current| current.cost(ruleset))
+            .sum::<u32>();
+    let left = treasury
+        .balance(player_turn.player_id)
+        .saturating_sub(committed);
+    if cost > left {
+        Some(format!(
+            "This costs {} tithes.\nYou have {} left to spend this season.",
+            cost, left
+        ))
+    } else {
+        None
+    }
+}
+
+/// A button in the area panel to pay off the unaligned locals.
+pub fn spawn_bribe_button(parent: &mut ChildBuilder, font: Handle<Font>) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    border: UiRect::all(Val::Px(ONE_UNIT)),
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                ..default()
+            },
+            BribeButton,
+            RelativeCursorPosition::default(),
+            SimpleTooltip::new(
+                "Pay the unaligned locals here to follow you.\nOne of your agents must be here to hand over the tithes.\nClick again to keep your money.",
+            ),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "",
+                TextStyle {
+                    font,
+                    font_size: FONT_SIZE,
+                    color: Color::BLACK,
+                },
+            ));
+        });
+}
+
+fn selected_area<'a>(
+    tile_input: &TileInputState,
+    map_query: &'a Query<&MapArea>,
+) -> Option<&'a MapArea> {
+    tile_input
+        .selected
+        .and_then(|(entity, _, _)| map_query.get(entity).ok())
+}
+
+fn bribe_on_click(
+    mut player_turn: ResMut<CurrentTurn>,
+    mut history: ResMut<TurnHistory>,
+    treasury: Res<GameTreasury>,
+    ruleset: Res<GameRuleset>,
+    season: Res<Season>,
+    tile_input: Res<TileInputState>,
+    map_query: Query<&MapArea>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<BribeButton>)>,
+) {
+    let ruleset = ruleset.for_season(**season);
+    for interaction in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        if let Some(area) = selected_area(&tile_input, &map_query) {
+            let location = area.world_position;
+            if player_turn.is_bribing(location) {
+                history.record(&mut player_turn, |turn| turn.set_bribe(location, false));
+            } else if player_turn.spending(&ruleset) + ruleset.bribe_cost
+                <= treasury.balance(player_turn.player_id)
+            {
+                history.record(&mut player_turn, |turn| turn.set_bribe(location, true));
+            }
+        }
+    }
+}
+
+fn update_treasury_ui(
+    player_id: Res<LocalPlayer>,
+    player_turn: Res<CurrentTurn>,
+    treasury: Res<GameTreasury>,
+    ruleset: Res<GameRuleset>,
+    season: Res<Season>,
+    tile_input: Res<TileInputState>,
+    map_query: Query<&MapArea>,
+    mut button_query: Query<(&Children, &mut BackgroundColor, &mut Visibility), With<BribeButton>>,
+    mut text_query: Query<(Option<&Name>, &mut Text)>,
+) {
+    let ruleset = ruleset.for_season(**season);
+    let left = treasury
+        .balance(**player_id)
+        .saturating_sub(player_turn.spending(&ruleset));
+    for (name, mut text) in text_query.iter_mut() {
+        if name
+            .map(|name| name.eq_ignore_ascii_case("Tithes-value"))
+            .unwrap_or_default()
+        {
+            text.sections[0].value = left.to_string();
+        }
+    }
+    let area = selected_area(&tile_input, &map_query);
+    for (children, mut background, mut visibility) in button_query.iter_mut() {
+        let area = match area {
+            Some(area) if area.get_player_agent_count(**player_id) > 0 => area,
+            _ => {
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+        };
+        *visibility = Visibility::Inherited;
+        let bribing = player_turn.is_bribing(area.world_position);
+        *background = if bribing {
+            EVOKE_COLOR.into()
+        } else if ruleset.bribe_cost > left {
+            Color::GRAY.into()
+        } else {
+            Color::WHITE.into()
+        };
+        let value = if bribing {
+            format!("Bribing the locals for {} tithes", ruleset.bribe_cost)
+        } else {
+            format!("Bribe the locals: {} tithes", ruleset.bribe_cost)
+        };
+        for child in children.iter() {
+            if let Ok((_, mut text)) = text_query.get_mut(*child) {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+}
+
+fn treasury_tooltip(
+    mut tooltip_control: Local<bool>,
+    player_id: Res<LocalPlayer>,
+    player_turn: Res<CurrentTurn>,
+    treasury: Res<GameTreasury>,
+    ruleset: Res<GameRuleset>,
+    season: Res<Season>,
+    mut tooltip: ResMut<Tooltip>,
+    label_query: Query<(&RelativeCursorPosition, &Name)>,
+    map_query: Query<&MapArea>,
+) {
+    let ruleset = ruleset.for_season(**season);
+    for (cursor, name) in label_query.iter() {
+        if name.eq_ignore_ascii_case("Tithes") {
+            if cursor.mouse_over() {
+                *tooltip_control = true;
+                let balance = treasury.balance(**player_id);
+                let spending = player_turn.spending(&ruleset);
+                let mut tithes =
+                    tithes(map_query.iter().map(|area| &area.0), **player_id, &ruleset);
+                tithes.sort_by_key(|tithe| std::cmp::Reverse(tithe.amount));
+                let income: u32 = tithes.iter().map(|tithe| tithe.amount).sum();
+                let mut value = format!(
+                    "Your treasury holds {} tithes.\n{} are promised to this season's orders.\n\nNext season {} tithes come in",
+                    balance, spending, income
+                );
+                if tithes.is_empty() {
+                    value.push_str(".\nHold an area with your followers to collect its tithes.");
+                } else {
+                    value.push_str(":\n");
+                    for tithe in tithes.iter().take(TITHES_LISTED) {
+                        value.push_str(&format!("{}: {}\n", tithe.location_name, tithe.amount));
+                    }
+                    if tithes.len() > TITHES_LISTED {
+                        let rest: u32 = tithes
+                            .iter()
+                            .skip(TITHES_LISTED)
+                            .map(|tithe| tithe.amount)
+                            .sum();
+                        value.push_str(&format!(
+                            "{} more areas: {}\n",
+                            tithes.len() - TITHES_LISTED,
+                            rest
+                        ));
+                    }
+                }
+                tooltip.value = Some(value);
+            } else if *tooltip_control {
+                tooltip.value = None;
+                *tooltip_control = false;
+            }
+        }
+    }
+}
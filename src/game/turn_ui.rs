@@ -1,7 +1,7 @@
-use bevy::utils::HashMap;
-
 use crate::prelude::*;
 
+pub use signs_core::report::TurnReportEvent;
+
 pub struct TurnUiPlugin;
 
 impl Plugin for TurnUiPlugin {
@@ -17,300 +17,6 @@ impl Plugin for TurnUiPlugin {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum TurnReportEvent {
-    GameStart {
-        player_names: Vec<String>,
-    },
-    AgentAction {
-        location: (u32, u32),
-        location_name: String,
-        agent_name: String,
-        action: AgentAction,
-        success_amount: u32,
-        fail_amount: u32,
-    },
-    Brutalized {
-        location: (u32, u32),
-        location_name: String,
-        dead: u32,
-        fleeing: u32,
-    },
-    Sacrificed {
-        location: (u32, u32),
-        location_name: String,
-        follower: bool,
-    },
-    PromotedFollower {
-        location: (u32, u32),
-        location_name: String,
-        agent_name: String,
-    },
-    AgentSeen {
-        location: (u32, u32),
-        location_name: String,
-        power: u32,
-    },
-    SignSeen {
-        location: (u32, u32),
-        location_name: String,
-        mine: bool,
-    },
-    FollowersLost {
-        location: (u32, u32),
-        location_name: String,
-    },
-    GameOver {
-        winner: PlayerId,
-        scores: HashMap<PlayerId, u32>,
-    },
-    NewTurn {
-        turn: i32,
-    },
-}
-
-impl TurnReportEvent {
-    fn get_title(&self) -> String {
-        match self {
-            TurnReportEvent::GameStart { .. } => "Campaign Start".to_string(),
-            TurnReportEvent::AgentAction {
-                agent_name, action, ..
-            } => match action {
-                AgentAction::Brutalize => format!("Brutality"),
-                AgentAction::Corrupt => format!("Corruption"),
-                AgentAction::CorruptAgent => format!("Agent Corruption"),
-                AgentAction::Sacrifice => format!("Sacrifice"),
-                AgentAction::Prostelytize => format!("Prostelytizing"),
-                AgentAction::Move(_, _, _) => {
-                    format!("{} arrived", agent_name)
-                }
-                AgentAction::None => format!("???"),
-            },
-            TurnReportEvent::Brutalized { .. } => {
-                format!("Violence erupts!")
-            }
-            TurnReportEvent::FollowersLost { .. } => format!("Heretics!"),
-            TurnReportEvent::Sacrificed { follower, .. } => {
-                if *follower {
-                    format!("Follower sacrificed!")
-                } else {
-                    format!("Body found!")
-                }
-            }
-            TurnReportEvent::PromotedFollower {
-                location_name,
-                agent_name,
-                ..
-            } => {
-                format!("New agent: {}", agent_name)
-            }
-            TurnReportEvent::AgentSeen { .. } => format!("Agent Spotted!"),
-            TurnReportEvent::SignSeen { mine, .. } => {
-                if *mine {
-                    format!("Sign of Corruption!")
-                } else {
-                    format!("Sign of Corruption!")
-                }
-            }
-            TurnReportEvent::GameOver { winner, .. } => format!("Game Over"),
-            TurnReportEvent::NewTurn { turn } => format!("Season {}", turn),
-        }
-    }
-
-    fn get_location(&self) -> Option<(u32, u32)> {
-        match self {
-            TurnReportEvent::GameStart { .. } => None,
-            TurnReportEvent::AgentSeen { location, .. } => Some(*location),
-            TurnReportEvent::PromotedFollower { location, .. } => Some(*location),
-            TurnReportEvent::AgentAction { location, .. } => Some(*location),
-            TurnReportEvent::Brutalized { location, .. } => Some(*location),
-            TurnReportEvent::FollowersLost { location, .. } => Some(*location),
-            TurnReportEvent::Sacrificed { location, .. } => Some(*location),
-            TurnReportEvent::SignSeen { location, .. } => Some(*location),
-            TurnReportEvent::GameOver { .. } => None,
-            TurnReportEvent::NewTurn { .. } => None,
-        }
-    }
-
-    fn get_sections(&self) -> Vec<String> {
-        match self {
-            TurnReportEvent::GameStart { player_names } => vec![format!(
-                "The campaign has begun! {} players have joined.",
-                player_names.len()
-            )],
-            TurnReportEvent::AgentAction {
-                location_name,
-                agent_name,
-                action,
-                success_amount,
-                fail_amount,
-                ..
-            } => match action {
-                AgentAction::Brutalize => vec![
-                    format!(
-                        "{} brutalized the locals at {}.\n",
-                        agent_name, location_name
-                    ),
-                    if *success_amount > 0 {
-                        format!(
-                            "{} locals were swayed, growing your power\n",
-                            success_amount
-                        )
-                    } else {
-                        format!("Your brutality failed.\n")
-                    },
-                    if *fail_amount > 0 {
-                        format!(
-                            "{} locals fled or died, limiting your growth.\n",
-                            fail_amount
-                        )
-                    } else {
-                        format!("")
-                    },
-                ],
-                AgentAction::Corrupt => vec![
-                    format!(
-                        "{} enacted a corruption ritual at {}.\n",
-                        agent_name, location_name
-                    ),
-                    if *success_amount > 0 {
-                        format!("Your ritual succeded.\nAfollower is now corrupted.\nYour power at {} grows.\n", location_name)
-                    } else {
-                        format!(
-                            "Your ritual failed.\nYour power at {} wanes.\n",
-                            location_name
-                        )
-                    },
-                    // Actually, these are signs seen, not failures.
-                    if *fail_amount > 0 {
-                        format!("Something wondrous happened...\n")
-                    } else {
-                        format!("")
-                    },
-                ],
-                AgentAction::CorruptAgent => vec![
-                    format!(
-                        "{} enacted a corruption ritual at {}.\n",
-                        agent_name, location_name
-                    ),
-                    if *success_amount > 0 {
-                        format!("Your ritual succeded.\n{} is now corrupted!\n", agent_name)
-                    } else {
-                        format!("Your ritual failed.\nYour corrupted follower parished.\n")
-                    },
-                    // Actually, these are signs seen, not failures.
-                    if *fail_amount > 0 {
-                        format!("Something wondrous happened...\n")
-                    } else {
-                        format!("")
-                    },
-                ],
-                AgentAction::Sacrifice => vec![
-                    format!(
-                        "{} sacrificed a follower at {}.\n\n",
-                        agent_name, location_name
-                    ),
-                    {
-                        if *success_amount > 0 {
-                            format!("Your sacrifice has brought untold power!\n")
-                        } else {
-                            format!("Your ritual failed.")
-                        }
-                    },
-                    {
-                        if *fail_amount > 0 {
-                            format!(
-                                "Your actions send {} potential sign holder{} fleeing.\n",
-                                *fail_amount,
-                                if *fail_amount > 1 { "s" } else { "" }
-                            )
-                        } else {
-                            format!("")
-                        }
-                    },
-                ],
-                AgentAction::Prostelytize => vec![
-                    format!("{} has heard the darkness.\n\n", location_name),
-                    format!("{} followers were gained.\n", success_amount),
-                    format!("{} were too strong to convert, yet.\n", fail_amount),
-                ],
-                AgentAction::Move(_, _, _) => {
-                    vec![format!("{} arrived at\n{}", agent_name, location_name)]
-                }
-                AgentAction::None => vec![format!("???")],
-            },
-            TurnReportEvent::Brutalized {
-                location_name,
-                dead,
-                fleeing,
-                ..
-            } => vec![
-                format!("Heretics enact brutality at {}.\n", location_name),
-                format!("{} locals joined the attackers.\n", dead),
-                format!("{} locals fled or died.\n", fleeing),
-            ],
-            TurnReportEvent::Sacrificed {
-                location_name,
-                follower,
-                ..
-            } => {
-                if *follower {
-                    vec![format!("A follower was sacrificed at {}.", location_name)]
-                } else {
-                    vec![format!("A sacrifice was made at {}.", location_name)]
-                }
-            }
-            TurnReportEvent::FollowersLost { location_name, .. } => vec![format!(
-                "Your followers are being swayed by heretical ideas at {}.",
-                location_name
-            )],
-            TurnReportEvent::AgentSeen { location_name, .. } => vec![
-                format!("An unknown traveler arrives at {}.\n", location_name),
-                format!("Their heretical words capture the attention of your followers.\n"),
-            ],
-            TurnReportEvent::PromotedFollower {
-                location_name,
-                agent_name,
-                ..
-            } => {
-                vec![format!(
-                    "With the absense of leadership at {}, {} has been promoted to an agent!",
-                    location_name, agent_name
-                )]
-            }
-            TurnReportEvent::SignSeen {
-                location_name,
-                mine,
-                ..
-            } => {
-                if *mine {
-                    vec![format!(
-                        "You discovered a Sign of Corruption at {}!\n\nThe coming darkness is nearer!",
-                        location_name
-                    )]
-                } else {
-                    vec![format!(
-                        "The heretics at {} have discovered something...",
-                        location_name
-                    )]
-                }
-            }
-            TurnReportEvent::GameOver { winner, scores } => vec![format!(
-                "Game Over! Player {} wins with {} points!",
-                winner.0 + 1,
-                scores.get(winner).unwrap()
-            )],
-            TurnReportEvent::NewTurn { turn } => vec![
-                format!("A new season begins!\n\n"),
-                format!(
-                    "It has been {} seasons since your campaign has begun.",
-                    turn
-                ),
-            ],
-        }
-    }
-}
-
 #[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TurnReport {
     pub events: Vec<TurnReportEvent>,
@@ -369,8 +75,9 @@ fn hide_turn_report(
 }
 
 fn view_turn_report(
-    player_id: Res<PlayerId>,
+    player_id: Res<LocalPlayer>,
     evokation_state: Res<EvokingState>,
+    hotseat: Res<Hotseat>,
     mut turn_report: ResMut<TurnReport>,
     keyboard: Res<Input<KeyCode>>,
     mut text_query: Query<(&Name, &mut Text, &mut Visibility)>,
@@ -378,6 +85,10 @@ fn view_turn_report(
     mut tiles: Query<&mut MapTile>,
     audio: Res<Audio>,
 ) {
+    if hotseat.passing {
+        // The report waits for its reader.
+        return;
+    }
     if let Some(mut event_id) = turn_report.event_id {
         if keyboard.just_pressed(KeyCode::Space) {
             // Advance the turn report.
@@ -388,7 +99,7 @@ fn view_turn_report(
         } else if keyboard.just_pressed(KeyCode::Escape) {
             // Close the turn report.
             event_id = turn_report.events.len() as u32;
-        } else if keyboard.just_pressed(KeyCode::C) {
+        } else if keyboard.just_pressed(KeyCode::C) && !hotseat.is_hotseat() {
             if let Some(evokation) = evokation_state.get_evokation(&player_id) {
                 evokation.store_evokation(true);
                 #[cfg(target_arch = "wasm32")]
@@ -417,8 +128,8 @@ fn view_turn_report(
                 // });
             }
             match event {
-                TurnReportEvent::GameOver { winner, scores } => {
-                    if *winner == *player_id {
+                TurnReportEvent::GameOver { winner, allies, .. } => {
+                    if *winner == **player_id || allies.contains(&**player_id) {
                         audio.play(assets.win.clone());
                     } else {
                         audio.play(assets.lose.clone());
@@ -455,7 +166,7 @@ fn view_turn_report(
 }
 
 fn update_end_turn_button(
-    player_turn: Res<PlayerTurn>,
+    player_turn: Res<CurrentTurn>,
     mut button_query: Query<(&Name, &mut Visibility)>,
 ) {
     if player_turn.get_unassigned_agents() > 0 {
@@ -2,7 +2,7 @@ use bevy::ui::RelativeCursorPosition;
 
 use crate::prelude::*;
 
-use super::turn_ui::EVOKE_COLOR;
+use super::{followers::spawn_follower_list, treasury::spawn_bribe_button, turn_ui::EVOKE_COLOR};
 
 pub struct UiPlugin;
 
@@ -110,6 +110,20 @@ fn spawn_stat_ui(parent: &mut ChildBuilder, font: Handle<Font>, fancy_font: Hand
                 "Signs",
                 EVOKE_COLOR,
             );
+            spawn_stat_block(
+                parent,
+                font.clone(),
+                fancy_font.clone(),
+                "Tithes",
+                Color::GOLD,
+            );
+            spawn_stat_block(
+                parent,
+                font.clone(),
+                fancy_font.clone(),
+                "Calendar",
+                Color::SILVER,
+            );
         });
 }
 
@@ -203,7 +217,7 @@ fn spawn_agent_action_button(
             image: inactive_action_button.into(),
             ..Default::default()
         },
-        agent_action,
+        ActionButton(agent_action),
         RelativeCursorPosition::default(),
         images,
     ));
@@ -264,6 +278,22 @@ fn spawn_agent_section(
                         AgentAction::Prostelytize,
                         default(),
                     );
+                    spawn_agent_action_button(
+                        parent,
+                        action_buttons["ProstelytizeActive.png"].clone(),
+                        action_buttons["Prostelytize.png"].clone(),
+                        action_buttons["ProstelytizeDeactivated.png"].clone(),
+                        AgentAction::Recruit,
+                        default(),
+                    );
+                    spawn_agent_action_button(
+                        parent,
+                        action_buttons["MoveActive.png"].clone(),
+                        action_buttons["Move.png"].clone(),
+                        action_buttons["MoveDeactivated.png"].clone(),
+                        AgentAction::Search,
+                        default(),
+                    );
                     spawn_agent_action_button(
                         parent,
                         action_buttons["BrutalizeActive.png"].clone(),
@@ -316,6 +346,22 @@ fn spawn_agent_section(
                         AgentAction::Sacrifice,
                         default(),
                     );
+                    spawn_agent_action_button(
+                        parent,
+                        action_buttons["SacrificeActive.png"].clone(),
+                        action_buttons["Sacrifice.png"].clone(),
+                        action_buttons["SacrificeDeactivated.png"].clone(),
+                        AgentAction::GrandSacrifice,
+                        default(),
+                    );
+                    spawn_agent_action_button(
+                        parent,
+                        action_buttons["CorruptAgentActive.png"].clone(),
+                        action_buttons["CorruptAgent.png"].clone(),
+                        action_buttons["CorruptAgentDeactivated.png"].clone(),
+                        AgentAction::DarkCommunion,
+                        default(),
+                    );
                     spawn_agent_action_button(
                         parent,
                         action_buttons["NextActive.png"].clone(),
@@ -354,6 +400,10 @@ fn spawn_area_ui(
             spawn_labeled_value(parent, font.clone(), "Followers", "area_followers");
             spawn_labeled_value(parent, font.clone(), "Your Power", "area_your_power");
             spawn_labeled_value(parent, font.clone(), "Corrupted", "area_corrupted");
+            spawn_labeled_value(parent, font.clone(), "Suspicion", "area_suspicion");
+            spawn_labeled_value(parent, font.clone(), "Omens", "area_events");
+            spawn_follower_list(parent, font.clone());
+            spawn_bribe_button(parent, font.clone());
             spawn_agent_section(parent, font.clone(), action_buttons);
         });
 }
@@ -1,6 +1,4 @@
 mod area;
-mod follower;
 pub mod tiles_2d;
 pub mod tiles_3d;
 pub use area::*;
-pub use follower::*;
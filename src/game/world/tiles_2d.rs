@@ -1,4 +1,5 @@
 use bevy::ui::RelativeCursorPosition;
+use signs_core::map::{BRIDGE, RIVER, ROAD, TEMPLE, WATER};
 
 use crate::prelude::*;
 
@@ -33,62 +34,39 @@ impl Plugin for TilesPlugin {
 }
 
 pub const TILE_SIZE: f32 = 128.;
-pub const WATER_SPRITE_ID: u32 = 5;
-pub const WATER_COUNT: i32 = 7;
+/// The atlas sprite for open water.
+const WATER_SPRITE: usize = 5;
+/// A blank sprite for settlements the atlas has no art for yet.
+const BLANK_SPRITE: usize = 6;
+
+/// The atlas sprite for a map tile. The sea and rivers share the water sprite, and newer tiles
+/// borrow the nearest one until the atlas catches up.
+fn atlas_sprite(tile: u32) -> usize {
+    match tile {
+        WATER | RIVER => WATER_SPRITE,
+        TEMPLE => BLANK_SPRITE,
+        ROAD | BRIDGE => 0,
+        tile => tile as usize,
+    }
+}
 
 fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
-fn load_map(mut commands: Commands, map: Res<MapDesc>, assets: Res<MyAssets>) {
+fn load_map(mut commands: Commands, map: Res<GameMap>, assets: Res<MyAssets>) {
     for y in 0..map.height {
         for x in 0..map.width {
             // spawn_tile_2d(x, y, &map, &mut commands, &assets);
             spawn_tile_2d(x, y, &map, &mut commands, &assets);
         }
     }
-    for y in -WATER_COUNT..(map.height as TileLoc + WATER_COUNT) {
-        for x in -WATER_COUNT..(map.width as TileLoc + WATER_COUNT) {
-            if x >= 0 && x < map.width as TileLoc && y >= 0 && y < map.height as TileLoc {
-                continue;
-            }
-            spawn_water_2d(x, y, &mut commands, &assets);
-        }
-    }
-}
-
-fn spawn_water_2d(x: i32, y: i32, commands: &mut Commands, assets: &Res<MyAssets>) {
-    let (base_x, base_y) = get_world_pos_for_tile(x, y);
-    let sprite_id = WATER_SPRITE_ID;
-    commands.spawn((
-        SpriteSheetBundle {
-            texture_atlas: assets.map.clone(),
-            transform: Transform {
-                translation: Vec3::new(base_x, base_y, 100. + (y - x) as f32),
-                ..Default::default()
-            },
-            sprite: TextureAtlasSprite::new(sprite_id as usize),
-            ..Default::default()
-        },
-        MapTile {
-            selected: false,
-            hovered: false,
-            focused: false,
-            travelling: false,
-            x,
-            y,
-            sprite_id,
-            offset_y: TILE_SIZE,
-            target_y: rand::random::<f32>() * 8.,
-            base_y,
-        },
-    ));
 }
 
 fn spawn_tile_2d(
     x: u32,
     y: u32,
-    map: &Res<MapDesc>,
+    map: &Res<GameMap>,
     commands: &mut Commands,
     assets: &Res<MyAssets>,
 ) {
@@ -101,7 +79,7 @@ fn spawn_tile_2d(
                 translation: Vec3::new(base_x, base_y, 100. + (y as i32 - x as i32) as f32),
                 ..Default::default()
             },
-            sprite: TextureAtlasSprite::new(sprite_id as usize),
+            sprite: TextureAtlasSprite::new(atlas_sprite(sprite_id)),
             ..Default::default()
         },
         MapTile {
@@ -118,13 +96,16 @@ fn spawn_tile_2d(
         },
     ));
     if let Some(area) = map.get_area(x, y) {
-        tile.insert(area.clone());
+        let mut area = area.clone();
+        // Followers in older saves are only numbered once they are loaded.
+        area.identify_followers();
+        tile.insert(MapArea(area));
     }
 }
 
 fn map_tooltip(
     mut is_showing_map_tooltip: Local<bool>,
-    map_query: Query<(&MapTile, &WorldArea)>,
+    map_query: Query<(&MapTile, &MapArea)>,
     mut tooltip: ResMut<Tooltip>,
 ) {
     let mut tooltip_value = None;
@@ -190,8 +171,8 @@ fn map_mouse_system(
     mut mouse_location_timer: Local<(Vec2, f32)>,
     time: Res<Time>,
     camera: Query<(&Camera, &GlobalTransform)>,
-    mut tile_query: Query<(&mut MapTile, Option<&WorldArea>)>,
-    mut agent_action_query: Query<&mut AgentAction>,
+    mut tile_query: Query<(&mut MapTile, Option<&MapArea>)>,
+    mut agent_action_query: Query<&mut ActionButton>,
     ui_query: Query<(&ComputedVisibility, &RelativeCursorPosition)>,
     mut cursor_moved: EventReader<CursorMoved>,
     mouse_button_input: Res<Input<MouseButton>>,
@@ -225,8 +206,8 @@ fn map_mouse_system(
                 } else if m_area.is_some() && mouse_button_input.just_pressed(MouseButton::Right) {
                     audio.play(assets.tile_click.clone());
                     agent_action_query.iter_mut().for_each(|mut action| {
-                        if matches!(*action, AgentAction::Move(_, _, _)) && x >= 0 && y >= 0 {
-                            *action =
+                        if matches!(**action, AgentAction::Move(_, _, _)) && x >= 0 && y >= 0 {
+                            **action =
                                 AgentAction::Move(x as u32, y as u32, m_area.unwrap().name.clone());
                         }
                     });
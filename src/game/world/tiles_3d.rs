@@ -10,6 +10,8 @@ pub struct TileInputState {
     pub hovered: Option<(Entity, TileLoc, TileLoc)>,
     pub traveling: Option<(Entity, TileLoc, TileLoc)>,
     pub noise_timer: f32,
+    /// Set while the keyboard is taken by a text field, so it doesn't also steer the camera.
+    pub typing: bool,
 }
 
 impl TileInputState {
@@ -36,6 +38,12 @@ impl TileInputState {
     }
 }
 
+/// A click on a map tile, for modes that do more with one than select it.
+pub struct TileClicked {
+    pub entity: Entity,
+    pub button: PointerButton,
+}
+
 #[derive(Component)]
 pub struct MapTile {
     pub x: TileLoc,
@@ -50,20 +58,26 @@ pub struct TilesPlugin;
 impl Plugin for TilesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TileInputState>()
+            .add_event::<TileClicked>()
             .add_system(load_map.in_schedule(OnEnter(GameState::Playing)))
+            .add_system(load_map.in_schedule(OnEnter(GameState::Editor)))
             // .add_startup_system(spawn_camera_3d)
             .add_system(spawn_camera_3d.in_schedule(OnEnter(GameState::Playing)))
-            .add_system(raise_map.run_if(in_state(GameState::Playing)))
+            .add_system(spawn_camera_3d.in_schedule(OnEnter(GameState::Editor)))
+            .add_system(raise_map.run_if(on_map()))
             // .add_system(focus_tile.run_if(in_state(GameState::Playing)))
-            .add_system(map_timer_system.run_if(in_state(GameState::Playing)))
+            .add_system(map_timer_system.run_if(on_map()))
             .add_system(map_tooltip.run_if(in_state(GameState::Playing)))
-            .add_system(move_camera.run_if(in_state(GameState::Playing)));
+            .add_system(move_camera.run_if(on_map()));
     }
 }
 
+/// The map is on screen both in play and in the editor.
+fn on_map() -> impl Condition<()> {
+    in_state(GameState::Playing).or_else(in_state(GameState::Editor))
+}
+
 pub const MAP_WAVINESS: f32 = 0.25;
-pub const WATER_SPRITE_ID: u32 = 5;
-pub const WATER_COUNT: i32 = 7;
 
 fn spawn_camera_3d(mut commands: Commands) {
     let mut projection = PerspectiveProjection::default();
@@ -96,7 +110,7 @@ fn spawn_camera_3d(mut commands: Commands) {
 
 fn load_map(
     mut commands: Commands,
-    map: Res<MapDesc>,
+    map: Res<GameMap>,
     assets: Res<MyAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut camera: Query<&mut Transform, With<Camera>>,
@@ -113,14 +127,6 @@ fn load_map(
             spawn_tile_3d(x, y, &map, &mut commands, &assets, pick_mesh.clone());
         }
     }
-    for y in -WATER_COUNT..(map.height as TileLoc + WATER_COUNT) {
-        for x in -WATER_COUNT..(map.width as TileLoc + WATER_COUNT) {
-            if x >= 0 && x < map.width as TileLoc && y >= 0 && y < map.height as TileLoc {
-                continue;
-            }
-            // spawn_water_2d(x, y, &mut commands, &assets);
-        }
-    }
 }
 
 fn on_mouse_over_tile(
@@ -164,12 +170,17 @@ fn on_mouse_out_tile(
 
 fn on_mouse_click_tile(
     In(event): In<ListenedEvent<Click>>,
-    mut tiles: Query<(&mut MapTile, Option<&WorldArea>)>,
+    mut tiles: Query<(&mut MapTile, Option<&MapArea>)>,
     mut state: ResMut<TileInputState>,
     assets: Res<MyAssets>,
     audio: Res<Audio>,
-    mut agent_action_query: Query<&mut AgentAction>,
+    mut agent_action_query: Query<&mut ActionButton>,
+    mut clicks: EventWriter<TileClicked>,
 ) -> Bubble {
+    clicks.send(TileClicked {
+        entity: event.listener,
+        button: event.button,
+    });
     if let Ok((mut tile, m_area)) = tiles.get_mut(event.listener) {
         if m_area.is_some() && event.button == PointerButton::Secondary {
             if state.selected == Some((event.listener, tile.x, tile.y)) {
@@ -178,8 +189,8 @@ fn on_mouse_click_tile(
             state.traveling = Some((event.listener, tile.x, tile.y));
             audio.play(assets.tile_click.clone());
             agent_action_query.iter_mut().for_each(|mut action| {
-                if matches!(*action, AgentAction::Move(_, _, _)) && tile.x >= 0 && tile.y >= 0 {
-                    *action = AgentAction::Move(
+                if matches!(**action, AgentAction::Move(_, _, _)) && tile.x >= 0 && tile.y >= 0 {
+                    **action = AgentAction::Move(
                         tile.x as u32,
                         tile.y as u32,
                         m_area.unwrap().name.clone(),
@@ -203,7 +214,7 @@ fn on_mouse_click_tile(
 fn spawn_tile_3d(
     x: u32,
     y: u32,
-    map: &Res<MapDesc>,
+    map: &Res<GameMap>,
     commands: &mut Commands,
     assets: &Res<MyAssets>,
     pick_mesh: Handle<Mesh>,
@@ -234,22 +245,45 @@ fn spawn_tile_3d(
         OnPointer::<Click>::run_callback(on_mouse_click_tile),
     ));
     if let Some(area) = map.get_area(x, y) {
-        tile.insert(area.clone());
+        let mut area = area.clone();
+        // Followers in older saves are only numbered once they are loaded.
+        area.identify_followers();
+        tile.insert(MapArea(area));
     }
 }
 
 fn map_tooltip(
     mut is_showing_map_tooltip: Local<bool>,
-    area_query: Query<&WorldArea>,
+    area_query: Query<&MapArea>,
     mut tooltip: ResMut<Tooltip>,
     input_state: Res<TileInputState>,
+    (ruleset, season): (Res<GameRuleset>, Res<Season>),
 ) {
     let mut tooltip_value = None;
     if let Some(area) = input_state
         .hovered
         .and_then(|(entity, x, y)| area_query.get(entity).ok())
     {
-        tooltip_value = Some(format!("{} ({})", area.name.clone(), area.get_value()));
+        let mut value = format!(
+            "{} ({}, {})",
+            area.name.clone(),
+            area.kind.name(),
+            area.get_value()
+        );
+        // How the journey from the selected area would tire an agent.
+        if let Some(source) = input_state
+            .selected
+            .and_then(|(entity, _, _)| area_query.get(entity).ok())
+            .filter(|source| source.world_position != area.world_position)
+        {
+            let cost = ruleset.for_season(**season).travel_cost(source, area);
+            if source.road.is_some() && source.road == area.road {
+                value.push_str(&format!("\nBy road, costing {} stamina", cost));
+            } else if cost > 0 {
+                value.push_str(&format!("\nTravel here costs {} stamina", cost));
+            }
+        }
+        tooltip_value = Some(value);
     }
     if *is_showing_map_tooltip && tooltip_value.is_none() {
         *is_showing_map_tooltip = false;
@@ -316,7 +350,11 @@ fn move_camera(
     time: Res<Time>,
     mut camera: Query<(&mut Transform, &Camera)>,
     input: Res<Input<KeyCode>>,
+    input_state: Res<TileInputState>,
 ) {
+    if input_state.typing {
+        return;
+    }
     let (mut transform, _camera) = camera.single_mut();
     let mut direction = Vec3::ZERO;
     if input.pressed(KeyCode::W) {
@@ -1,4 +1,5 @@
 mod assets;
+mod editor;
 mod game;
 mod menu;
 mod monkey;
@@ -6,6 +7,7 @@ mod prelude;
 mod state;
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy_mod_picking::prelude::*;
+use editor::EditorPlugin;
 use game::GamePlugins;
 use menu::MenuPlugin;
 use monkey::BevyUiBackend;
@@ -35,8 +37,10 @@ fn main() {
         .add_plugin(LogDiagnosticsPlugin::default())
         .add_plugin(bevy::diagnostic::EntityCountDiagnosticsPlugin::default())
         .add_plugin(MenuPlugin)
+        .add_plugin(EditorPlugin)
         .add_plugin(assets::GameAssetsPlugin)
         .add_system(despawn_camera.in_schedule(OnEnter(GameState::Playing)))
+        .add_system(despawn_camera.in_schedule(OnEnter(GameState::Editor)))
         .add_startup_system(spawn_menu_camera)
         .add_system(spawn_menu_camera.in_schedule(OnEnter(GameState::MainMenu)))
         .add_system(despawn_menu_camera.in_schedule(OnEnter(GameState::MainMenu)))
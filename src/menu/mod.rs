@@ -2,7 +2,15 @@ use std::path;
 
 use bevy::{input::keyboard::KeyboardInput, utils::HashSet};
 
-use crate::{game::ai::generate_seeds, prelude::*};
+use signs_core::scenario::Scenario;
+
+use crate::{
+    game::{
+        ai::generate_seeds,
+        scenario::{find_scenario, next_scenario},
+    },
+    prelude::*,
+};
 
 pub struct MenuPlugin;
 
@@ -34,6 +42,10 @@ pub struct MenuState {
     pub awaiting_players: bool,
     pub players: HashSet<Joiner>,
     pub ai: u32,
+    /// Guests sharing this device, each taking their turn in hotseat.
+    pub guests: u32,
+    /// The id of the scenario to play, or `None` for a generated map.
+    pub scenario: Option<String>,
 }
 
 fn add_welcome_screen(mut commands: Commands, assets: Res<MyAssets>) {
@@ -141,6 +153,32 @@ fn add_welcome_screen(mut commands: Commands, assets: Res<MyAssets>) {
                         ..Default::default()
                     });
                 });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            border: UiRect::all(Val::Px(ONE_UNIT)),
+                            margin: UiRect::all(Val::Px(ONE_UNIT)),
+                            size: Size::width(Val::Percent(50.)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    MainMenuElement::OpenEditor,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "Map Editor",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 20.,
+                                color: Color::BLACK,
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                });
         });
 }
 
@@ -148,6 +186,36 @@ fn add_welcome_screen(mut commands: Commands, assets: Res<MyAssets>) {
 pub struct Joiner {
     name: String,
     seed: u32,
+    /// The scenario this player wants to play, so the lobby can tell whether everyone agrees.
+    /// Sent after the joiner itself, see `to_bytes`.
+    #[serde(skip)]
+    scenario: Option<String>,
+}
+
+impl Joiner {
+    /// The scenario rides after the joiner itself, so runes from before scenarios still read.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = postcard::to_allocvec(self).unwrap();
+        if self.scenario.is_some() {
+            bytes.extend(postcard::to_allocvec(&self.scenario).unwrap());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Joiner, String> {
+        let (mut joiner, rest) =
+            postcard::take_from_bytes::<Joiner>(bytes).map_err(|e| e.to_string())?;
+        joiner.scenario = postcard::from_bytes(rest).unwrap_or_default();
+        Ok(joiner)
+    }
+
+    fn store(&self) -> Option<String> {
+        store_bytes_in_runes(&self.to_bytes(), true)
+    }
+
+    fn retrieve() -> Result<Joiner, String> {
+        retrieve_bytes_from_runes().and_then(|bytes| Self::from_bytes(&bytes))
+    }
 }
 
 fn add_new_game_screen(mut commands: Commands, assets: Res<MyAssets>) {
@@ -237,6 +305,14 @@ fn add_new_game_screen(mut commands: Commands, assets: Res<MyAssets>) {
                                 color: Color::WHITE,
                             },
                         },
+                        TextSection {
+                            value: map_description(None),
+                            style: TextStyle {
+                                font: assets.font.clone(),
+                                font_size: FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        },
                         TextSection {
                             value:
                                 "All players must include all other players, and add the same number of AI.\n\n"
@@ -298,6 +374,75 @@ fn add_new_game_screen(mut commands: Commands, assets: Res<MyAssets>) {
                     ..Default::default()
                 });
             });
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                        margin: UiRect::all(Val::Px(ONE_UNIT)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                MainMenuElement::AddGuest,
+            )).with_children(|parent| {
+                parent.spawn(TextBundle {
+                    text: Text::from_section(
+                        "Add Hotseat Guest",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 20.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..Default::default()
+                });
+            });
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                        margin: UiRect::all(Val::Px(ONE_UNIT)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                MainMenuElement::RemoveGuest,
+            )).with_children(|parent| {
+                parent.spawn(TextBundle {
+                    text: Text::from_section(
+                        "Remove Hotseat Guest",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 20.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..Default::default()
+                });
+            });
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        border: UiRect::all(Val::Px(ONE_UNIT)),
+                        margin: UiRect::all(Val::Px(ONE_UNIT)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                MainMenuElement::ChangeScenario,
+            )).with_children(|parent| {
+                parent.spawn(TextBundle {
+                    text: Text::from_section(
+                        "Change Map",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 20.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..Default::default()
+                });
+            });
             parent.spawn((
                 ButtonBundle {
                     style: Style {
@@ -381,10 +526,54 @@ enum MainMenuElement {
     NameField,
     AddAi,
     RemoveAi,
+    AddGuest,
+    RemoveGuest,
+    ChangeScenario,
     ConfirmPlayers,
     PlayerList,
     LoadGame,
+    OpenEditor,
 }
+
+fn guest_name(guest: u32) -> String {
+    format!("Guest {}", guest + 1)
+}
+
+fn map_description(scenario: Option<&Scenario>) -> String {
+    match scenario {
+        Some(scenario) => format!(
+            "Map: {}, for {} players\n{}\n\n",
+            scenario.name,
+            scenario.seats.len(),
+            scenario.description
+        ),
+        None => "Map: generated for any number of players\n\n".to_string(),
+    }
+}
+
+/// Everyone in the lobby, with the map they want when it differs from ours.
+fn player_list(menu_state: &MenuState) -> String {
+    let mut joiners: Vec<&Joiner> = menu_state.players.iter().collect();
+    joiners.sort_by(|a, b| a.name.cmp(&b.name));
+    joiners
+        .into_iter()
+        .map(|joiner| {
+            if joiner.scenario == menu_state.scenario {
+                format!("{}\n", joiner.name)
+            } else {
+                let wanted = joiner
+                    .scenario
+                    .as_deref()
+                    .and_then(find_scenario)
+                    .map(|scenario| scenario.name.as_str())
+                    .unwrap_or("a generated map");
+                format!("{} (wants {})\n", joiner.name, wanted)
+            }
+        })
+        .chain((0..menu_state.guests).map(|guest| format!("{} (hotseat)\n", guest_name(guest))))
+        .collect()
+}
+
 fn watch_for_players(
     mut cooldown: Local<f32>,
     time: Res<Time>,
@@ -402,18 +591,56 @@ fn watch_for_players(
         menu_state.awaiting_name = false;
         return;
     }
+    let mut warning = None;
     for (element, interaction) in interactions.iter() {
         if *element == MainMenuElement::AddAi && *interaction == Interaction::Clicked {
             menu_state.ai += 1;
         } else if *element == MainMenuElement::RemoveAi && *interaction == Interaction::Clicked {
             menu_state.ai = menu_state.ai.saturating_sub(1);
+        } else if *element == MainMenuElement::AddGuest && *interaction == Interaction::Clicked {
+            menu_state.guests += 1;
+        } else if *element == MainMenuElement::RemoveGuest && *interaction == Interaction::Clicked {
+            menu_state.guests = menu_state.guests.saturating_sub(1);
+        } else if *element == MainMenuElement::ChangeScenario
+            && *interaction == Interaction::Clicked
+        {
+            menu_state.scenario =
+                next_scenario(menu_state.scenario.as_deref()).map(|scenario| scenario.id.clone());
+            let name = menu_state.name.trim().to_string();
+            if let Some(mut joiner) = menu_state
+                .players
+                .iter()
+                .find(|joiner| joiner.name == name)
+                .cloned()
+            {
+                // Hand the other players new runes that carry the choice.
+                menu_state.players.remove(&joiner);
+                joiner.scenario = menu_state.scenario.clone();
+                menu_state.players.insert(joiner.clone());
+                joiner.store();
+            }
         } else if *element == MainMenuElement::ConfirmPlayers
             && *interaction == Interaction::Clicked
         {
+            if menu_state
+                .players
+                .iter()
+                .any(|joiner| joiner.scenario != menu_state.scenario)
+            {
+                warning = Some("All players must choose the same map.\n\n".to_string());
+                continue;
+            } else if menu_state.guests > 0 && menu_state.players.len() > 1 {
+                warning = Some(
+                    "Guests can only join a game played on this device alone.\n\n".to_string(),
+                );
+                continue;
+            }
+            let guests: Vec<String> = (0..menu_state.guests).map(guest_name).collect();
             let mut players: Vec<String> = menu_state
                 .players
                 .iter()
                 .map(|joiner| joiner.name.to_string())
+                .chain(guests.iter().cloned())
                 .collect();
             players.sort();
             let game_players = GamePlayers::new(players.clone(), menu_state.ai);
@@ -424,13 +651,45 @@ fn watch_for_players(
                 .unwrap()
                 .0
                 .into();
+            let (map, ruleset) = match menu_state.scenario.as_deref().and_then(find_scenario) {
+                Some(scenario) => match scenario.map_desc(game_players.get_ids()) {
+                    Ok(map) => (map, scenario.ruleset(&Ruleset::default())),
+                    Err(err) => {
+                        warning = Some(format!("{}\n\n", err));
+                        continue;
+                    }
+                },
+                None => {
+                    let ruleset = Ruleset::default();
+                    (generate_map(game_players.get_ids(), &ruleset), ruleset)
+                }
+            };
             commands.insert_resource(generate_seeds(players, menu_state.ai));
-            commands.insert_resource(generate_map(game_players.get_ids()));
-            commands.insert_resource(PlayerTurn::new(my_player));
-            commands.insert_resource(my_player);
+            commands.insert_resource(GameMap(map));
+            commands.insert_resource(GameRuleset(ruleset));
+            let seats = game_players
+                .get_ids()
+                .into_iter()
+                .filter(|player| {
+                    *player == my_player
+                        || game_players
+                            .get_name(*player)
+                            .is_some_and(|name| guests.contains(name))
+                })
+                .collect::<Vec<_>>();
+            // The first seat at the table plays first.
+            let hotseat = Hotseat::new(seats);
+            let first = hotseat.seats[0];
+            commands.insert_resource(CurrentTurn(PlayerTurn::new(first)));
+            commands.insert_resource(LocalPlayer(first));
+            commands.insert_resource(hotseat);
             commands.insert_resource(game_players);
             commands.insert_resource(MenuState::default());
             commands.insert_resource(Season(1));
+            commands.insert_resource(GamePacts::default());
+            commands.insert_resource(GameStandingOrders::default());
+            commands.insert_resource(GameTreasury::default());
+            commands.insert_resource(GameAgentNumbers::default());
             #[cfg(target_arch = "wasm32")]
             hide_clipboard();
             next_state.set(GameState::Playing);
@@ -438,23 +697,26 @@ fn watch_for_players(
     }
     for (element, mut text) in text.iter_mut() {
         if *element == MainMenuElement::PlayerList {
+            text.sections[1].value = player_list(&menu_state);
             text.sections[2].value = format!("{} AI Players\n\n", menu_state.ai);
+            text.sections[3].value =
+                map_description(menu_state.scenario.as_deref().and_then(find_scenario));
+            if let Some(warning) = warning.take() {
+                text.sections[4].value = warning;
+            }
         }
     }
     if *cooldown < 0. {
         *cooldown = 1.;
-        if let Ok(joiner) = retrieve_from_runes::<Joiner>() {
+        if let Ok(joiner) = Joiner::retrieve() {
             if menu_state.players.contains(&joiner) {
                 return;
             } else {
-                menu_state.players.insert(joiner.clone());
-                for (element, mut text) in text.iter_mut() {
-                    if *element == MainMenuElement::PlayerList {
-                        text.sections[1]
-                            .value
-                            .push_str(&format!("{}\n", joiner.name));
-                    }
-                }
+                // A player who changes their map sends new runes under the same name.
+                menu_state
+                    .players
+                    .retain(|player| player.name != joiner.name);
+                menu_state.players.insert(joiner);
             }
         }
     } else {
@@ -476,6 +738,7 @@ fn handle_typing(
             let joiner = Joiner {
                 name: name.clone(),
                 seed: rand::thread_rng().gen(),
+                scenario: menu_state.scenario.clone(),
             };
 
             menu_state.players = HashSet::new();
@@ -488,7 +751,7 @@ fn handle_typing(
                 }
             }
 
-            store_in_runes(joiner, true);
+            joiner.store();
             switch_menu(MainMenu::NewGame, &mut menus);
         } else if keyboard_input.just_pressed(KeyCode::Escape) {
             menu_state.awaiting_name = false;
@@ -522,7 +785,7 @@ fn handle_typing(
                 .iter()
                 .find(|joiner| joiner.name.eq(&menu_state.name))
                 .unwrap();
-            store_in_runes(joiner, true);
+            joiner.store();
         }
     }
 }
@@ -567,37 +830,21 @@ fn handle_button_clicks(
                     menu_state.awaiting_name = true;
                     switch_menu(MainMenu::EnterName, &mut menus);
                 }
+                MainMenuElement::OpenEditor => {
+                    // Start from a generated map for two, to paint over.
+                    commands.insert_resource(GameMap(generate_map(
+                        vec![PlayerId(0), PlayerId(1)],
+                        &Ruleset::default(),
+                    )));
+                    next_state.set(GameState::Editor);
+                }
                 #[cfg(not(target_arch = "wasm32"))]
                 MainMenuElement::LoadGame => {
                     let save = load_save();
                     if let Some(save) = save {
                         let save: SaveData = serde_json::from_str(&save).unwrap();
-                        commands.insert_resource(save.players.clone());
-                        commands.insert_resource(save.player_id.clone());
-                        commands.insert_resource(save.ai_seeds.clone());
-                        commands.insert_resource(save.map_desc);
-                        commands.insert_resource(save.turn_report);
-                        if let Some(evokation) = save
-                            .last_evokation
-                            .and_then(|evokation| read_from_runes::<Evokation>(&evokation, false))
-                        {
-                            if evokation.season == *save.season {
-                                commands.insert_resource(EvokingState::resume(
-                                    evokation.clone(),
-                                    &save.players,
-                                ));
-                                commands.insert_resource(evokation.player_turn);
-                            } else {
-                                commands.insert_resource(EvokingState::None {
-                                    last_evokation: Some(evokation),
-                                });
-                                commands.insert_resource(PlayerTurn::new(save.player_id));
-                            }
-                        } else {
-                            commands.insert_resource(PlayerTurn::new(save.player_id));
-                        }
+                        save.restore(&mut commands);
                         commands.insert_resource(MenuState::default());
-                        commands.insert_resource(save.season);
                         next_state.set(GameState::Playing);
                     }
                 }
@@ -625,3 +872,25 @@ fn load_save() -> Option<String> {
 fn load_save() -> Option<String> {
     None
 }
+
+#[cfg(test)]
+mod joiner_tests {
+    use super::*;
+
+    #[test]
+    fn test_joiner_scenario() {
+        // Runes sent before scenarios could be chosen: "Ada", seed 7.
+        let joiner = Joiner::from_bytes(&parse_runes("diqiwq4a", false)).unwrap();
+        assert_eq!(joiner.name, "Ada");
+        assert_eq!(joiner.seed, 7);
+        assert_eq!(joiner.scenario, None);
+        assert_eq!(generate_runes(&joiner.to_bytes(), false), "diqiwq4a");
+
+        let joiner = Joiner {
+            scenario: Some("crossroads".to_string()),
+            ..joiner
+        };
+        let runes = generate_runes(&joiner.to_bytes(), true);
+        assert_eq!(Joiner::from_bytes(&parse_runes(&runes, true)), Ok(joiner));
+    }
+}
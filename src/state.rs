@@ -6,6 +6,7 @@ pub enum GameState {
     MainMenu,
     Playing,
     Summary,
+    Editor,
 }
 
 impl Default for GameState {